@@ -0,0 +1,87 @@
+// Loading a big logbook: 2,000 routes and 50,000 sends in a SQLite file
+// Run with `cargo bench -p climb-log-core`
+use chrono::{Duration, NaiveDate};
+use criterion::{criterion_group, criterion_main, Criterion};
+use sea_orm::{ActiveValue, Database, EntityTrait};
+use tokio::runtime::Runtime;
+use climb_log_core::{climbing::{Discipline, Grade, SendType, Yosemite}, conversion, logbook::LogbookData, routes_db::{entities::{grades, routes, sends}, RoutesDb}, settings::Settings, store::LogbookStore};
+
+const ROUTES: i32 = 2_000;
+const SENDS: i32 = 50_000;
+const SENDS_PER_SESSION: i32 = 5;
+
+fn runtime() -> Runtime {
+    tokio::runtime::Builder::new_current_thread().build().unwrap()
+}
+
+async fn synthetic_logbook() -> RoutesDb {
+    // A fresh file every run, so the numbers don't depend on what was left behind
+    let path = std::env::temp_dir().join("climb_log_bench.db");
+    let _ = std::fs::remove_file(&path);
+    let url = format!("sqlite:{}?mode=rwc", path.display());
+    let db = RoutesDb::open(&url).await.unwrap();
+
+    // Filled straight through the entities, going through add_send would take minutes
+    let conn = Database::connect(&url).await.unwrap();
+    // Every real Yosemite grade, so the stats parse them like any logbook's
+    let all_grades: Vec<grades::ActiveModel> = Yosemite::iter().map(|grade| grades::ActiveModel {
+        id: ActiveValue::NotSet,
+        ..conversion::grade_row(Grade::Yosemite(grade)).into()
+    }).collect();
+    let grade_count = i32::try_from(all_grades.len()).unwrap();
+    grades::Entity::insert_many(all_grades).exec(&conn).await.unwrap();
+
+    let all_routes: Vec<routes::ActiveModel> = (1..=ROUTES).map(|id| routes::ActiveModel {
+        name: ActiveValue::Set(format!("Route {id}")),
+        length: ActiveValue::Set(3000),
+        pitches: ActiveValue::Set(1),
+        grade_id: ActiveValue::Set(id % grade_count + 1),
+        location: ActiveValue::Set(Some(format!("Crag {}", id % 40))),
+        discipline: ActiveValue::Set(Discipline::TallWall.to_string()),
+        ..Default::default()
+    }).collect();
+    for chunk in all_routes.chunks(500) {
+        routes::Entity::insert_many(chunk.to_vec()).exec(&conn).await.unwrap();
+    }
+
+    let start = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap();
+    let all_sends: Vec<sends::ActiveModel> = (0..SENDS).map(|id| {
+        let session = id / SENDS_PER_SESSION + 1;
+        let send_type = if id % 3 == 0 { SendType::Flash } else { SendType::Redpoint };
+        sends::ActiveModel {
+            session: ActiveValue::Set(session),
+            date: ActiveValue::Set((start + Duration::days(i64::from(session / 3))).format("%Y-%m-%d").to_string()),
+            r#type: ActiveValue::Set(send_type.to_string()),
+            attempts: ActiveValue::Set(id % 4 + 1),
+            notes: ActiveValue::Set(None),
+            route: ActiveValue::Set(id * 7 % ROUTES + 1),
+            personal_grade: ActiveValue::Set(None),
+            climber: ActiveValue::Set(1),
+            ..Default::default()
+        }
+    }).collect();
+    for chunk in all_sends.chunks(1_000) {
+        sends::Entity::insert_many(chunk.to_vec()).exec(&conn).await.unwrap();
+    }
+    db
+}
+
+fn big_logbook(c: &mut Criterion) {
+    let rt = runtime();
+    let db = rt.block_on(synthetic_logbook());
+    let settings = Settings::default();
+
+    c.bench_function("find_all_routes_and_grade", |b| b.iter(|| rt.block_on(db.clone().find_all_routes_and_grade()).unwrap()));
+    c.bench_function("find_route_and_grade", |b| b.iter(|| rt.block_on(db.clone().find_route_and_grade("Route 1234")).unwrap()));
+    c.bench_function("load_logbook", |b| b.iter(|| rt.block_on(LogbookData::load(db.clone())).unwrap()));
+
+    let data = rt.block_on(LogbookData::load(db.clone())).unwrap();
+    c.bench_function("summary", |b| b.iter(|| data.logbook(&settings).summary(&data.route_styles, &data.partners, &data.send_partners)));
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = big_logbook
+}
+criterion_main!(benches);
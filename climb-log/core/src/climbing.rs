@@ -0,0 +1,2410 @@
+use std::fmt;
+use crate::conversion::{self, GradeSystem};
+
+/// Style of a route, a route can have several.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Style {
+    Boulder,
+    TopRope,
+    Sport,
+    Trad,
+    Ice,
+    Alpine,
+    Aid,
+    Speed,
+    FreeSolo,
+    DeepWater,
+}
+impl std::fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Style::Boulder => write!(f, "Boulder"),
+            Style::TopRope => write!(f, "Top Rope"),
+            Style::Sport => write!(f, "Sport"),
+            Style::Trad => write!(f, "Trad"),
+            Style::Ice => write!(f, "Ice"),
+            Style::Alpine => write!(f, "Alpine"),
+            Style::Aid => write!(f, "Aid"),
+            Style::Speed => write!(f, "Speed"),
+            Style::FreeSolo => write!(f, "Free Solo"),
+            Style::DeepWater => write!(f, "Deep Water"),
+        }
+    }
+}
+impl std::str::FromStr for Style { //Inverse of Display, used for the styles table
+    type Err = String;
+
+    fn from_str(style: &str) -> Result<Style, String> {
+        Style::iter()
+            .find(|candidate| candidate.to_string() == style.trim())
+            .ok_or_else(|| format!("Unknown style: {style}"))
+    }
+}
+impl Style {
+    /// Every style, in the order they're offered when adding a route.
+    pub fn iter() -> impl Iterator<Item = Style> {
+        [
+            Style::Boulder,
+            Style::TopRope,
+            Style::Sport,
+            Style::Trad,
+            Style::Ice,
+            Style::Alpine,
+            Style::Aid,
+            Style::Speed,
+            Style::FreeSolo,
+            Style::DeepWater,
+        ].iter().copied()
+    }
+}
+
+/// Boulders and everything roped are graded and counted separately.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Discipline {
+    Boulder,
+    #[default]
+    TallWall,
+}
+impl std::fmt::Display for Discipline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Discipline::Boulder => write!(f, "Boulder"),
+            Discipline::TallWall => write!(f, "Tall Wall"),
+        }
+    }
+}
+impl std::str::FromStr for Discipline { //Inverse of Display, used for the routes.discipline column
+    type Err = String;
+
+    fn from_str(discipline: &str) -> Result<Discipline, String> {
+        match discipline.trim() {
+            "Boulder" => Ok(Discipline::Boulder),
+            "Tall Wall" => Ok(Discipline::TallWall),
+            _ => Err(format!("Unknown discipline: {discipline}")),
+        }
+    }
+}
+
+
+/// A grade in any of the supported systems.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Grade {
+    Yosemite(Yosemite), //5.7, 5.8, 5.9, etc
+    Font(Font), //4-, 4, 4+, 5-, 5, 5+, then 6A-, 6A, 6A+, 6B-, 6B+, 6C-, 6C, 6C+, 7A-, etc.
+    Hueco(Hueco), //VB, V0, V1, etc
+    French(French), //4a, 4b, 4c, 5a, 5b, 5c, etc.
+    Uiaa(Uiaa), //I, II, III, IV, V, VI, etc. with + and - as needed
+    British(British), //VS 4c, HVS 5a, E1 5b, etc.
+    Ewbank(Ewbank), //14, 15, 16, etc.
+    Saxon(Saxon), //VIIa, VIIb, VIIc, etc.
+    Brazilian(Brazilian), //VIsup, 7a, 7b, etc.
+    Kurtyka(Kurtyka), //VI.1, VI.1+, VI.2, etc.
+    //add others as needed, but these are most common
+}
+impl std::fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Grade::Yosemite(grade) => write!(f, "{grade}"),
+            Grade::Font(grade) => write!(f, "{grade}"),
+            Grade::Hueco(grade) => write!(f, "{grade}"),
+            Grade::French(grade) => write!(f, "{grade}"),
+            Grade::Uiaa(grade) => write!(f, "{grade}"),
+            Grade::British(grade) => write!(f, "{grade}"),
+            Grade::Ewbank(grade) => write!(f, "{grade}"),
+            Grade::Saxon(grade) => write!(f, "{grade}"),
+            Grade::Brazilian(grade) => write!(f, "{grade}"),
+            Grade::Kurtyka(grade) => write!(f, "{grade}"),
+        }
+    }
+}
+
+/// One grade in every free climbing system, like a row of the grades table.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct FullGrade {
+    pub yosemite: Yosemite,
+    pub font: Font,
+    pub hueco: Hueco,
+    pub french: French,
+    pub uiaa: Uiaa,
+    pub british: British,
+    pub ewbank: Ewbank,
+    pub saxon: Saxon,
+    pub brazilian: Brazilian,
+    pub kurtyka: Kurtyka,
+}
+
+/// Text that isn't a grade in the system it was parsed as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GradeParseError {
+    pub system: &'static str,
+    pub input: String,
+}
+impl GradeParseError {
+    fn new(system: &'static str, input: &str) -> GradeParseError {
+        GradeParseError { system, input: input.to_string() }
+    }
+}
+impl std::fmt::Display for GradeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\" is not a valid {} grade", self.input, self.system)
+    }
+}
+impl std::error::Error for GradeParseError {}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Modifier { //Trailing + or - on a grade
+    Minus,
+    None,
+    Plus,
+}
+
+fn normalize(grade: &str) -> String { //Drop whitespace so "5.10 a" and " V5 " still parse
+    grade.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+fn split_modifier(grade: &str) -> (&str, Modifier) {
+    if let Some(rest) = grade.strip_suffix('+') {
+        (rest, Modifier::Plus)
+    } else if let Some(rest) = grade.strip_suffix('-') {
+        (rest, Modifier::Minus)
+    } else {
+        (grade, Modifier::None)
+    }
+}
+
+/// Slash grade or range like "5.10c/d" or "V4-5", low and high are the same for a single grade.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GradeRange<G> {
+    pub low: G,
+    pub high: G,
+}
+impl<G: Copy + PartialOrd> GradeRange<G> {
+    /// A range between two grades, ends given the wrong way round are swapped.
+    pub fn new(low: G, high: G) -> GradeRange<G> {
+        if high < low { GradeRange { low: high, high: low } } else { GradeRange { low, high } }
+    }
+
+    /// A single grade, not a range.
+    pub fn single(grade: G) -> GradeRange<G> {
+        GradeRange { low: grade, high: grade }
+    }
+
+    /// Whether the ends differ.
+    pub fn is_range(&self) -> bool {
+        self.low != self.high
+    }
+}
+impl<G: fmt::Display + PartialEq> std::fmt::Display for GradeRange<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (low, high) = (self.low.to_string(), self.high.to_string());
+        if self.low == self.high {
+            return write!(f, "{low}");
+        }
+        //Only the letter differs, "5.10c/d" or "6a/b"
+        let (low_start, low_end) = low.split_at(low.len() - low.chars().last().map_or(0, char::len_utf8));
+        let (high_start, high_end) = high.split_at(high.len() - high.chars().last().map_or(0, char::len_utf8));
+        if low_start == high_start && low_end.chars().all(|c| c.is_ascii_alphabetic()) && high_end.chars().all(|c| c.is_ascii_alphabetic()) {
+            return write!(f, "{low}/{high_end}");
+        }
+        //Only the number differs, "V4-5"
+        let low_prefix = low.trim_end_matches(|c: char| c.is_ascii_digit());
+        let high_prefix = high.trim_end_matches(|c: char| c.is_ascii_digit());
+        if !low_prefix.is_empty() && low_prefix == high_prefix && low_prefix.chars().all(|c| c.is_ascii_alphabetic()) {
+            return write!(f, "{low}-{}", &high[high_prefix.len()..]);
+        }
+        write!(f, "{low}/{high}")
+    }
+}
+impl<G: std::str::FromStr<Err = GradeParseError> + Copy + PartialOrd> std::str::FromStr for GradeRange<G> {
+    type Err = GradeParseError;
+
+    fn from_str(range: &str) -> Result<GradeRange<G>, GradeParseError> { //Accepts "5.10c/d", "5.10a-c", "V4-5", "6a+/6b" and single grades
+        let text = normalize(range);
+        for (index, _) in text.char_indices().filter(|(_, c)| *c == '/' || *c == '-') {
+            let (low, high) = (&text[..index], &text[index + 1..]);
+            if low.is_empty() || high.is_empty() {
+                continue; //A trailing "-" is a minus grade, not a range
+            }
+            let Ok(low_grade) = G::from_str(low) else { continue };
+            if let Some(high_grade) = complete_high(low, high) {
+                return Ok(GradeRange::new(low_grade, high_grade));
+            }
+        }
+        G::from_str(&text).map(GradeRange::single)
+    }
+}
+
+fn complete_high<G: std::str::FromStr>(low: &str, high: &str) -> Option<G> { //Fill in the top of a shortened range from the bottom, "5.10c" and "d" give "5.10d"
+    if let Ok(grade) = G::from_str(high) {
+        return Some(grade);
+    }
+    [low, low.trim_end_matches(['+', '-'])].into_iter().find_map(|base| {
+        (1..=high.len().min(base.len())).rev()
+            .filter(|&cut| base.is_char_boundary(base.len() - cut))
+            .find_map(|cut| G::from_str(&format!("{}{high}", &base[..base.len() - cut])).ok())
+    })
+}
+
+/// Yosemite Decimal System, the stored grade for roped routes.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Yosemite {
+    One,
+    Two,
+    Three,
+    Four,
+    FiveZero,
+    FiveOne,
+    FiveTwo,
+    FiveThree,
+    FiveFour,
+    FiveFive,
+    FiveSix,
+    FiveSeven,
+    FiveEight,
+    #[default]
+    FiveNine,
+    FiveTenA,
+    FiveTenB,
+    FiveTenC,
+    FiveTenD,
+    FiveElevenA,
+    FiveElevenB,
+    FiveElevenC,
+    FiveElevenD,
+    FiveTwelveA,
+    FiveTwelveB,
+    FiveTwelveC,
+    FiveTwelveD,
+    FiveThirteenA,
+    FiveThirteenB,
+    FiveThirteenC,
+    FiveThirteenD,
+    FiveFourteenA,
+    FiveFourteenB,
+    FiveFourteenC,
+    FiveFourteenD,
+    FiveFifteenA,
+    FiveFifteenB,
+    FiveFifteenC,
+    FiveFifteenD,
+    //add more as needed
+    None,
+}
+impl std::fmt::Display for Yosemite {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Yosemite::One => write!(f, "1"),
+            Yosemite::Two => write!(f, "2"),
+            Yosemite::Three => write!(f, "3"),
+            Yosemite::Four => write!(f, "4"),
+            Yosemite::FiveZero => write!(f, "5.0"),
+            Yosemite::FiveOne => write!(f, "5.1"),
+            Yosemite::FiveTwo => write!(f, "5.2"),
+            Yosemite::FiveThree => write!(f, "5.3"),
+            Yosemite::FiveFour => write!(f, "5.4"),
+            Yosemite::FiveFive => write!(f, "5.5"),
+            Yosemite::FiveSix => write!(f, "5.6"),
+            Yosemite::FiveSeven => write!(f, "5.7"),
+            Yosemite::FiveEight => write!(f, "5.8"),
+            Yosemite::FiveNine => write!(f, "5.9"),
+            Yosemite::FiveTenA => write!(f, "5.10a"),
+            Yosemite::FiveTenB => write!(f, "5.10b"),
+            Yosemite::FiveTenC => write!(f, "5.10c"),
+            Yosemite::FiveTenD => write!(f, "5.10d"),
+            Yosemite::FiveElevenA => write!(f, "5.11a"),
+            Yosemite::FiveElevenB => write!(f, "5.11b"),
+            Yosemite::FiveElevenC => write!(f, "5.11c"),
+            Yosemite::FiveElevenD => write!(f, "5.11d"),
+            Yosemite::FiveTwelveA => write!(f, "5.12a"),
+            Yosemite::FiveTwelveB => write!(f, "5.12b"),
+            Yosemite::FiveTwelveC => write!(f, "5.12c"),
+            Yosemite::FiveTwelveD => write!(f, "5.12d"),
+            Yosemite::FiveThirteenA => write!(f, "5.13a"),
+            Yosemite::FiveThirteenB => write!(f, "5.13b"),
+            Yosemite::FiveThirteenC => write!(f, "5.13c"),
+            Yosemite::FiveThirteenD => write!(f, "5.13d"),
+            Yosemite::FiveFourteenA => write!(f, "5.14a"),
+            Yosemite::FiveFourteenB => write!(f, "5.14b"),
+            Yosemite::FiveFourteenC => write!(f, "5.14c"),
+            Yosemite::FiveFourteenD => write!(f, "5.14d"),
+            Yosemite::FiveFifteenA => write!(f, "5.15a"),
+            Yosemite::FiveFifteenB => write!(f, "5.15b"),
+            Yosemite::FiveFifteenC => write!(f, "5.15c"),
+            Yosemite::FiveFifteenD => write!(f, "5.15d"),
+            Yosemite::None => write!(f, "None"),
+        }
+    }
+}
+impl std::convert::From<Hueco> for Yosemite { //Note, since this is between bouldering and big wall, it's not a solid conversion
+    fn from(hueco: Hueco) -> Yosemite {
+        match conversion::convert(Grade::Hueco(hueco), GradeSystem::Yosemite) {
+            Some(Grade::Yosemite(grade)) => grade,
+            _ => Yosemite::None,
+        }
+    }
+}
+impl std::convert::From<French> for Yosemite { //Note, conversions are always rough between systems
+    fn from(french: French) -> Yosemite {
+        match conversion::convert(Grade::French(french), GradeSystem::Yosemite) {
+            Some(Grade::Yosemite(grade)) => grade,
+            _ => Yosemite::None,
+        }
+    }
+}
+impl std::convert::From<Uiaa> for Yosemite {
+    fn from(uiaa: Uiaa) -> Yosemite {
+        match conversion::convert(Grade::Uiaa(uiaa), GradeSystem::Yosemite) {
+            Some(Grade::Yosemite(grade)) => grade,
+            _ => Yosemite::None,
+        }
+    }
+}
+impl std::convert::From<Font> for Yosemite { //Note, bouldering grades are not a direct conversion to big wall
+    fn from(font: Font) -> Yosemite {
+        match conversion::convert(Grade::Font(font), GradeSystem::Yosemite) {
+            Some(Grade::Yosemite(grade)) => grade,
+            _ => Yosemite::None,
+        }
+    }
+}
+impl std::str::FromStr for Yosemite { //Accepts the displayed grades, plus letterless grades like "5.10-", "5.10" and "5.10+"
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Yosemite, GradeParseError> {
+        let text = normalize(grade).to_lowercase();
+        if let Some(found) = Yosemite::iter().find(|found| found.to_string() == text) {
+            return Ok(found);
+        }
+        let error = || GradeParseError::new("Yosemite", grade);
+        let rest = text.strip_prefix("5.").ok_or_else(error)?;
+        let (number, modifier) = split_modifier(rest);
+        let number: i32 = number.parse().map_err(|_| error())?;
+        let letter = match modifier { // 5.10- covers a/b, 5.10 covers b/c and 5.10+ covers c/d, the easier letter is used
+            Modifier::Minus => "a",
+            Modifier::None => "b",
+            Modifier::Plus => "c",
+        };
+        let full = if number < 10 { format!("5.{number}") } else { format!("5.{number}{letter}") };
+        Yosemite::iter().find(|found| found.to_string() == full).ok_or_else(error)
+    }
+}
+impl std::convert::From<i32> for Yosemite {
+    fn from(grade: i32) -> Yosemite {
+        match grade {
+            0 => Yosemite::One,
+            1 => Yosemite::Two,
+            2 => Yosemite::Three,
+            3 => Yosemite::Four,
+            4 => Yosemite::FiveZero,
+            5 => Yosemite::FiveOne,
+            6 => Yosemite::FiveTwo,
+            7 => Yosemite::FiveThree,
+            8 => Yosemite::FiveFour,
+            9 => Yosemite::FiveFive,
+            10 => Yosemite::FiveSix,
+            11 => Yosemite::FiveSeven,
+            12 => Yosemite::FiveEight,
+            13 => Yosemite::FiveNine,
+            14 => Yosemite::FiveTenA,
+            15 => Yosemite::FiveTenB,
+            16 => Yosemite::FiveTenC,
+            17 => Yosemite::FiveTenD,
+            18 => Yosemite::FiveElevenA,
+            19 => Yosemite::FiveElevenB,
+            20 => Yosemite::FiveElevenC,
+            21 => Yosemite::FiveElevenD,
+            22 => Yosemite::FiveTwelveA,
+            23 => Yosemite::FiveTwelveB,
+            24 => Yosemite::FiveTwelveC,
+            25 => Yosemite::FiveTwelveD,
+            26 => Yosemite::FiveThirteenA,
+            27 => Yosemite::FiveThirteenB,
+            28 => Yosemite::FiveThirteenC,
+            29 => Yosemite::FiveThirteenD,
+            30 => Yosemite::FiveFourteenA,
+            31 => Yosemite::FiveFourteenB,
+            32 => Yosemite::FiveFourteenC,
+            33 => Yosemite::FiveFourteenD,
+            34 => Yosemite::FiveFifteenA,
+            35 => Yosemite::FiveFifteenB,
+            36 => Yosemite::FiveFifteenC,
+            37 => Yosemite::FiveFifteenD,
+            _ => Yosemite::None, //default to 5.9
+        }
+    }
+}
+impl Yosemite {
+    /// Every Yosemite grade, easiest first, without the None placeholder.
+    pub fn iter() -> impl Iterator<Item = Yosemite> {
+        [
+            Yosemite::One,
+            Yosemite::Two,
+            Yosemite::Three,
+            Yosemite::Four,
+            Yosemite::FiveZero,
+            Yosemite::FiveOne,
+            Yosemite::FiveTwo,
+            Yosemite::FiveThree,
+            Yosemite::FiveFour,
+            Yosemite::FiveFive,
+            Yosemite::FiveSix,
+            Yosemite::FiveSeven,
+            Yosemite::FiveEight,
+            Yosemite::FiveNine,
+            Yosemite::FiveTenA,
+            Yosemite::FiveTenB,
+            Yosemite::FiveTenC,
+            Yosemite::FiveTenD,
+            Yosemite::FiveElevenA,
+            Yosemite::FiveElevenB,
+            Yosemite::FiveElevenC,
+            Yosemite::FiveElevenD,
+            Yosemite::FiveTwelveA,
+            Yosemite::FiveTwelveB,
+            Yosemite::FiveTwelveC,
+            Yosemite::FiveTwelveD,
+            Yosemite::FiveThirteenA,
+            Yosemite::FiveThirteenB,
+            Yosemite::FiveThirteenC,
+            Yosemite::FiveThirteenD,
+            Yosemite::FiveFourteenA,
+            Yosemite::FiveFourteenB,
+            Yosemite::FiveFourteenC,
+            Yosemite::FiveFourteenD,
+            Yosemite::FiveFifteenA,
+            Yosemite::FiveFifteenB,
+            Yosemite::FiveFifteenC,
+            Yosemite::FiveFifteenD,
+        ].iter().copied()
+    }
+}
+
+/// Fontainebleau boulder grades.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Font {
+    OneMinus,
+    One,
+    OnePlus,
+    TwoMinus,
+    Two,
+    TwoPlus,
+    ThreeMinus,
+    #[default]
+    Three,
+    ThreePlus,
+    FourMinus,
+    Four,
+    FourPlus,
+    FiveMinus,
+    Five,
+    FivePlus,
+    SixAMinus,
+    SixA,
+    SixAPlus,
+    SixBMinus,
+    SixB,
+    SixBPlus,
+    SixCMinus,
+    SixC,
+    SixCPlus,
+    SevenAMinus,
+    SevenA,
+    SevenAPlus,
+    SevenBMinus,
+    SevenB,
+    SevenBPlus,
+    SevenCMinus,
+    SevenC,
+    SevenCPlus,
+    EightAMinus,
+    EightA,
+    EightAPlus,
+    EightBMinus,
+    EightB,
+    EightBPlus,
+    EightCMinus,
+    EightC,
+    EightCPlus,
+    NineAMinus,
+    NineA,
+    NineAPlus,
+    NineBMinus,
+    NineB,
+    NineBPlus,
+    NineCMinus,
+    NineC,
+    NineCPlus,
+    //add more as needed
+}
+impl std::fmt::Display for Font {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Font::OneMinus => write!(f, "1-"),
+            Font::One => write!(f, "1"),
+            Font::OnePlus => write!(f, "1+"),
+            Font::TwoMinus => write!(f, "2-"),
+            Font::Two => write!(f, "2"),
+            Font::TwoPlus => write!(f, "2+"),
+            Font::ThreeMinus => write!(f, "3-"),
+            Font::Three => write!(f, "3"),
+            Font::ThreePlus => write!(f, "3+"),
+            Font::FourMinus => write!(f, "4-"),
+            Font::Four => write!(f, "4"),
+            Font::FourPlus => write!(f, "4+"),
+            Font::FiveMinus => write!(f, "5-"),
+            Font::Five => write!(f, "5"),
+            Font::FivePlus => write!(f, "5+"),
+            Font::SixAMinus => write!(f, "6A-"),
+            Font::SixA => write!(f, "6A"),
+            Font::SixAPlus => write!(f, "6A+"),
+            Font::SixBMinus => write!(f, "6B-"),
+            Font::SixB => write!(f, "6B"),
+            Font::SixBPlus => write!(f, "6B+"),
+            Font::SixCMinus => write!(f, "6C-"),
+            Font::SixC => write!(f, "6C"),
+            Font::SixCPlus => write!(f, "6C+"),
+            Font::SevenAMinus => write!(f, "7A-"),
+            Font::SevenA => write!(f, "7A"),
+            Font::SevenAPlus => write!(f, "7A+"),
+            Font::SevenBMinus => write!(f, "7B-"),
+            Font::SevenB => write!(f, "7B"),
+            Font::SevenBPlus => write!(f, "7B+"),
+            Font::SevenCMinus => write!(f, "7C-"),
+            Font::SevenC => write!(f, "7C"),
+            Font::SevenCPlus => write!(f, "7C+"),
+            Font::EightAMinus => write!(f, "8A-"),
+            Font::EightA => write!(f, "8A"),
+            Font::EightAPlus => write!(f, "8A+"),
+            Font::EightBMinus => write!(f, "8B-"),
+            Font::EightB => write!(f, "8B"),
+            Font::EightBPlus => write!(f, "8B+"),
+            Font::EightCMinus => write!(f, "8C-"),
+            Font::EightC => write!(f, "8C"),
+            Font::EightCPlus => write!(f, "8C+"),
+            Font::NineAMinus => write!(f, "9A-"),
+            Font::NineA => write!(f, "9A"),
+            Font::NineAPlus => write!(f, "9A+"),
+            Font::NineBMinus => write!(f, "9B-"),
+            Font::NineB => write!(f, "9B"),
+            Font::NineBPlus => write!(f, "9B+"),
+            Font::NineCMinus => write!(f, "9C-"),
+            Font::NineC => write!(f, "9C"),
+            Font::NineCPlus => write!(f, "9C+"),
+        }
+    }
+}
+impl std::str::FromStr for Font { //Case insensitive, so "7a+" parses as 7A+
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Font, GradeParseError> {
+        let text = normalize(grade).to_uppercase();
+        Font::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("Font", grade))
+    }
+}
+impl Font {
+    /// Every Font grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Font> {
+        [
+            Font::OneMinus,
+            Font::One,
+            Font::OnePlus,
+            Font::TwoMinus,
+            Font::Two,
+            Font::TwoPlus,
+            Font::ThreeMinus,
+            Font::Three,
+            Font::ThreePlus,
+            Font::FourMinus,
+            Font::Four,
+            Font::FourPlus,
+            Font::FiveMinus,
+            Font::Five,
+            Font::FivePlus,
+            Font::SixAMinus,
+            Font::SixA,
+            Font::SixAPlus,
+            Font::SixBMinus,
+            Font::SixB,
+            Font::SixBPlus,
+            Font::SixCMinus,
+            Font::SixC,
+            Font::SixCPlus,
+            Font::SevenAMinus,
+            Font::SevenA,
+            Font::SevenAPlus,
+            Font::SevenBMinus,
+            Font::SevenB,
+            Font::SevenBPlus,
+            Font::SevenCMinus,
+            Font::SevenC,
+            Font::SevenCPlus,
+            Font::EightAMinus,
+            Font::EightA,
+            Font::EightAPlus,
+            Font::EightBMinus,
+            Font::EightB,
+            Font::EightBPlus,
+            Font::EightCMinus,
+            Font::EightC,
+            Font::EightCPlus,
+            Font::NineAMinus,
+            Font::NineA,
+            Font::NineAPlus,
+            Font::NineBMinus,
+            Font::NineB,
+            Font::NineBPlus,
+            Font::NineCMinus,
+            Font::NineC,
+            Font::NineCPlus,
+        ].iter().copied()
+    }
+}
+
+/// V scale, the stored grade for boulders.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Hueco {
+    Vb,
+    V0Minus,
+    #[default]
+    V0,
+    V0Plus,
+    V1Minus,
+    V1,
+    V1Plus,
+    V2Minus,
+    V2,
+    V2Plus,
+    V3Minus,
+    V3,
+    V3Plus,
+    V4Minus,
+    V4,
+    V4Plus,
+    V5Minus,
+    V5,
+    V5Plus,
+    V6Minus,
+    V6,
+    V6Plus,
+    V7Minus,
+    V7,
+    V7Plus,
+    V8Minus,
+    V8,
+    V8Plus,
+    V9Minus,
+    V9,
+    V9Plus,
+    V10Minus,
+    V10,
+    V10Plus,
+    V11Minus,
+    V11,
+    V11Plus,
+    V12Minus,
+    V12,
+    V12Plus,
+    V13Minus,
+    V13,
+    V13Plus,
+    V14Minus,
+    V14,
+    V14Plus,
+    V15Minus,
+    V15,
+    V15Plus,
+    V16Minus,
+    V16,
+    V16Plus,
+    V17Minus,
+    V17,
+    V17Plus,
+    //add more as needed
+    None,
+}
+impl std::fmt::Display for Hueco {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hueco::Vb => write!(f, "VB"),
+            Hueco::V0Minus => write!(f, "V0-"),
+            Hueco::V0 => write!(f, "V0"),
+            Hueco::V0Plus => write!(f, "V0+"),
+            Hueco::V1Minus => write!(f, "V1-"),
+            Hueco::V1 => write!(f, "V1"),
+            Hueco::V1Plus => write!(f, "V1+"),
+            Hueco::V2Minus => write!(f, "V2-"),
+            Hueco::V2 => write!(f, "V2"),
+            Hueco::V2Plus => write!(f, "V2+"),
+            Hueco::V3Minus => write!(f, "V3-"),
+            Hueco::V3 => write!(f, "V3"),
+            Hueco::V3Plus => write!(f, "V3+"),
+            Hueco::V4Minus => write!(f, "V4-"),
+            Hueco::V4 => write!(f, "V4"),
+            Hueco::V4Plus => write!(f, "V4+"),
+            Hueco::V5Minus => write!(f, "V5-"),
+            Hueco::V5 => write!(f, "V5"),
+            Hueco::V5Plus => write!(f, "V5+"),
+            Hueco::V6Minus => write!(f, "V6-"),
+            Hueco::V6 => write!(f, "V6"),
+            Hueco::V6Plus => write!(f, "V6+"),
+            Hueco::V7Minus => write!(f, "V7-"),
+            Hueco::V7 => write!(f, "V7"),
+            Hueco::V7Plus => write!(f, "V7+"),
+            Hueco::V8Minus => write!(f, "V8-"),
+            Hueco::V8 => write!(f, "V8"),
+            Hueco::V8Plus => write!(f, "V8+"),
+            Hueco::V9Minus => write!(f, "V9-"),
+            Hueco::V9 => write!(f, "V9"),
+            Hueco::V9Plus => write!(f, "V9+"),
+            Hueco::V10Minus => write!(f, "V10-"),
+            Hueco::V10 => write!(f, "V10"),
+            Hueco::V10Plus => write!(f, "V10+"),
+            Hueco::V11Minus => write!(f, "V11-"),
+            Hueco::V11 => write!(f, "V11"),
+            Hueco::V11Plus => write!(f, "V11+"),
+            Hueco::V12Minus => write!(f, "V12-"),
+            Hueco::V12 => write!(f, "V12"),
+            Hueco::V12Plus => write!(f, "V12+"),
+            Hueco::V13Minus => write!(f, "V13-"),
+            Hueco::V13 => write!(f, "V13"),
+            Hueco::V13Plus => write!(f, "V13+"),
+            Hueco::V14Minus => write!(f, "V14-"),
+            Hueco::V14 => write!(f, "V14"),
+            Hueco::V14Plus => write!(f, "V14+"),
+            Hueco::V15Minus => write!(f, "V15-"),
+            Hueco::V15 => write!(f, "V15"),
+            Hueco::V15Plus => write!(f, "V15+"),
+            Hueco::V16Minus => write!(f, "V16-"),
+            Hueco::V16 => write!(f, "V16"),
+            Hueco::V16Plus => write!(f, "V16+"),
+            Hueco::V17Minus => write!(f, "V17-"),
+            Hueco::V17 => write!(f, "V17"),
+            Hueco::V17Plus => write!(f, "V17+"),
+            Hueco::None => write!(f, "None"),
+        }
+    }
+}
+impl std::convert::From<i32> for Hueco {
+    fn from(grade: i32) -> Hueco {
+        match grade {
+            -1 => Hueco::Vb,
+            0 => Hueco::V0Minus,
+            1 => Hueco::V0,
+            2 => Hueco::V0Plus,
+            3 => Hueco::V1Minus,
+            4 => Hueco::V1,
+            5 => Hueco::V1Plus,
+            6 => Hueco::V2Minus,
+            7 => Hueco::V2,
+            8 => Hueco::V2Plus,
+            9 => Hueco::V3Minus,
+            10 => Hueco::V3,
+            11 => Hueco::V3Plus,
+            12 => Hueco::V4Minus,
+            13 => Hueco::V4,
+            14 => Hueco::V4Plus,
+            15 => Hueco::V5Minus,
+            16 => Hueco::V5,
+            17 => Hueco::V5Plus,
+            18 => Hueco::V6Minus,
+            19 => Hueco::V6,
+            20 => Hueco::V6Plus,
+            21 => Hueco::V7Minus,
+            22 => Hueco::V7,
+            23 => Hueco::V7Plus,
+            24 => Hueco::V8Minus,
+            25 => Hueco::V8,
+            26 => Hueco::V8Plus,
+            27 => Hueco::V9Minus,
+            28 => Hueco::V9,
+            29 => Hueco::V9Plus,
+            30 => Hueco::V10Minus,
+            31 => Hueco::V10,
+            32 => Hueco::V10Plus,
+            33 => Hueco::V11Minus,
+            34 => Hueco::V11,
+            35 => Hueco::V11Plus,
+            36 => Hueco::V12Minus,
+            37 => Hueco::V12,
+            38 => Hueco::V12Plus,
+            39 => Hueco::V13Minus,
+            40 => Hueco::V13,
+            41 => Hueco::V13Plus,
+            42 => Hueco::V14Minus,
+            43 => Hueco::V14,
+            44 => Hueco::V14Plus,
+            45 => Hueco::V15Minus,
+            46 => Hueco::V15,
+            47 => Hueco::V15Plus,
+            48 => Hueco::V16Minus,
+            49 => Hueco::V16,
+            50 => Hueco::V16Plus,
+            51 => Hueco::V17Minus,
+            52 => Hueco::V17,
+            53 => Hueco::V17Plus,
+            _ => Hueco::None, //default to no grade
+        }
+    }
+
+}
+impl std::str::FromStr for Hueco { //Accepts the displayed grades, split grades like "V5-6" are ranges for GradeRange
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Hueco, GradeParseError> {
+        let text = normalize(grade).to_uppercase();
+        Hueco::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("Hueco", grade))
+    }
+}
+impl Hueco {
+    /// Every V grade, easiest first, without the None placeholder.
+    pub fn iter() -> impl Iterator<Item = Hueco> {
+        [
+            Hueco::Vb,
+            Hueco::V0Minus,
+            Hueco::V0,
+            Hueco::V0Plus,
+            Hueco::V1Minus,
+            Hueco::V1,
+            Hueco::V1Plus,
+            Hueco::V2Minus,
+            Hueco::V2,
+            Hueco::V2Plus,
+            Hueco::V3Minus,
+            Hueco::V3,
+            Hueco::V3Plus,
+            Hueco::V4Minus,
+            Hueco::V4,
+            Hueco::V4Plus,
+            Hueco::V5Minus,
+            Hueco::V5,
+            Hueco::V5Plus,
+            Hueco::V6Minus,
+            Hueco::V6,
+            Hueco::V6Plus,
+            Hueco::V7Minus,
+            Hueco::V7,
+            Hueco::V7Plus,
+            Hueco::V8Minus,
+            Hueco::V8,
+            Hueco::V8Plus,
+            Hueco::V9Minus,
+            Hueco::V9,
+            Hueco::V9Plus,
+            Hueco::V10Minus,
+            Hueco::V10,
+            Hueco::V10Plus,
+            Hueco::V11Minus,
+            Hueco::V11,
+            Hueco::V11Plus,
+            Hueco::V12Minus,
+            Hueco::V12,
+            Hueco::V12Plus,
+            Hueco::V13Minus,
+            Hueco::V13,
+            Hueco::V13Plus,
+            Hueco::V14Minus,
+            Hueco::V14,
+            Hueco::V14Plus,
+            Hueco::V15Minus,
+            Hueco::V15,
+            Hueco::V15Plus,
+            Hueco::V16Minus,
+            Hueco::V16,
+            Hueco::V16Plus,
+            Hueco::V17Minus,
+            Hueco::V17,
+            Hueco::V17Plus,
+        ].iter().copied()
+    }
+}
+
+/// French sport grades, 5a, 6a+, etc.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum French {
+    One,
+    Two,
+    Three,
+    FourA,
+    FourB,
+    FourC,
+    FiveA,
+    FiveB,
+    FiveC,
+    #[default]
+    SixAMinus,
+    SixA,
+    SixAPlus,
+    SixBMinus,
+    SixB,
+    SixBPlus,
+    SixCMinus,
+    SixC,
+    SixCPlus,
+    SevenAMinus,
+    SevenA,
+    SevenAPlus,
+    SevenBMinus,
+    SevenB,
+    SevenBPlus,
+    SevenCMinus,
+    SevenC,
+    SevenCPlus,
+    EightAMinus,
+    EightA,
+    EightAPlus,
+    EightBMinus,
+    EightB,
+    EightBPlus,
+    EightCMinus,
+    EightC,
+    EightCPlus,
+    NineAMinus,
+    NineA,
+    NineAPlus,
+    NineBMinus,
+    NineB,
+    NineBPlus,
+    NineCMinus,
+    NineC,
+    NineCPlus,
+    //add more as needed
+}
+impl std::fmt::Display for French {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            French::One => write!(f, "1"),
+            French::Two => write!(f, "2"),
+            French::Three => write!(f, "3"),
+            French::FourA => write!(f, "4a"),
+            French::FourB => write!(f, "4b"),
+            French::FourC => write!(f, "4c"),
+            French::FiveA => write!(f, "5a"),
+            French::FiveB => write!(f, "5b"),
+            French::FiveC => write!(f, "5c"),
+            French::SixAMinus => write!(f, "6a-"),
+            French::SixA => write!(f, "6a"),
+            French::SixAPlus => write!(f, "6a+"),
+            French::SixBMinus => write!(f, "6b-"),
+            French::SixB => write!(f, "6b"),
+            French::SixBPlus => write!(f, "6b+"),
+            French::SixCMinus => write!(f, "6c-"),
+            French::SixC => write!(f, "6c"),
+            French::SixCPlus => write!(f, "6c+"),
+            French::SevenAMinus => write!(f, "7a-"),
+            French::SevenA => write!(f, "7a"),
+            French::SevenAPlus => write!(f, "7a+"),
+            French::SevenBMinus => write!(f, "7b-"),
+            French::SevenB => write!(f, "7b"),
+            French::SevenBPlus => write!(f, "7b+"),
+            French::SevenCMinus => write!(f, "7c-"),
+            French::SevenC => write!(f, "7c"),
+            French::SevenCPlus => write!(f, "7c+"),
+            French::EightAMinus => write!(f, "8a-"),
+            French::EightA => write!(f, "8a"),
+            French::EightAPlus => write!(f, "8a+"),
+            French::EightBMinus => write!(f, "8b-"),
+            French::EightB => write!(f, "8b"),
+            French::EightBPlus => write!(f, "8b+"),
+            French::EightCMinus => write!(f, "8c-"),
+            French::EightC => write!(f, "8c"),
+            French::EightCPlus => write!(f, "8c+"),
+            French::NineAMinus => write!(f, "9a-"),
+            French::NineA => write!(f, "9a"),
+            French::NineAPlus => write!(f, "9a+"),
+            French::NineBMinus => write!(f, "9b-"),
+            French::NineB => write!(f, "9b"),
+            French::NineBPlus => write!(f, "9b+"),
+            French::NineCMinus => write!(f, "9c-"),
+            French::NineC => write!(f, "9c"),
+            French::NineCPlus => write!(f, "9c+"),
+        }
+    }
+}
+impl std::convert::From<Yosemite> for French {
+    fn from(yosemite: Yosemite) -> French {
+        match conversion::convert(Grade::Yosemite(yosemite), GradeSystem::French) {
+            Some(Grade::French(grade)) => grade,
+            _ => French::default(),
+        }
+    }
+}
+impl std::str::FromStr for French { //Case insensitive, so "6A+" parses as 6a+
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<French, GradeParseError> {
+        let text = normalize(grade).to_lowercase();
+        French::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("French", grade))
+    }
+}
+impl French {
+    /// Every French grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = French> {
+        [
+            French::One,
+            French::Two,
+            French::Three,
+            French::FourA,
+            French::FourB,
+            French::FourC,
+            French::FiveA,
+            French::FiveB,
+            French::FiveC,
+            French::SixAMinus,
+            French::SixA,
+            French::SixAPlus,
+            French::SixBMinus,
+            French::SixB,
+            French::SixBPlus,
+            French::SixCMinus,
+            French::SixC,
+            French::SixCPlus,
+            French::SevenAMinus,
+            French::SevenA,
+            French::SevenAPlus,
+            French::SevenBMinus,
+            French::SevenB,
+            French::SevenBPlus,
+            French::SevenCMinus,
+            French::SevenC,
+            French::SevenCPlus,
+            French::EightAMinus,
+            French::EightA,
+            French::EightAPlus,
+            French::EightBMinus,
+            French::EightB,
+            French::EightBPlus,
+            French::EightCMinus,
+            French::EightC,
+            French::EightCPlus,
+            French::NineAMinus,
+            French::NineA,
+            French::NineAPlus,
+            French::NineBMinus,
+            French::NineB,
+            French::NineBPlus,
+            French::NineCMinus,
+            French::NineC,
+            French::NineCPlus,
+        ].iter().copied()
+    }
+}
+
+/// UIAA grades, I to XII with + and - as needed.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Uiaa {
+    I,
+    Ii,
+    Iii,
+    IvMinus,
+    Iv,
+    IvPlus,
+    VMinus,
+    V,
+    VPlus,
+    #[default]
+    ViMinus,
+    Vi,
+    ViPlus,
+    ViiMinus,
+    Vii,
+    ViiPlus,
+    ViiiMinus,
+    Viii,
+    ViiiPlus,
+    IxMinus,
+    Ix,
+    IxPlus,
+    XMinus,
+    X,
+    XPlus,
+    XiMinus,
+    Xi,
+    XiPlus,
+    XiiMinus,
+    Xii,
+    XiiPlus,
+}
+impl std::fmt::Display for Uiaa {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Uiaa::I => write!(f, "I"),
+            Uiaa::Ii => write!(f, "II"),
+            Uiaa::Iii => write!(f, "III"),
+            Uiaa::IvMinus => write!(f, "IV-"),
+            Uiaa::Iv => write!(f, "IV"),
+            Uiaa::IvPlus => write!(f, "IV+"),
+            Uiaa::VMinus => write!(f, "V-"),
+            Uiaa::V => write!(f, "V"),
+            Uiaa::VPlus => write!(f, "V+"),
+            Uiaa::ViMinus => write!(f, "VI-"),
+            Uiaa::Vi => write!(f, "VI"),
+            Uiaa::ViPlus => write!(f, "VI+"),
+            Uiaa::ViiMinus => write!(f, "VII-"),
+            Uiaa::Vii => write!(f, "VII"),
+            Uiaa::ViiPlus => write!(f, "VII+"),
+            Uiaa::ViiiMinus => write!(f, "VIII-"),
+            Uiaa::Viii => write!(f, "VIII"),
+            Uiaa::ViiiPlus => write!(f, "VIII+"),
+            Uiaa::IxMinus => write!(f, "IX-"),
+            Uiaa::Ix => write!(f, "IX"),
+            Uiaa::IxPlus => write!(f, "IX+"),
+            Uiaa::XMinus => write!(f, "X-"),
+            Uiaa::X => write!(f, "X"),
+            Uiaa::XPlus => write!(f, "X+"),
+            Uiaa::XiMinus => write!(f, "XI-"),
+            Uiaa::Xi => write!(f, "XI"),
+            Uiaa::XiPlus => write!(f, "XI+"),
+            Uiaa::XiiMinus => write!(f, "XII-"),
+            Uiaa::Xii => write!(f, "XII"),
+            Uiaa::XiiPlus => write!(f, "XII+"),
+        }
+    }
+}
+impl std::str::FromStr for Uiaa { //Accepts roman numerals like "VIII-" and the arabic form like "8-"
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Uiaa, GradeParseError> {
+        const ROMAN: [&str; 12] = ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII"];
+        let text = normalize(grade).to_uppercase();
+        let (number, modifier) = split_modifier(&text);
+        let roman = match number.parse::<usize>() {
+            Ok(arabic) if (1..=ROMAN.len()).contains(&arabic) => ROMAN[arabic - 1],
+            Ok(_) => return Err(GradeParseError::new("UIAA", grade)),
+            Err(_) => number,
+        };
+        let full = match modifier {
+            Modifier::Minus => format!("{roman}-"),
+            Modifier::None => roman.to_string(),
+            Modifier::Plus => format!("{roman}+"),
+        };
+        Uiaa::iter().find(|found| found.to_string() == full).ok_or_else(|| GradeParseError::new("UIAA", grade))
+    }
+}
+impl Uiaa {
+    /// Every UIAA grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Uiaa> {
+        [
+            Uiaa::I,
+            Uiaa::Ii,
+            Uiaa::Iii,
+            Uiaa::IvMinus,
+            Uiaa::Iv,
+            Uiaa::IvPlus,
+            Uiaa::VMinus,
+            Uiaa::V,
+            Uiaa::VPlus,
+            Uiaa::ViMinus,
+            Uiaa::Vi,
+            Uiaa::ViPlus,
+            Uiaa::ViiMinus,
+            Uiaa::Vii,
+            Uiaa::ViiPlus,
+            Uiaa::ViiiMinus,
+            Uiaa::Viii,
+            Uiaa::ViiiPlus,
+            Uiaa::IxMinus,
+            Uiaa::Ix,
+            Uiaa::IxPlus,
+            Uiaa::XMinus,
+            Uiaa::X,
+            Uiaa::XPlus,
+            Uiaa::XiMinus,
+            Uiaa::Xi,
+            Uiaa::XiPlus,
+            Uiaa::XiiMinus,
+            Uiaa::Xii,
+            Uiaa::XiiPlus,
+        ].iter().copied()
+    }
+}
+
+/// British adjectival grade, how serious the whole route is.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Adjectival {
+    Moderate,
+    Difficult,
+    VeryDifficult,
+    HardVeryDifficult,
+    MildSevere,
+    Severe,
+    HardSevere,
+    MildVerySevere,
+    VerySevere,
+    #[default]
+    HardVerySevere,
+    E1,
+    E2,
+    E3,
+    E4,
+    E5,
+    E6,
+    E7,
+    E8,
+    E9,
+    E10,
+    E11,
+}
+impl std::fmt::Display for Adjectival {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Adjectival::Moderate => write!(f, "M"),
+            Adjectival::Difficult => write!(f, "D"),
+            Adjectival::VeryDifficult => write!(f, "VD"),
+            Adjectival::HardVeryDifficult => write!(f, "HVD"),
+            Adjectival::MildSevere => write!(f, "MS"),
+            Adjectival::Severe => write!(f, "S"),
+            Adjectival::HardSevere => write!(f, "HS"),
+            Adjectival::MildVerySevere => write!(f, "MVS"),
+            Adjectival::VerySevere => write!(f, "VS"),
+            Adjectival::HardVerySevere => write!(f, "HVS"),
+            Adjectival::E1 => write!(f, "E1"),
+            Adjectival::E2 => write!(f, "E2"),
+            Adjectival::E3 => write!(f, "E3"),
+            Adjectival::E4 => write!(f, "E4"),
+            Adjectival::E5 => write!(f, "E5"),
+            Adjectival::E6 => write!(f, "E6"),
+            Adjectival::E7 => write!(f, "E7"),
+            Adjectival::E8 => write!(f, "E8"),
+            Adjectival::E9 => write!(f, "E9"),
+            Adjectival::E10 => write!(f, "E10"),
+            Adjectival::E11 => write!(f, "E11"),
+        }
+    }
+}
+impl std::str::FromStr for Adjectival { //Also takes the long forms like "Diff", "VDiff" and "Sev"
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Adjectival, GradeParseError> {
+        let text = normalize(grade).to_uppercase();
+        let text = match text.as_str() {
+            "MOD" => "M",
+            "DIFF" => "D",
+            "VDIFF" => "VD",
+            "HVDIFF" => "HVD",
+            "MSEV" => "MS",
+            "SEV" => "S",
+            "HSEV" => "HS",
+            other => other,
+        };
+        Adjectival::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("British adjectival", grade))
+    }
+}
+impl Adjectival {
+    /// Every adjectival grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Adjectival> {
+        [
+            Adjectival::Moderate,
+            Adjectival::Difficult,
+            Adjectival::VeryDifficult,
+            Adjectival::HardVeryDifficult,
+            Adjectival::MildSevere,
+            Adjectival::Severe,
+            Adjectival::HardSevere,
+            Adjectival::MildVerySevere,
+            Adjectival::VerySevere,
+            Adjectival::HardVerySevere,
+            Adjectival::E1,
+            Adjectival::E2,
+            Adjectival::E3,
+            Adjectival::E4,
+            Adjectival::E5,
+            Adjectival::E6,
+            Adjectival::E7,
+            Adjectival::E8,
+            Adjectival::E9,
+            Adjectival::E10,
+            Adjectival::E11,
+        ].iter().copied()
+    }
+}
+
+/// British technical grade, how hard the hardest move is.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Technical {
+    FourA,
+    FourB,
+    FourC,
+    #[default]
+    FiveA,
+    FiveB,
+    FiveC,
+    SixA,
+    SixB,
+    SixC,
+    SevenA,
+    SevenB,
+}
+impl std::fmt::Display for Technical {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Technical::FourA => write!(f, "4a"),
+            Technical::FourB => write!(f, "4b"),
+            Technical::FourC => write!(f, "4c"),
+            Technical::FiveA => write!(f, "5a"),
+            Technical::FiveB => write!(f, "5b"),
+            Technical::FiveC => write!(f, "5c"),
+            Technical::SixA => write!(f, "6a"),
+            Technical::SixB => write!(f, "6b"),
+            Technical::SixC => write!(f, "6c"),
+            Technical::SevenA => write!(f, "7a"),
+            Technical::SevenB => write!(f, "7b"),
+        }
+    }
+}
+impl std::str::FromStr for Technical {
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Technical, GradeParseError> {
+        let text = normalize(grade).to_lowercase();
+        Technical::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("British technical", grade))
+    }
+}
+impl Technical {
+    /// Every technical grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Technical> {
+        [
+            Technical::FourA,
+            Technical::FourB,
+            Technical::FourC,
+            Technical::FiveA,
+            Technical::FiveB,
+            Technical::FiveC,
+            Technical::SixA,
+            Technical::SixB,
+            Technical::SixC,
+            Technical::SevenA,
+            Technical::SevenB,
+        ].iter().copied()
+    }
+}
+
+/// E3 5c, HVS 5a, VD, etc. The technical grade is left off on easy routes.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct British {
+    pub adjectival: Adjectival,
+    pub technical: Option<Technical>,
+}
+impl std::fmt::Display for British {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.technical {
+            Some(technical) => write!(f, "{} {technical}", self.adjectival),
+            None => write!(f, "{}", self.adjectival),
+        }
+    }
+}
+impl std::str::FromStr for British { //Adjectival grade with an optional technical grade, "E3 5c", "E3" or "Sev 4a"
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<British, GradeParseError> {
+        let error = || GradeParseError::new("British", grade);
+        let mut parts = grade.split_whitespace();
+        let adjectival = parts.next().ok_or_else(error)?.parse::<Adjectival>().map_err(|_| error())?;
+        let technical = parts.next().map(Technical::from_str).transpose().map_err(|_| error())?;
+        if parts.next().is_some() {
+            return Err(error());
+        }
+        Ok(British { adjectival, technical })
+    }
+}
+impl British {
+    /// The usual adjectival and technical pairings, easiest first.
+    pub fn iter() -> impl Iterator<Item = British> {
+        // The usual pairings, easiest first. Other pairings still parse, they're just not offered in lists
+        let pairs: [(Adjectival, &[Technical]); 20] = [
+            (Adjectival::Moderate, &[]),
+            (Adjectival::Difficult, &[]),
+            (Adjectival::VeryDifficult, &[]),
+            (Adjectival::HardVeryDifficult, &[]),
+            (Adjectival::MildSevere, &[]),
+            (Adjectival::Severe, &[Technical::FourA, Technical::FourB]),
+            (Adjectival::HardSevere, &[Technical::FourB, Technical::FourC]),
+            (Adjectival::VerySevere, &[Technical::FourC, Technical::FiveA]),
+            (Adjectival::HardVerySevere, &[Technical::FourC, Technical::FiveA, Technical::FiveB]),
+            (Adjectival::E1, &[Technical::FiveA, Technical::FiveB, Technical::FiveC]),
+            (Adjectival::E2, &[Technical::FiveB, Technical::FiveC, Technical::SixA]),
+            (Adjectival::E3, &[Technical::FiveC, Technical::SixA]),
+            (Adjectival::E4, &[Technical::SixA, Technical::SixB]),
+            (Adjectival::E5, &[Technical::SixA, Technical::SixB, Technical::SixC]),
+            (Adjectival::E6, &[Technical::SixB, Technical::SixC]),
+            (Adjectival::E7, &[Technical::SixC, Technical::SevenA]),
+            (Adjectival::E8, &[Technical::SixC, Technical::SevenA]),
+            (Adjectival::E9, &[Technical::SevenA, Technical::SevenB]),
+            (Adjectival::E10, &[Technical::SevenB]),
+            (Adjectival::E11, &[Technical::SevenB]),
+        ];
+        pairs.into_iter().flat_map(|(adjectival, technicals)| {
+            let grades: Vec<British> = if technicals.is_empty() {
+                vec![British { adjectival, technical: None }]
+            } else {
+                technicals.iter().map(|technical| British { adjectival, technical: Some(*technical) }).collect()
+            };
+            grades
+        })
+    }
+}
+
+/// Australian, NZ and South African grades, a single number from 1 up.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+pub struct Ewbank(u8);
+impl Ewbank {
+    /// Hardest Ewbank grade there is.
+    pub const MAX: u8 = 39;
+
+    /// The grade with this number, None outside 1 to `MAX`.
+    pub fn new(grade: u8) -> Option<Ewbank> {
+        (1..=Ewbank::MAX).contains(&grade).then_some(Ewbank(grade))
+    }
+
+    /// The grade as its number.
+    pub fn number(self) -> u8 {
+        self.0
+    }
+
+    /// Every Ewbank grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Ewbank> {
+        (1..=Ewbank::MAX).map(Ewbank)
+    }
+}
+impl Default for Ewbank {
+    fn default() -> Self {
+        Ewbank(16)
+    }
+}
+impl std::fmt::Display for Ewbank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl std::str::FromStr for Ewbank {
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Ewbank, GradeParseError> {
+        normalize(grade).parse::<u8>().ok().and_then(Ewbank::new).ok_or_else(|| GradeParseError::new("Ewbank", grade))
+    }
+}
+
+/// Elbsandstein grades, `VIIa`, `VIIb`, etc.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Saxon {
+    I,
+    Ii,
+    Iii,
+    Iv,
+    V,
+    Vi,
+    #[default]
+    ViiA,
+    ViiB,
+    ViiC,
+    ViiiA,
+    ViiiB,
+    ViiiC,
+    IxA,
+    IxB,
+    IxC,
+    XA,
+    XB,
+    XC,
+    XiA,
+    XiB,
+    XiC,
+    XiiA,
+    XiiB,
+    XiiC,
+}
+impl std::fmt::Display for Saxon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Saxon::I => write!(f, "I"),
+            Saxon::Ii => write!(f, "II"),
+            Saxon::Iii => write!(f, "III"),
+            Saxon::Iv => write!(f, "IV"),
+            Saxon::V => write!(f, "V"),
+            Saxon::Vi => write!(f, "VI"),
+            Saxon::ViiA => write!(f, "VIIa"),
+            Saxon::ViiB => write!(f, "VIIb"),
+            Saxon::ViiC => write!(f, "VIIc"),
+            Saxon::ViiiA => write!(f, "VIIIa"),
+            Saxon::ViiiB => write!(f, "VIIIb"),
+            Saxon::ViiiC => write!(f, "VIIIc"),
+            Saxon::IxA => write!(f, "IXa"),
+            Saxon::IxB => write!(f, "IXb"),
+            Saxon::IxC => write!(f, "IXc"),
+            Saxon::XA => write!(f, "Xa"),
+            Saxon::XB => write!(f, "Xb"),
+            Saxon::XC => write!(f, "Xc"),
+            Saxon::XiA => write!(f, "XIa"),
+            Saxon::XiB => write!(f, "XIb"),
+            Saxon::XiC => write!(f, "XIc"),
+            Saxon::XiiA => write!(f, "XIIa"),
+            Saxon::XiiB => write!(f, "XIIb"),
+            Saxon::XiiC => write!(f, "XIIc"),
+        }
+    }
+}
+impl std::str::FromStr for Saxon { //Roman numerals are case insensitive, "viia" parses as VIIa
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Saxon, GradeParseError> {
+        let text = normalize(grade).to_lowercase();
+        Saxon::iter().find(|found| found.to_string().to_lowercase() == text).ok_or_else(|| GradeParseError::new("Saxon", grade))
+    }
+}
+impl Saxon {
+    /// Every Saxon grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Saxon> {
+        [
+            Saxon::I,
+            Saxon::Ii,
+            Saxon::Iii,
+            Saxon::Iv,
+            Saxon::V,
+            Saxon::Vi,
+            Saxon::ViiA,
+            Saxon::ViiB,
+            Saxon::ViiC,
+            Saxon::ViiiA,
+            Saxon::ViiiB,
+            Saxon::ViiiC,
+            Saxon::IxA,
+            Saxon::IxB,
+            Saxon::IxC,
+            Saxon::XA,
+            Saxon::XB,
+            Saxon::XC,
+            Saxon::XiA,
+            Saxon::XiB,
+            Saxon::XiC,
+            Saxon::XiiA,
+            Saxon::XiiB,
+            Saxon::XiiC,
+        ].iter().copied()
+    }
+}
+
+/// Brazilian grades, I to `VIsup`, then 7a, 7b, etc.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Brazilian {
+    I,
+    ISup,
+    Ii,
+    IiSup,
+    Iii,
+    IiiSup,
+    Iv,
+    IvSup,
+    V,
+    VSup,
+    #[default]
+    Vi,
+    ViSup,
+    SevenA,
+    SevenB,
+    SevenC,
+    EightA,
+    EightB,
+    EightC,
+    NineA,
+    NineB,
+    NineC,
+    TenA,
+    TenB,
+    TenC,
+    ElevenA,
+    ElevenB,
+    ElevenC,
+    TwelveA,
+    TwelveB,
+    TwelveC,
+}
+impl std::fmt::Display for Brazilian {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Brazilian::I => write!(f, "I"),
+            Brazilian::ISup => write!(f, "Isup"),
+            Brazilian::Ii => write!(f, "II"),
+            Brazilian::IiSup => write!(f, "IIsup"),
+            Brazilian::Iii => write!(f, "III"),
+            Brazilian::IiiSup => write!(f, "IIIsup"),
+            Brazilian::Iv => write!(f, "IV"),
+            Brazilian::IvSup => write!(f, "IVsup"),
+            Brazilian::V => write!(f, "V"),
+            Brazilian::VSup => write!(f, "Vsup"),
+            Brazilian::Vi => write!(f, "VI"),
+            Brazilian::ViSup => write!(f, "VIsup"),
+            Brazilian::SevenA => write!(f, "7a"),
+            Brazilian::SevenB => write!(f, "7b"),
+            Brazilian::SevenC => write!(f, "7c"),
+            Brazilian::EightA => write!(f, "8a"),
+            Brazilian::EightB => write!(f, "8b"),
+            Brazilian::EightC => write!(f, "8c"),
+            Brazilian::NineA => write!(f, "9a"),
+            Brazilian::NineB => write!(f, "9b"),
+            Brazilian::NineC => write!(f, "9c"),
+            Brazilian::TenA => write!(f, "10a"),
+            Brazilian::TenB => write!(f, "10b"),
+            Brazilian::TenC => write!(f, "10c"),
+            Brazilian::ElevenA => write!(f, "11a"),
+            Brazilian::ElevenB => write!(f, "11b"),
+            Brazilian::ElevenC => write!(f, "11c"),
+            Brazilian::TwelveA => write!(f, "12a"),
+            Brazilian::TwelveB => write!(f, "12b"),
+            Brazilian::TwelveC => write!(f, "12c"),
+        }
+    }
+}
+impl std::str::FromStr for Brazilian { //Case insensitive, "visup" parses as VIsup
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Brazilian, GradeParseError> {
+        let text = normalize(grade).to_lowercase();
+        Brazilian::iter().find(|found| found.to_string().to_lowercase() == text).ok_or_else(|| GradeParseError::new("Brazilian", grade))
+    }
+}
+impl Brazilian {
+    /// Every Brazilian grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Brazilian> {
+        [
+            Brazilian::I,
+            Brazilian::ISup,
+            Brazilian::Ii,
+            Brazilian::IiSup,
+            Brazilian::Iii,
+            Brazilian::IiiSup,
+            Brazilian::Iv,
+            Brazilian::IvSup,
+            Brazilian::V,
+            Brazilian::VSup,
+            Brazilian::Vi,
+            Brazilian::ViSup,
+            Brazilian::SevenA,
+            Brazilian::SevenB,
+            Brazilian::SevenC,
+            Brazilian::EightA,
+            Brazilian::EightB,
+            Brazilian::EightC,
+            Brazilian::NineA,
+            Brazilian::NineB,
+            Brazilian::NineC,
+            Brazilian::TenA,
+            Brazilian::TenB,
+            Brazilian::TenC,
+            Brazilian::ElevenA,
+            Brazilian::ElevenB,
+            Brazilian::ElevenC,
+            Brazilian::TwelveA,
+            Brazilian::TwelveB,
+            Brazilian::TwelveC,
+        ].iter().copied()
+    }
+}
+
+/// Polish grades, VI.1, VI.2+, etc.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Kurtyka {
+    I,
+    Ii,
+    Iii,
+    Iv,
+    IvPlus,
+    VMinus,
+    V,
+    VPlus,
+    ViMinus,
+    #[default]
+    Vi,
+    ViPlus,
+    ViOne,
+    ViOnePlus,
+    ViTwo,
+    ViTwoPlus,
+    ViThree,
+    ViThreePlus,
+    ViFour,
+    ViFourPlus,
+    ViFive,
+    ViFivePlus,
+    ViSix,
+    ViSixPlus,
+    ViSeven,
+    ViSevenPlus,
+    ViEight,
+    ViEightPlus,
+    ViNine,
+}
+impl std::fmt::Display for Kurtyka {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kurtyka::I => write!(f, "I"),
+            Kurtyka::Ii => write!(f, "II"),
+            Kurtyka::Iii => write!(f, "III"),
+            Kurtyka::Iv => write!(f, "IV"),
+            Kurtyka::IvPlus => write!(f, "IV+"),
+            Kurtyka::VMinus => write!(f, "V-"),
+            Kurtyka::V => write!(f, "V"),
+            Kurtyka::VPlus => write!(f, "V+"),
+            Kurtyka::ViMinus => write!(f, "VI-"),
+            Kurtyka::Vi => write!(f, "VI"),
+            Kurtyka::ViPlus => write!(f, "VI+"),
+            Kurtyka::ViOne => write!(f, "VI.1"),
+            Kurtyka::ViOnePlus => write!(f, "VI.1+"),
+            Kurtyka::ViTwo => write!(f, "VI.2"),
+            Kurtyka::ViTwoPlus => write!(f, "VI.2+"),
+            Kurtyka::ViThree => write!(f, "VI.3"),
+            Kurtyka::ViThreePlus => write!(f, "VI.3+"),
+            Kurtyka::ViFour => write!(f, "VI.4"),
+            Kurtyka::ViFourPlus => write!(f, "VI.4+"),
+            Kurtyka::ViFive => write!(f, "VI.5"),
+            Kurtyka::ViFivePlus => write!(f, "VI.5+"),
+            Kurtyka::ViSix => write!(f, "VI.6"),
+            Kurtyka::ViSixPlus => write!(f, "VI.6+"),
+            Kurtyka::ViSeven => write!(f, "VI.7"),
+            Kurtyka::ViSevenPlus => write!(f, "VI.7+"),
+            Kurtyka::ViEight => write!(f, "VI.8"),
+            Kurtyka::ViEightPlus => write!(f, "VI.8+"),
+            Kurtyka::ViNine => write!(f, "VI.9"),
+        }
+    }
+}
+impl std::str::FromStr for Kurtyka { //Case insensitive, "vi.2+" parses as VI.2+
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Kurtyka, GradeParseError> {
+        let text = normalize(grade).to_lowercase();
+        Kurtyka::iter().find(|found| found.to_string().to_lowercase() == text).ok_or_else(|| GradeParseError::new("Kurtyka", grade))
+    }
+}
+impl Kurtyka {
+    /// Every Kurtyka grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Kurtyka> {
+        [
+            Kurtyka::I,
+            Kurtyka::Ii,
+            Kurtyka::Iii,
+            Kurtyka::Iv,
+            Kurtyka::IvPlus,
+            Kurtyka::VMinus,
+            Kurtyka::V,
+            Kurtyka::VPlus,
+            Kurtyka::ViMinus,
+            Kurtyka::Vi,
+            Kurtyka::ViPlus,
+            Kurtyka::ViOne,
+            Kurtyka::ViOnePlus,
+            Kurtyka::ViTwo,
+            Kurtyka::ViTwoPlus,
+            Kurtyka::ViThree,
+            Kurtyka::ViThreePlus,
+            Kurtyka::ViFour,
+            Kurtyka::ViFourPlus,
+            Kurtyka::ViFive,
+            Kurtyka::ViFivePlus,
+            Kurtyka::ViSix,
+            Kurtyka::ViSixPlus,
+            Kurtyka::ViSeven,
+            Kurtyka::ViSevenPlus,
+            Kurtyka::ViEight,
+            Kurtyka::ViEightPlus,
+            Kurtyka::ViNine,
+        ].iter().copied()
+    }
+}
+
+/// Water ice (WI) and alpine ice (AI) grades.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Ice {
+    Wi1,
+    Wi2,
+    #[default]
+    Wi3,
+    Wi4,
+    Wi5,
+    Wi6,
+    Wi7,
+    Ai1,
+    Ai2,
+    Ai3,
+    Ai4,
+    Ai5,
+    Ai6,
+}
+impl std::fmt::Display for Ice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ice::Wi1 => write!(f, "WI1"),
+            Ice::Wi2 => write!(f, "WI2"),
+            Ice::Wi3 => write!(f, "WI3"),
+            Ice::Wi4 => write!(f, "WI4"),
+            Ice::Wi5 => write!(f, "WI5"),
+            Ice::Wi6 => write!(f, "WI6"),
+            Ice::Wi7 => write!(f, "WI7"),
+            Ice::Ai1 => write!(f, "AI1"),
+            Ice::Ai2 => write!(f, "AI2"),
+            Ice::Ai3 => write!(f, "AI3"),
+            Ice::Ai4 => write!(f, "AI4"),
+            Ice::Ai5 => write!(f, "AI5"),
+            Ice::Ai6 => write!(f, "AI6"),
+        }
+    }
+}
+impl std::str::FromStr for Ice { //Case insensitive, "wi4" parses as WI4
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Ice, GradeParseError> {
+        let text = normalize(grade).to_uppercase();
+        Ice::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("ice", grade))
+    }
+}
+impl Ice {
+    /// Every ice grade, water ice then alpine ice, easiest first.
+    pub fn iter() -> impl Iterator<Item = Ice> {
+        [
+            Ice::Wi1,
+            Ice::Wi2,
+            Ice::Wi3,
+            Ice::Wi4,
+            Ice::Wi5,
+            Ice::Wi6,
+            Ice::Wi7,
+            Ice::Ai1,
+            Ice::Ai2,
+            Ice::Ai3,
+            Ice::Ai4,
+            Ice::Ai5,
+            Ice::Ai6,
+        ].iter().copied()
+    }
+}
+
+/// Mixed rock and ice, M1 to M15.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Mixed {
+    M1,
+    M2,
+    M3,
+    #[default]
+    M4,
+    M5,
+    M6,
+    M7,
+    M8,
+    M9,
+    M10,
+    M11,
+    M12,
+    M13,
+    M14,
+    M15,
+}
+impl std::fmt::Display for Mixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mixed::M1 => write!(f, "M1"),
+            Mixed::M2 => write!(f, "M2"),
+            Mixed::M3 => write!(f, "M3"),
+            Mixed::M4 => write!(f, "M4"),
+            Mixed::M5 => write!(f, "M5"),
+            Mixed::M6 => write!(f, "M6"),
+            Mixed::M7 => write!(f, "M7"),
+            Mixed::M8 => write!(f, "M8"),
+            Mixed::M9 => write!(f, "M9"),
+            Mixed::M10 => write!(f, "M10"),
+            Mixed::M11 => write!(f, "M11"),
+            Mixed::M12 => write!(f, "M12"),
+            Mixed::M13 => write!(f, "M13"),
+            Mixed::M14 => write!(f, "M14"),
+            Mixed::M15 => write!(f, "M15"),
+        }
+    }
+}
+impl std::str::FromStr for Mixed {
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Mixed, GradeParseError> {
+        let text = normalize(grade).to_uppercase();
+        Mixed::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("mixed", grade))
+    }
+}
+impl Mixed {
+    /// Every mixed grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Mixed> {
+        [
+            Mixed::M1,
+            Mixed::M2,
+            Mixed::M3,
+            Mixed::M4,
+            Mixed::M5,
+            Mixed::M6,
+            Mixed::M7,
+            Mixed::M8,
+            Mixed::M9,
+            Mixed::M10,
+            Mixed::M11,
+            Mixed::M12,
+            Mixed::M13,
+            Mixed::M14,
+            Mixed::M15,
+        ].iter().copied()
+    }
+}
+
+/// Aid climbing, A grades with hammer and C grades clean.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Aid {
+    A0,
+    #[default]
+    A1,
+    A2,
+    A3,
+    A4,
+    A5,
+    C0,
+    C1,
+    C2,
+    C3,
+    C4,
+    C5,
+}
+impl std::fmt::Display for Aid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Aid::A0 => write!(f, "A0"),
+            Aid::A1 => write!(f, "A1"),
+            Aid::A2 => write!(f, "A2"),
+            Aid::A3 => write!(f, "A3"),
+            Aid::A4 => write!(f, "A4"),
+            Aid::A5 => write!(f, "A5"),
+            Aid::C0 => write!(f, "C0"),
+            Aid::C1 => write!(f, "C1"),
+            Aid::C2 => write!(f, "C2"),
+            Aid::C3 => write!(f, "C3"),
+            Aid::C4 => write!(f, "C4"),
+            Aid::C5 => write!(f, "C5"),
+        }
+    }
+}
+impl std::str::FromStr for Aid {
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Aid, GradeParseError> {
+        let text = normalize(grade).to_uppercase();
+        Aid::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("aid", grade))
+    }
+}
+impl Aid {
+    /// Every aid grade, A grades then C grades, easiest first.
+    pub fn iter() -> impl Iterator<Item = Aid> {
+        [
+            Aid::A0,
+            Aid::A1,
+            Aid::A2,
+            Aid::A3,
+            Aid::A4,
+            Aid::A5,
+            Aid::C0,
+            Aid::C1,
+            Aid::C2,
+            Aid::C3,
+            Aid::C4,
+            Aid::C5,
+        ].iter().copied()
+    }
+}
+
+/// How long and serious a route is, from a short climb (I) to a multi-day wall (VII).
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Commitment {
+    I,
+    Ii,
+    #[default]
+    Iii,
+    Iv,
+    V,
+    Vi,
+    Vii,
+}
+impl std::fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Commitment::I => write!(f, "I"),
+            Commitment::Ii => write!(f, "II"),
+            Commitment::Iii => write!(f, "III"),
+            Commitment::Iv => write!(f, "IV"),
+            Commitment::V => write!(f, "V"),
+            Commitment::Vi => write!(f, "VI"),
+            Commitment::Vii => write!(f, "VII"),
+        }
+    }
+}
+impl std::str::FromStr for Commitment {
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Commitment, GradeParseError> {
+        let text = normalize(grade).to_uppercase();
+        Commitment::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("commitment", grade))
+    }
+}
+impl Commitment {
+    /// Every commitment grade, shortest first.
+    pub fn iter() -> impl Iterator<Item = Commitment> {
+        [
+            Commitment::I,
+            Commitment::Ii,
+            Commitment::Iii,
+            Commitment::Iv,
+            Commitment::V,
+            Commitment::Vi,
+            Commitment::Vii,
+        ].iter().copied()
+    }
+}
+
+/// Overall alpine grade, Facile to Extremement Difficile.
+#[derive(Debug, Copy, Clone, PartialEq, Default, PartialOrd)]
+pub enum Ifas {
+    F,
+    PdMinus,
+    Pd,
+    PdPlus,
+    AdMinus,
+    #[default]
+    Ad,
+    AdPlus,
+    DMinus,
+    D,
+    DPlus,
+    TdMinus,
+    Td,
+    TdPlus,
+    Ed,
+}
+impl std::fmt::Display for Ifas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ifas::F => write!(f, "F"),
+            Ifas::PdMinus => write!(f, "PD-"),
+            Ifas::Pd => write!(f, "PD"),
+            Ifas::PdPlus => write!(f, "PD+"),
+            Ifas::AdMinus => write!(f, "AD-"),
+            Ifas::Ad => write!(f, "AD"),
+            Ifas::AdPlus => write!(f, "AD+"),
+            Ifas::DMinus => write!(f, "D-"),
+            Ifas::D => write!(f, "D"),
+            Ifas::DPlus => write!(f, "D+"),
+            Ifas::TdMinus => write!(f, "TD-"),
+            Ifas::Td => write!(f, "TD"),
+            Ifas::TdPlus => write!(f, "TD+"),
+            Ifas::Ed => write!(f, "ED"),
+        }
+    }
+}
+impl std::str::FromStr for Ifas { //Case insensitive, "ad+" parses as AD+
+    type Err = GradeParseError;
+
+    fn from_str(grade: &str) -> Result<Ifas, GradeParseError> {
+        let text = normalize(grade).to_uppercase();
+        Ifas::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("IFAS", grade))
+    }
+}
+impl Ifas {
+    /// Every alpine grade, easiest first.
+    pub fn iter() -> impl Iterator<Item = Ifas> {
+        [
+            Ifas::F,
+            Ifas::PdMinus,
+            Ifas::Pd,
+            Ifas::PdPlus,
+            Ifas::AdMinus,
+            Ifas::Ad,
+            Ifas::AdPlus,
+            Ifas::DMinus,
+            Ifas::D,
+            Ifas::DPlus,
+            Ifas::TdMinus,
+            Ifas::Td,
+            Ifas::TdPlus,
+            Ifas::Ed,
+        ].iter().copied()
+    }
+}
+
+/// How bad a fall is, from well protected (G) to likely death (X).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, PartialOrd, Ord)]
+pub enum Danger {
+    #[default]
+    G,
+    Pg,
+    Pg13,
+    R,
+    X,
+}
+impl std::fmt::Display for Danger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Danger::G => write!(f, "G"),
+            Danger::Pg => write!(f, "PG"),
+            Danger::Pg13 => write!(f, "PG-13"),
+            Danger::R => write!(f, "R"),
+            Danger::X => write!(f, "X"),
+        }
+    }
+}
+impl std::str::FromStr for Danger {
+    type Err = GradeParseError;
+
+    fn from_str(rating: &str) -> Result<Danger, GradeParseError> {
+        let text = normalize(rating).to_uppercase();
+        let text = if text == "PG13" { "PG-13".to_string() } else { text }; //Dash is often left off
+        Danger::iter().find(|found| found.to_string() == text).ok_or_else(|| GradeParseError::new("danger", rating))
+    }
+}
+impl Danger {
+    /// Every protection rating, safest first.
+    pub fn iter() -> impl Iterator<Item = Danger> {
+        [
+            Danger::G,
+            Danger::Pg,
+            Danger::Pg13,
+            Danger::R,
+            Danger::X,
+        ].iter().copied()
+    }
+}
+
+/// What the climber thought of a route's grade on a send.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum GradeOpinion {
+    #[default]
+    Agree,
+    Soft, //Easier than graded
+    Hard, //Harder than graded, sandbagged
+    Grade(String), //Their own grade, in the route's grading system
+}
+impl std::fmt::Display for GradeOpinion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GradeOpinion::Agree => write!(f, "Agree"),
+            GradeOpinion::Soft => write!(f, "Soft"),
+            GradeOpinion::Hard => write!(f, "Hard"),
+            GradeOpinion::Grade(grade) => write!(f, "{grade}"),
+        }
+    }
+}
+impl From<Option<&str>> for GradeOpinion { //Inverse of GradeOpinion::stored, for the sends.personal_grade column
+    fn from(stored: Option<&str>) -> GradeOpinion {
+        match stored.map(str::trim) {
+            None | Some("" | "Agree") => GradeOpinion::Agree,
+            Some("Soft") => GradeOpinion::Soft,
+            Some("Hard") => GradeOpinion::Hard,
+            Some(grade) => GradeOpinion::Grade(grade.to_string()),
+        }
+    }
+}
+impl GradeOpinion {
+    /// Value for the `sends.personal_grade` column, agreeing isn't stored.
+    pub fn stored(&self) -> Option<String> {
+        match self {
+            GradeOpinion::Agree => None,
+            GradeOpinion::Grade(grade) if grade.trim().is_empty() => None,
+            opinion => Some(opinion.to_string()),
+        }
+    }
+
+    /// Grade index the climber felt the route was, soft and hard are one step either side.
+    ///
+    /// None when their own grade doesn't parse, rather than guessing.
+    pub fn apply(&self, consensus: i32, parse: impl Fn(&str) -> Option<i32>) -> Option<i32> {
+        match self {
+            GradeOpinion::Agree => Some(consensus),
+            GradeOpinion::Soft => Some(consensus - 1),
+            GradeOpinion::Hard => Some(consensus + 1),
+            GradeOpinion::Grade(grade) => parse(grade),
+        }
+    }
+}
+
+/// How a route was climbed on a send.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum SendType {
+    Redpoint, //Send after previous attempts/tops, no rests, lead or boulder (free) only
+    Flash, //Completion on first attempt, with beta
+    Onsight, //Completion on first attempt, without beta
+    Pinkpoint, //Redpoint but on toprope or similar
+    #[default]
+    Attempt, //No completion
+    Top, //Completed but with takes/falls
+    Repeat, //Repeat of a route
+    FreeSolo, //No rope or protection, but not on boulders
+}
+impl std::fmt::Display for SendType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SendType::Redpoint => write!(f, "Redpoint"),
+            SendType::Flash => write!(f, "Flash"),
+            SendType::Onsight => write!(f, "Onsight"),
+            SendType::Pinkpoint => write!(f, "Pinkpoint"),
+            SendType::Attempt => write!(f, "Attempt"),
+            SendType::Top => write!(f, "Top"),
+            SendType::Repeat => write!(f, "Repeat"),
+            SendType::FreeSolo => write!(f, "Free Solo"),
+        }
+    }
+}
+impl std::str::FromStr for SendType { //Inverse of Display, used for the sends.type column and settings
+    type Err = String;
+
+    fn from_str(send_type: &str) -> Result<SendType, String> {
+        SendType::iter()
+            .find(|candidate| candidate.to_string() == send_type.trim())
+            .ok_or_else(|| format!("Unknown send type: {send_type}"))
+    }
+}
+impl SendType {
+    /// Every send type, in the order they're offered when logging a send.
+    pub fn iter() -> impl Iterator<Item = SendType> {
+        [
+            SendType::Redpoint,
+            SendType::Flash,
+            SendType::Onsight,
+            SendType::Pinkpoint,
+            SendType::Attempt,
+            SendType::Top,
+            SendType::Repeat,
+            SendType::FreeSolo,
+        ].iter().copied()
+    }
+
+    /// Whether the route was actually completed clean.
+    pub fn is_send(self) -> bool {
+        matches!(self, SendType::Redpoint | SendType::Flash | SendType::Onsight | SendType::Pinkpoint | SendType::Repeat | SendType::FreeSolo)
+    }
+
+    /// Clean send placing or clipping protection on the way up.
+    pub fn is_lead(self) -> bool {
+        matches!(self, SendType::Redpoint | SendType::Flash | SendType::Onsight)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn yosemite_takes_letterless_grades() {
+        assert_eq!(Yosemite::from_str("5.10a"), Ok(Yosemite::FiveTenA));
+        assert_eq!(Yosemite::from_str(" 5.10 C "), Ok(Yosemite::FiveTenC));
+        assert_eq!(Yosemite::from_str("5.10-"), Ok(Yosemite::FiveTenA));
+        assert_eq!(Yosemite::from_str("5.10"), Ok(Yosemite::FiveTenB));
+        assert_eq!(Yosemite::from_str("5.10+"), Ok(Yosemite::FiveTenC));
+        assert_eq!(Yosemite::from_str("5.9+"), Ok(Yosemite::FiveNine), "no letters below 5.10");
+        assert_eq!(Yosemite::from_str("5.15d"), Ok(Yosemite::FiveFifteenD));
+    }
+
+    #[test]
+    fn hueco_font_and_french() {
+        assert_eq!(Hueco::from_str("v5"), Ok(Hueco::V5));
+        assert!(Hueco::from_str("V5-6").is_err(), "split grades are ranges, read by GradeRange");
+        assert_eq!(Font::from_str("7A"), Ok(Font::SevenA));
+        assert_eq!(Font::from_str("6a+"), Ok(Font::SixAPlus));
+        assert_eq!(French::from_str("6a+"), Ok(French::SixAPlus));
+        assert_eq!(French::from_str("6A+"), Ok(French::SixAPlus));
+    }
+
+    #[test]
+    fn uiaa_roman_and_arabic() {
+        assert_eq!(Uiaa::from_str("VIII-"), Ok(Uiaa::ViiiMinus));
+        assert_eq!(Uiaa::from_str("viii+"), Ok(Uiaa::ViiiPlus));
+        assert_eq!(Uiaa::from_str("8-"), Ok(Uiaa::ViiiMinus));
+        assert_eq!(Uiaa::from_str("8"), Ok(Uiaa::Viii));
+        assert_eq!(Uiaa::from_str("7"), Ok(Uiaa::Vii));
+        assert_eq!(Uiaa::from_str("12+"), Ok(Uiaa::XiiPlus));
+    }
+
+    #[test]
+    fn rejects_unknown_and_out_of_range_grades() {
+        for text in ["", "banana", "5.", "5.10e", "5.16a", "5.16", "6.10a"] {
+            assert_eq!(Yosemite::from_str(text), Err(GradeParseError::new("Yosemite", text)), "{text:?}");
+        }
+        for text in ["V", "V18", "V5-7", "V6-5", "5.10a"] {
+            assert!(Hueco::from_str(text).is_err(), "{text:?} parsed as Hueco");
+        }
+        for text in ["0", "13", "XIII", "VIII++", "8a"] {
+            assert!(Uiaa::from_str(text).is_err(), "{text:?} parsed as UIAA");
+        }
+        assert!(Font::from_str("10A").is_err());
+        assert!(French::from_str("9d").is_err());
+        assert_eq!(Yosemite::from_str("5.16a").unwrap_err().to_string(), "\"5.16a\" is not a valid Yosemite grade");
+    }
+
+    #[test]
+    fn ranges() {
+        let range = GradeRange::<Yosemite>::from_str("5.10c/d").unwrap();
+        assert_eq!((range.low, range.high), (Yosemite::FiveTenC, Yosemite::FiveTenD));
+        let range = GradeRange::<Yosemite>::from_str("5.10-").unwrap();
+        assert!(!range.is_range(), "a trailing minus is a grade, not a range");
+        let range = GradeRange::<Hueco>::from_str("V4-5").unwrap();
+        assert_eq!((range.low, range.high), (Hueco::V4, Hueco::V5));
+        assert_eq!(range.to_string(), "V4-5");
+        let range = GradeRange::<Hueco>::from_str("V0-1").unwrap();
+        assert_eq!((range.low, range.high), (Hueco::V0, Hueco::V1), "a split grade is a range like any other");
+        assert!(!GradeRange::<Hueco>::from_str("V0-").unwrap().is_range());
+        let range = GradeRange::<French>::from_str("6b/6a+").unwrap();
+        assert_eq!((range.low, range.high), (French::SixAPlus, French::SixB), "ends given the wrong way round are swapped");
+    }
+
+    #[test]
+    fn newer_systems_round_trip_through_display() {
+        // Every grade parses back from how it's shown, in lower and upper case
+        fn round_trip<G: std::fmt::Display + FromStr<Err = GradeParseError> + PartialEq + std::fmt::Debug + Copy>(grades: impl Iterator<Item = G>) {
+            for grade in grades {
+                let shown = grade.to_string();
+                assert_eq!(G::from_str(&shown), Ok(grade), "{shown}");
+                assert_eq!(G::from_str(&shown.to_lowercase()), Ok(grade), "{shown} lowercase");
+                assert_eq!(G::from_str(&shown.to_uppercase()), Ok(grade), "{shown} uppercase");
+            }
+        }
+        round_trip(Saxon::iter());
+        round_trip(Brazilian::iter());
+        round_trip(Kurtyka::iter());
+        round_trip(Ewbank::iter());
+    }
+
+    #[test]
+    fn newer_systems_parse_and_display() {
+        assert_eq!(Saxon::from_str("viia"), Ok(Saxon::ViiA));
+        assert_eq!(Saxon::XiiC.to_string(), "XIIc");
+        assert_eq!(Brazilian::from_str("visup"), Ok(Brazilian::ViSup));
+        assert_eq!(Brazilian::from_str("7A"), Ok(Brazilian::SevenA));
+        assert_eq!(Brazilian::ViSup.to_string(), "VIsup");
+        assert_eq!(Kurtyka::from_str("vi.2+"), Ok(Kurtyka::ViTwoPlus));
+        assert_eq!(Kurtyka::ViTwoPlus.to_string(), "VI.2+");
+        assert_eq!(Ewbank::from_str(" 24 ").map(Ewbank::number), Ok(24));
+        assert!(Saxon::from_str("VIId").is_err());
+        assert!(Brazilian::from_str("13a").is_err());
+        assert!(Kurtyka::from_str("VI.10").is_err());
+    }
+
+    #[test]
+    fn ewbank_bounds() {
+        assert_eq!(Ewbank::iter().count(), 39);
+        assert_eq!(Ewbank::new(1).map(Ewbank::number), Some(1));
+        assert_eq!(Ewbank::new(Ewbank::MAX).map(Ewbank::number), Some(39));
+        assert_eq!(Ewbank::new(0), None);
+        assert_eq!(Ewbank::new(40), None);
+        for text in ["0", "40", "-1", "256", "17a", ""] {
+            assert!(Ewbank::from_str(text).is_err(), "{text:?} parsed as Ewbank");
+        }
+    }
+
+    #[test]
+    fn opinions_apply_to_the_consensus() {
+        let parse = |text: &str| Yosemite::from_str(text).ok().map(|grade| grade as i32);
+        assert_eq!(GradeOpinion::Agree.apply(10, parse), Some(10));
+        assert_eq!(GradeOpinion::Soft.apply(10, parse), Some(9));
+        assert_eq!(GradeOpinion::Hard.apply(10, parse), Some(11));
+        assert_eq!(GradeOpinion::Grade("5.10a".to_string()).apply(3, parse), parse("5.10a"));
+        assert_eq!(GradeOpinion::Grade("hard-ish".to_string()).apply(10, parse), None, "not a grade, not an opinion");
+    }
+}
@@ -0,0 +1,635 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::stats::round_to_i32;
+use crate::routes_db::entities::grades;
+use crate::climbing::{Adjectival, Brazilian, British, Ewbank, Font, French, Grade, GradeParseError, GradeRange, Hueco, Kurtyka, Saxon, Technical, Uiaa, Yosemite};
+
+// Central grade conversion table
+// Every grade sits on one shared difficulty scale, counted in Yosemite steps (5.9 = 13, 5.10a = 14, ...)
+// Converting from the finer of two systems picks the closest grade on the scale, ties go to the easier grade
+// Converting the other way picks the middle of the finer grades that round to it, so going back and forth never drifts
+// Boulder grades are placed on the same scale so boulder <-> rope conversions work, but treat those as rough
+
+const YOSEMITE: [(Yosemite, f64); 38] = [
+    (Yosemite::One, 0.0), (Yosemite::Two, 1.0), (Yosemite::Three, 2.0), (Yosemite::Four, 3.0),
+    (Yosemite::FiveZero, 4.0), (Yosemite::FiveOne, 5.0), (Yosemite::FiveTwo, 6.0), (Yosemite::FiveThree, 7.0),
+    (Yosemite::FiveFour, 8.0), (Yosemite::FiveFive, 9.0), (Yosemite::FiveSix, 10.0), (Yosemite::FiveSeven, 11.0),
+    (Yosemite::FiveEight, 12.0), (Yosemite::FiveNine, 13.0),
+    (Yosemite::FiveTenA, 14.0), (Yosemite::FiveTenB, 15.0), (Yosemite::FiveTenC, 16.0), (Yosemite::FiveTenD, 17.0),
+    (Yosemite::FiveElevenA, 18.0), (Yosemite::FiveElevenB, 19.0), (Yosemite::FiveElevenC, 20.0), (Yosemite::FiveElevenD, 21.0),
+    (Yosemite::FiveTwelveA, 22.0), (Yosemite::FiveTwelveB, 23.0), (Yosemite::FiveTwelveC, 24.0), (Yosemite::FiveTwelveD, 25.0),
+    (Yosemite::FiveThirteenA, 26.0), (Yosemite::FiveThirteenB, 27.0), (Yosemite::FiveThirteenC, 28.0), (Yosemite::FiveThirteenD, 29.0),
+    (Yosemite::FiveFourteenA, 30.0), (Yosemite::FiveFourteenB, 31.0), (Yosemite::FiveFourteenC, 32.0), (Yosemite::FiveFourteenD, 33.0),
+    (Yosemite::FiveFifteenA, 34.0), (Yosemite::FiveFifteenB, 35.0), (Yosemite::FiveFifteenC, 36.0), (Yosemite::FiveFifteenD, 37.0),
+];
+
+const FRENCH: [(French, f64); 45] = [
+    (French::One, 6.0), (French::Two, 7.0), (French::Three, 8.0),
+    (French::FourA, 8.5), (French::FourB, 9.0), (French::FourC, 9.5),
+    (French::FiveA, 10.0), (French::FiveB, 11.0), (French::FiveC, 12.0),
+    (French::SixAMinus, 13.0), (French::SixA, 14.0), (French::SixAPlus, 15.0),
+    (French::SixBMinus, 16.0), (French::SixB, 16.5), (French::SixBPlus, 17.0),
+    (French::SixCMinus, 18.0), (French::SixC, 18.5), (French::SixCPlus, 19.0),
+    (French::SevenAMinus, 19.5), (French::SevenA, 20.0), (French::SevenAPlus, 21.0),
+    (French::SevenBMinus, 21.5), (French::SevenB, 22.0), (French::SevenBPlus, 23.0),
+    (French::SevenCMinus, 24.0), (French::SevenC, 24.5), (French::SevenCPlus, 25.0),
+    (French::EightAMinus, 26.0), (French::EightA, 27.0), (French::EightAPlus, 28.0),
+    (French::EightBMinus, 29.0), (French::EightB, 29.5), (French::EightBPlus, 30.0),
+    (French::EightCMinus, 30.5), (French::EightC, 31.0), (French::EightCPlus, 32.0),
+    (French::NineAMinus, 33.0), (French::NineA, 33.5), (French::NineAPlus, 34.0),
+    (French::NineBMinus, 34.5), (French::NineB, 35.0), (French::NineBPlus, 36.0),
+    (French::NineCMinus, 36.5), (French::NineC, 37.0), (French::NineCPlus, 37.5),
+];
+
+const UIAA: [(Uiaa, f64); 30] = [
+    (Uiaa::I, 5.0), (Uiaa::Ii, 6.0), (Uiaa::Iii, 7.0),
+    (Uiaa::IvMinus, 8.0), (Uiaa::Iv, 8.5), (Uiaa::IvPlus, 9.0),
+    (Uiaa::VMinus, 10.0), (Uiaa::V, 11.0), (Uiaa::VPlus, 12.0),
+    (Uiaa::ViMinus, 13.0), (Uiaa::Vi, 14.0), (Uiaa::ViPlus, 15.0),
+    (Uiaa::ViiMinus, 16.0), (Uiaa::Vii, 17.0), (Uiaa::ViiPlus, 18.0),
+    (Uiaa::ViiiMinus, 20.0), (Uiaa::Viii, 21.0), (Uiaa::ViiiPlus, 22.0),
+    (Uiaa::IxMinus, 24.0), (Uiaa::Ix, 25.0), (Uiaa::IxPlus, 27.0),
+    (Uiaa::XMinus, 28.0), (Uiaa::X, 29.0), (Uiaa::XPlus, 30.0),
+    (Uiaa::XiMinus, 31.0), (Uiaa::Xi, 33.0), (Uiaa::XiPlus, 34.0),
+    (Uiaa::XiiMinus, 35.0), (Uiaa::Xii, 36.0), (Uiaa::XiiPlus, 37.0),
+];
+
+const HUECO: [(Hueco, f64); 55] = [
+    (Hueco::Vb, 9.0),
+    (Hueco::V0Minus, 12.7), (Hueco::V0, 13.0), (Hueco::V0Plus, 13.3),
+    (Hueco::V1Minus, 15.7), (Hueco::V1, 16.0), (Hueco::V1Plus, 16.3),
+    (Hueco::V2Minus, 18.2), (Hueco::V2, 18.5), (Hueco::V2Plus, 18.8),
+    (Hueco::V3Minus, 20.2), (Hueco::V3, 20.5), (Hueco::V3Plus, 20.8),
+    (Hueco::V4Minus, 22.0), (Hueco::V4, 22.3), (Hueco::V4Plus, 22.6),
+    (Hueco::V5Minus, 23.0), (Hueco::V5, 23.3), (Hueco::V5Plus, 23.6),
+    (Hueco::V6Minus, 24.3), (Hueco::V6, 24.6), (Hueco::V6Plus, 24.9),
+    (Hueco::V7Minus, 25.9), (Hueco::V7, 26.2), (Hueco::V7Plus, 26.5),
+    (Hueco::V8Minus, 27.5), (Hueco::V8, 27.8), (Hueco::V8Plus, 28.1),
+    (Hueco::V9Minus, 28.7), (Hueco::V9, 29.0), (Hueco::V9Plus, 29.3),
+    (Hueco::V10Minus, 29.7), (Hueco::V10, 30.0), (Hueco::V10Plus, 30.3),
+    (Hueco::V11Minus, 30.7), (Hueco::V11, 31.0), (Hueco::V11Plus, 31.3),
+    (Hueco::V12Minus, 31.7), (Hueco::V12, 32.0), (Hueco::V12Plus, 32.3),
+    (Hueco::V13Minus, 32.7), (Hueco::V13, 33.0), (Hueco::V13Plus, 33.3),
+    (Hueco::V14Minus, 33.7), (Hueco::V14, 34.0), (Hueco::V14Plus, 34.3),
+    (Hueco::V15Minus, 34.7), (Hueco::V15, 35.0), (Hueco::V15Plus, 35.3),
+    (Hueco::V16Minus, 35.7), (Hueco::V16, 36.0), (Hueco::V16Plus, 36.3),
+    (Hueco::V17Minus, 36.7), (Hueco::V17, 37.0), (Hueco::V17Plus, 37.3),
+];
+
+const FONT: [(Font, f64); 51] = [
+    (Font::OneMinus, 6.0), (Font::One, 6.5), (Font::OnePlus, 7.0),
+    (Font::TwoMinus, 7.5), (Font::Two, 8.0), (Font::TwoPlus, 8.5),
+    (Font::ThreeMinus, 9.5), (Font::Three, 10.0), (Font::ThreePlus, 10.5),
+    (Font::FourMinus, 12.5), (Font::Four, 13.0), (Font::FourPlus, 14.5),
+    (Font::FiveMinus, 15.5), (Font::Five, 16.0), (Font::FivePlus, 18.5),
+    (Font::SixAMinus, 19.8), (Font::SixA, 20.5), (Font::SixAPlus, 20.8),
+    (Font::SixBMinus, 21.7), (Font::SixB, 22.3), (Font::SixBPlus, 22.6),
+    (Font::SixCMinus, 22.9), (Font::SixC, 23.3), (Font::SixCPlus, 23.6),
+    (Font::SevenAMinus, 24.2), (Font::SevenA, 24.6), (Font::SevenAPlus, 26.2),
+    (Font::SevenBMinus, 26.9), (Font::SevenB, 27.8), (Font::SevenBPlus, 28.1),
+    (Font::SevenCMinus, 28.6), (Font::SevenC, 29.0), (Font::SevenCPlus, 30.0),
+    (Font::EightAMinus, 30.5), (Font::EightA, 31.0), (Font::EightAPlus, 32.0),
+    (Font::EightBMinus, 32.5), (Font::EightB, 33.0), (Font::EightBPlus, 34.0),
+    (Font::EightCMinus, 34.5), (Font::EightC, 35.0), (Font::EightCPlus, 36.0),
+    (Font::NineAMinus, 36.5), (Font::NineA, 37.0), (Font::NineAPlus, 37.5),
+    (Font::NineBMinus, 37.8), (Font::NineB, 38.1), (Font::NineBPlus, 38.4),
+    (Font::NineCMinus, 38.7), (Font::NineC, 39.0), (Font::NineCPlus, 39.3),
+];
+
+const SAXON: [(Saxon, f64); 24] = [
+    (Saxon::I, 3.0), (Saxon::Ii, 5.0), (Saxon::Iii, 7.0), (Saxon::Iv, 9.0),
+    (Saxon::V, 10.5), (Saxon::Vi, 12.0), (Saxon::ViiA, 13.0), (Saxon::ViiB, 14.0),
+    (Saxon::ViiC, 15.0), (Saxon::ViiiA, 16.0), (Saxon::ViiiB, 17.0), (Saxon::ViiiC, 18.0),
+    (Saxon::IxA, 19.0), (Saxon::IxB, 20.0), (Saxon::IxC, 21.0), (Saxon::XA, 22.0),
+    (Saxon::XB, 23.5), (Saxon::XC, 25.0), (Saxon::XiA, 26.5), (Saxon::XiB, 28.0),
+    (Saxon::XiC, 29.5), (Saxon::XiiA, 31.0), (Saxon::XiiB, 32.5), (Saxon::XiiC, 34.0),
+];
+
+const BRAZILIAN: [(Brazilian, f64); 30] = [
+    (Brazilian::I, 5.0), (Brazilian::ISup, 6.0), (Brazilian::Ii, 7.0), (Brazilian::IiSup, 8.0),
+    (Brazilian::Iii, 9.0), (Brazilian::IiiSup, 10.0), (Brazilian::Iv, 11.0), (Brazilian::IvSup, 12.0),
+    (Brazilian::V, 12.5), (Brazilian::VSup, 13.0), (Brazilian::Vi, 14.0), (Brazilian::ViSup, 15.0),
+    (Brazilian::SevenA, 16.0), (Brazilian::SevenB, 17.0), (Brazilian::SevenC, 18.0), (Brazilian::EightA, 19.0),
+    (Brazilian::EightB, 20.0), (Brazilian::EightC, 21.0), (Brazilian::NineA, 22.0), (Brazilian::NineB, 23.0),
+    (Brazilian::NineC, 24.0), (Brazilian::TenA, 25.0), (Brazilian::TenB, 26.0), (Brazilian::TenC, 27.0),
+    (Brazilian::ElevenA, 28.0), (Brazilian::ElevenB, 29.0), (Brazilian::ElevenC, 30.0), (Brazilian::TwelveA, 31.0),
+    (Brazilian::TwelveB, 32.0), (Brazilian::TwelveC, 33.0),
+];
+
+const KURTYKA: [(Kurtyka, f64); 28] = [
+    (Kurtyka::I, 4.0), (Kurtyka::Ii, 6.0), (Kurtyka::Iii, 8.0), (Kurtyka::Iv, 9.5),
+    (Kurtyka::IvPlus, 10.0), (Kurtyka::VMinus, 10.5), (Kurtyka::V, 11.0), (Kurtyka::VPlus, 12.0),
+    (Kurtyka::ViMinus, 12.5), (Kurtyka::Vi, 13.0), (Kurtyka::ViPlus, 14.0), (Kurtyka::ViOne, 15.5),
+    (Kurtyka::ViOnePlus, 17.0), (Kurtyka::ViTwo, 18.0), (Kurtyka::ViTwoPlus, 19.5), (Kurtyka::ViThree, 20.5),
+    (Kurtyka::ViThreePlus, 21.5), (Kurtyka::ViFour, 22.5), (Kurtyka::ViFourPlus, 23.5), (Kurtyka::ViFive, 24.5),
+    (Kurtyka::ViFivePlus, 26.0), (Kurtyka::ViSix, 27.0), (Kurtyka::ViSixPlus, 28.0), (Kurtyka::ViSeven, 29.5),
+    (Kurtyka::ViSevenPlus, 31.0), (Kurtyka::ViEight, 32.0), (Kurtyka::ViEightPlus, 33.5), (Kurtyka::ViNine, 35.0),
+];
+
+const EWBANK: [f64; 39] = [ // Indexed by the Ewbank number minus one
+    0.0, 1.0, 2.0, 3.0, 4.0, 4.5, 5.0, 5.5, 6.0, 7.0,
+    8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.5, 17.0, 18.0,
+    19.5, 21.0, 22.0, 23.0, 24.5, 26.0, 27.0, 28.0, 29.0, 30.0,
+    31.0, 32.0, 33.0, 34.0, 35.0, 36.0, 37.0, 37.5, 38.0,
+];
+
+// British grades are scored from the adjectival grade, then nudged by how the technical grade compares to the usual one for it
+const ADJECTIVAL: [(Adjectival, f64, f64); 21] = [ // (grade, score, usual technical grade as an index from 4a = 0)
+    (Adjectival::Moderate, 5.0, 0.0), (Adjectival::Difficult, 6.5, 0.0), (Adjectival::VeryDifficult, 8.0, 0.0),
+    (Adjectival::HardVeryDifficult, 9.0, 0.0), (Adjectival::MildSevere, 9.4, 0.0), (Adjectival::Severe, 10.0, 0.5),
+    (Adjectival::HardSevere, 11.0, 1.0), (Adjectival::MildVerySevere, 11.5, 1.5), (Adjectival::VerySevere, 12.0, 2.0),
+    (Adjectival::HardVerySevere, 14.0, 3.0), (Adjectival::E1, 15.5, 4.0), (Adjectival::E2, 17.5, 5.0),
+    (Adjectival::E3, 19.5, 5.5), (Adjectival::E4, 21.5, 6.0), (Adjectival::E5, 23.5, 7.0),
+    (Adjectival::E6, 25.5, 7.5), (Adjectival::E7, 27.5, 8.0), (Adjectival::E8, 29.5, 8.5),
+    (Adjectival::E9, 31.5, 9.0), (Adjectival::E10, 33.5, 10.0), (Adjectival::E11, 35.0, 10.5),
+];
+const TECHNICAL_STEP: f64 = 0.6; // Score per technical grade away from the usual one, small enough to stay between adjectival grades
+
+/// A grading system grades can be shown and converted in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum GradeSystem {
+    /// Decimal grades like 5.10a.
+    #[default]
+    Yosemite,
+    /// French sport grades like 6a+.
+    French,
+    /// UIAA grades like VII-.
+    Uiaa,
+    /// V grades like V4.
+    Hueco,
+    /// Fontainebleau boulder grades like 7A+.
+    Font,
+    /// British adjectival grades with an optional technical grade, like E3 5c.
+    British,
+    /// Australian grades, plain numbers like 21.
+    Ewbank,
+    /// Saxon grades like `VIIIa`.
+    Saxon,
+    /// Brazilian grades like Vsup or 11a.
+    Brazilian,
+    /// Polish grades like VI.2.
+    Kurtyka,
+}
+impl fmt::Display for GradeSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GradeSystem::Yosemite => write!(f, "Yosemite"),
+            GradeSystem::French => write!(f, "French"),
+            GradeSystem::Uiaa => write!(f, "UIAA"),
+            GradeSystem::Hueco => write!(f, "Hueco"),
+            GradeSystem::Font => write!(f, "Font"),
+            GradeSystem::British => write!(f, "British"),
+            GradeSystem::Ewbank => write!(f, "Ewbank"),
+            GradeSystem::Saxon => write!(f, "Saxon"),
+            GradeSystem::Brazilian => write!(f, "Brazilian"),
+            GradeSystem::Kurtyka => write!(f, "Kurtyka"),
+        }
+    }
+}
+impl FromStr for GradeSystem { // Inverse of Display, used for the grade system settings
+    type Err = String;
+
+    fn from_str(system: &str) -> Result<GradeSystem, String> {
+        GradeSystem::iter().find(|found| found.to_string() == system.trim()).ok_or_else(|| format!("Unknown grade system: {system}"))
+    }
+}
+impl GradeSystem {
+    /// Every grading system, in the order they're offered in settings.
+    pub fn iter() -> impl Iterator<Item = GradeSystem> {
+        [
+            GradeSystem::Yosemite,
+            GradeSystem::French,
+            GradeSystem::Uiaa,
+            GradeSystem::British,
+            GradeSystem::Ewbank,
+            GradeSystem::Saxon,
+            GradeSystem::Brazilian,
+            GradeSystem::Kurtyka,
+            GradeSystem::Hueco,
+            GradeSystem::Font,
+        ].iter().copied()
+    }
+
+    /// Every grade in the system, easiest first.
+    pub fn grades(self) -> Vec<Grade> {
+        match self {
+            GradeSystem::Yosemite => YOSEMITE.iter().map(|(grade, _)| Grade::Yosemite(*grade)).collect(),
+            GradeSystem::French => FRENCH.iter().map(|(grade, _)| Grade::French(*grade)).collect(),
+            GradeSystem::Uiaa => UIAA.iter().map(|(grade, _)| Grade::Uiaa(*grade)).collect(),
+            GradeSystem::Hueco => HUECO.iter().map(|(grade, _)| Grade::Hueco(*grade)).collect(),
+            GradeSystem::Font => FONT.iter().map(|(grade, _)| Grade::Font(*grade)).collect(),
+            GradeSystem::British => British::iter().map(Grade::British).collect(),
+            GradeSystem::Ewbank => Ewbank::iter().map(Grade::Ewbank).collect(),
+            GradeSystem::Saxon => SAXON.iter().map(|(grade, _)| Grade::Saxon(*grade)).collect(),
+            GradeSystem::Brazilian => BRAZILIAN.iter().map(|(grade, _)| Grade::Brazilian(*grade)).collect(),
+            GradeSystem::Kurtyka => KURTYKA.iter().map(|(grade, _)| Grade::Kurtyka(*grade)).collect(),
+        }
+    }
+
+    /// Whether the system grades boulder problems rather than roped routes.
+    pub fn is_boulder(self) -> bool {
+        matches!(self, GradeSystem::Hueco | GradeSystem::Font)
+    }
+
+    /// Grade or range typed in this system, "6a+/b" in French or "V4-5" in Hueco.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the text isn't a grade or range in this system.
+    pub fn parse_range(self, text: &str) -> Result<GradeRange<Grade>, GradeParseError> {
+        fn wrap<G: FromStr<Err = GradeParseError> + Copy + PartialOrd>(text: &str, to_grade: fn(G) -> Grade) -> Result<GradeRange<Grade>, GradeParseError> {
+            GradeRange::<G>::from_str(text).map(|range| GradeRange { low: to_grade(range.low), high: to_grade(range.high) })
+        }
+        match self {
+            GradeSystem::Yosemite => wrap(text, Grade::Yosemite),
+            GradeSystem::French => wrap(text, Grade::French),
+            GradeSystem::Uiaa => wrap(text, Grade::Uiaa),
+            GradeSystem::Hueco => wrap(text, Grade::Hueco),
+            GradeSystem::Font => wrap(text, Grade::Font),
+            GradeSystem::British => British::from_str(text).map(|grade| GradeRange { low: Grade::British(grade), high: Grade::British(grade) }), // British grades already pair two grades, so no ranges
+            GradeSystem::Ewbank => wrap(text, Grade::Ewbank),
+            GradeSystem::Saxon => wrap(text, Grade::Saxon),
+            GradeSystem::Brazilian => wrap(text, Grade::Brazilian),
+            GradeSystem::Kurtyka => wrap(text, Grade::Kurtyka),
+        }
+    }
+
+    fn scored(self) -> Vec<(Grade, f64)> {
+        self.grades().into_iter().filter_map(|grade| score(grade).map(|score| (grade, score))).collect()
+    }
+
+    fn is_finer_than(self, other: GradeSystem) -> bool { // More grades means finer, ties broken by the order of iter()
+        let position = |system: GradeSystem| GradeSystem::iter().position(|found| found == system);
+        let (own, others) = (self.grades().len(), other.grades().len());
+        own > others || (own == others && position(self) < position(other))
+    }
+}
+
+fn lookup<G: PartialEq + Copy>(table: &[(G, f64)], grade: G) -> Option<f64> {
+    table.iter().find(|(found, _)| *found == grade).map(|(_, score)| *score)
+}
+
+/// The system a grade belongs to.
+pub fn system(grade: Grade) -> GradeSystem {
+    match grade {
+        Grade::Yosemite(_) => GradeSystem::Yosemite,
+        Grade::French(_) => GradeSystem::French,
+        Grade::Uiaa(_) => GradeSystem::Uiaa,
+        Grade::Hueco(_) => GradeSystem::Hueco,
+        Grade::Font(_) => GradeSystem::Font,
+        Grade::British(_) => GradeSystem::British,
+        Grade::Ewbank(_) => GradeSystem::Ewbank,
+        Grade::Saxon(_) => GradeSystem::Saxon,
+        Grade::Brazilian(_) => GradeSystem::Brazilian,
+        Grade::Kurtyka(_) => GradeSystem::Kurtyka,
+    }
+}
+
+/// Where a grade sits on the shared difficulty scale, None for placeholder grades like `Yosemite::None`.
+pub fn score(grade: Grade) -> Option<f64> {
+    match grade {
+        Grade::Yosemite(grade) => lookup(&YOSEMITE, grade),
+        Grade::French(grade) => lookup(&FRENCH, grade),
+        Grade::Uiaa(grade) => lookup(&UIAA, grade),
+        Grade::Hueco(grade) => lookup(&HUECO, grade),
+        Grade::Font(grade) => lookup(&FONT, grade),
+        Grade::British(grade) => british_score(grade),
+        Grade::Ewbank(grade) => EWBANK.get(usize::from(grade.number()) - 1).copied(),
+        Grade::Saxon(grade) => lookup(&SAXON, grade),
+        Grade::Brazilian(grade) => lookup(&BRAZILIAN, grade),
+        Grade::Kurtyka(grade) => lookup(&KURTYKA, grade),
+    }
+}
+
+fn british_score(grade: British) -> Option<f64> {
+    let (_, score, usual) = ADJECTIVAL.iter().find(|(adjectival, _, _)| *adjectival == grade.adjectival)?;
+    let Some(technical) = grade.technical else { return Some(*score) };
+    let index = Technical::iter().position(|found| found == technical)?;
+    let index = f64::from(u8::try_from(index).ok()?);
+    Some(score + TECHNICAL_STEP * (index - usual))
+}
+
+fn nearest(target: f64, candidates: impl IntoIterator<Item = (Grade, f64)>) -> Option<Grade> {
+    // Closest grade on the scale, candidates come easiest first so the easier grade wins a tie
+    candidates.into_iter()
+        .fold(None, |best: Option<(Grade, f64)>, (candidate, candidate_score)| match best {
+            Some((_, best_score)) if (best_score - target).abs() <= (candidate_score - target).abs() => best,
+            _ => Some((candidate, candidate_score)),
+        })
+        .map(|(found, _)| found)
+}
+
+/// The equivalent grade in another system, None for placeholder grades.
+pub fn convert(grade: Grade, to: GradeSystem) -> Option<Grade> {
+    let from = system(grade);
+    let target = score(grade)?;
+    if from == to {
+        return Some(grade);
+    }
+    if from.is_finer_than(to) {
+        return nearest(target, to.scored());
+    }
+
+    // Coarse to fine, spread back over the finer grades that round to this one
+    let rounded: Vec<(Grade, Grade)> = to.scored().into_iter()
+        .filter_map(|(fine, fine_score)| nearest(fine_score, from.scored()).map(|coarse| (fine, coarse)))
+        .collect();
+    let mut hit: Vec<(Grade, f64)> = Vec::new(); // Coarse grades that some finer grade rounds to, easiest first
+    for (_, coarse) in &rounded {
+        if hit.last().map(|(last, _)| last) != Some(coarse) {
+            hit.push((*coarse, score(*coarse)?));
+        }
+    }
+    let coarse = if hit.iter().any(|(found, _)| *found == grade) { grade } else { nearest(target, hit)? };
+    let spread: Vec<Grade> = rounded.into_iter().filter(|(_, found)| *found == coarse).map(|(fine, _)| fine).collect();
+    spread.get((spread.len().max(1) - 1) / 2).copied()
+}
+
+/// A stored grade index (Yosemite for tall walls, Hueco for boulders) as shown in another system.
+pub fn index_name(index: i32, boulder: bool, to: GradeSystem) -> String {
+    let grade = if boulder { Grade::Hueco(Hueco::from(index)) } else { Grade::Yosemite(Yosemite::from(index)) };
+    convert(grade, to).unwrap_or(grade).to_string()
+}
+
+/// A grade or range picked in any system as saved on a route: Yosemite/Hueco, plus the top of the range if there is one.
+pub fn stored_range(low: Grade, high: Grade, boulder: bool) -> (String, Option<String>) {
+    let stored_system = if boulder { GradeSystem::Hueco } else { GradeSystem::Yosemite };
+    let (low, high) = if score(high) < score(low) { (high, low) } else { (low, high) };
+    let to_stored = |grade: Grade| convert(grade, stored_system).unwrap_or(grade).to_string();
+    let grade = to_stored(low);
+    let grade_high = Some(to_stored(high)).filter(|high| *high != grade);
+    (grade, grade_high)
+}
+
+/// A grade typed for a route or problem, tried in the preferred system, then the stored one, then any other of the discipline.
+pub fn parse_grade(text: &str, boulder: bool, preferred: GradeSystem) -> Option<Grade> {
+    // The bottom of a range is used, so "5.10c/d" gives 5.10c
+    let stored = if boulder { GradeSystem::Hueco } else { GradeSystem::Yosemite };
+    [preferred, stored].into_iter()
+        .chain(GradeSystem::iter().filter(|system| system.is_boulder() == boulder))
+        .find_map(|system| system.parse_range(text).ok())
+        .map(|range| range.low)
+}
+
+/// The grade in every supported system, including its own.
+pub fn equivalents(grade: Grade) -> Vec<Grade> {
+    GradeSystem::iter().filter_map(|to| if to == system(grade) { Some(grade) } else { convert(grade, to) }).collect()
+}
+
+/// A new grades table row for one grade, with its equivalent in every system of the same discipline.
+pub fn grade_row(grade: Grade) -> grades::Model {
+    let boulder = system(grade).is_boulder();
+    let name = |to: GradeSystem| if to.is_boulder() == boulder { convert(grade, to).map(|found| found.to_string()) } else { None };
+    grades::Model {
+        id: 0,
+        yosemite: name(GradeSystem::Yosemite),
+        hueco: name(GradeSystem::Hueco),
+        font: name(GradeSystem::Font),
+        french: name(GradeSystem::French),
+        uiaa: name(GradeSystem::Uiaa),
+        british: name(GradeSystem::British),
+        ewbank: name(GradeSystem::Ewbank),
+        saxon: name(GradeSystem::Saxon),
+        brazilian: name(GradeSystem::Brazilian),
+        kurtyka: name(GradeSystem::Kurtyka),
+    }
+}
+
+// Route lengths are stored in whole centimetres, whichever unit they were entered in
+const CM_PER_FOOT: f64 = 30.48;
+const CM_PER_METRE: f64 = 100.0;
+
+/// Unit route lengths are typed and shown in, they're always stored in centimetres.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum LengthUnit {
+    /// Feet, shown as ft.
+    #[default]
+    Feet,
+    /// Metres, shown as m.
+    Metres,
+}
+impl fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LengthUnit::Feet => write!(f, "Feet"),
+            LengthUnit::Metres => write!(f, "Metres"),
+        }
+    }
+}
+impl FromStr for LengthUnit { // Inverse of Display, used for the units setting
+    type Err = String;
+
+    fn from_str(unit: &str) -> Result<LengthUnit, String> {
+        LengthUnit::iter().find(|found| found.to_string() == unit.trim()).ok_or_else(|| format!("Unknown unit: {unit}"))
+    }
+}
+impl LengthUnit {
+    /// Every unit, in the order they're offered in settings.
+    pub fn iter() -> impl Iterator<Item = LengthUnit> {
+        [LengthUnit::Feet, LengthUnit::Metres].iter().copied()
+    }
+
+    /// Short name shown after a length, "ft" or "m".
+    pub fn suffix(self) -> &'static str {
+        match self {
+            LengthUnit::Feet => "ft",
+            LengthUnit::Metres => "m",
+        }
+    }
+
+    fn cm_per_unit(self) -> f64 {
+        match self {
+            LengthUnit::Feet => CM_PER_FOOT,
+            LengthUnit::Metres => CM_PER_METRE,
+        }
+    }
+
+    /// A length in this unit as stored, in whole centimetres.
+    pub fn to_cm(self, value: f64) -> i32 {
+        round_to_i32(value * self.cm_per_unit())
+    }
+
+    /// A stored length in this unit.
+    pub fn in_unit(self, cm: i32) -> f64 {
+        f64::from(cm) / self.cm_per_unit()
+    }
+
+    /// Stored length for display, "100 ft" or "30.5 m".
+    pub fn format(self, cm: i32) -> String {
+        let value = self.in_unit(cm);
+        let shown = match self {
+            LengthUnit::Feet => format!("{value:.0}"),
+            LengthUnit::Metres => format!("{value:.1}").trim_end_matches(".0").to_string(),
+        };
+        format!("{shown} {}", self.suffix())
+    }
+
+    /// A typed length in centimetres, "30m" and "100ft" pick their own unit, a bare number uses this one.
+    ///
+    /// # Errors
+    ///
+    /// Fails with a message saying how lengths are written when the text isn't a length.
+    pub fn parse(self, text: &str) -> Result<i32, String> {
+        let text = text.trim().to_lowercase();
+        let units = [
+            ("feet", LengthUnit::Feet), ("foot", LengthUnit::Feet), ("ft", LengthUnit::Feet), ("'", LengthUnit::Feet),
+            ("metres", LengthUnit::Metres), ("meters", LengthUnit::Metres), ("metre", LengthUnit::Metres), ("meter", LengthUnit::Metres), ("m", LengthUnit::Metres),
+        ];
+        let (number, unit) = units.iter()
+            .find_map(|(suffix, unit)| text.strip_suffix(suffix).map(|number| (number.trim(), *unit)))
+            .unwrap_or((text.as_str(), self));
+        match number.parse::<f64>() {
+            Ok(value) if value.is_finite() && value >= 0.0 && value * unit.cm_per_unit() <= f64::from(i32::MAX) => Ok(unit.to_cm(value)),
+            _ => Err(format!("Invalid length: {}", text.trim())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs() -> impl Iterator<Item = (GradeSystem, GradeSystem)> {
+        GradeSystem::iter().flat_map(|from| GradeSystem::iter().map(move |to| (from, to)))
+    }
+
+    #[test]
+    fn tables_are_strictly_increasing() {
+        for system in GradeSystem::iter() {
+            let scores: Vec<f64> = system.scored().into_iter().map(|(_, score)| score).collect();
+            assert_eq!(scores.len(), system.grades().len(), "{system} has grades without a score");
+            for pair in scores.windows(2) {
+                assert!(pair[0] < pair[1], "{system} scores are not increasing at {pair:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn conversion_is_monotonic() {
+        // A harder grade never converts to an easier one
+        for (from, to) in pairs() {
+            let converted: Vec<f64> = from.grades().into_iter()
+                .map(|grade| convert(grade, to).and_then(score).expect("every grade converts"))
+                .collect();
+            for pair in converted.windows(2) {
+                assert!(pair[0] <= pair[1], "{from} -> {to} is not monotonic");
+            }
+        }
+    }
+
+    #[test]
+    fn conversion_is_stable_on_round_trip() {
+        // Going there and back settles straight away, converting again gives the same grade
+        for (from, to) in pairs() {
+            for grade in from.grades() {
+                let there = convert(grade, to).unwrap();
+                let back = convert(there, from).unwrap();
+                assert_eq!(convert(back, to), Some(there), "{grade} ({from}) -> {there} ({to}) -> {back} is not stable");
+            }
+        }
+    }
+
+    #[test]
+    fn same_system_is_identity() {
+        for system in GradeSystem::iter() {
+            for grade in system.grades() {
+                assert_eq!(convert(grade, system), Some(grade));
+            }
+        }
+    }
+
+    #[test]
+    fn common_equivalents() {
+        assert_eq!(convert(Grade::Yosemite(Yosemite::FiveTenA), GradeSystem::French), Some(Grade::French(French::SixA)));
+        assert_eq!(convert(Grade::French(French::SixA), GradeSystem::Yosemite), Some(Grade::Yosemite(Yosemite::FiveTenA)));
+        assert_eq!(convert(Grade::Font(Font::SevenA), GradeSystem::Hueco), Some(Grade::Hueco(Hueco::V6)));
+        assert_eq!(convert(Grade::Hueco(Hueco::V6), GradeSystem::Font), Some(Grade::Font(Font::SevenA)));
+        assert_eq!(convert(Grade::Uiaa(Uiaa::Vii), GradeSystem::Yosemite), Some(Grade::Yosemite(Yosemite::FiveTenD)));
+        assert_eq!(convert(Grade::Yosemite(Yosemite::None), GradeSystem::French), None);
+        assert_eq!(convert(Grade::Yosemite(Yosemite::FiveTenA), GradeSystem::Ewbank), Ewbank::new(17).map(Grade::Ewbank));
+        assert_eq!(convert(Grade::Saxon(Saxon::ViiiA), GradeSystem::Yosemite), Some(Grade::Yosemite(Yosemite::FiveTenC)));
+        assert_eq!(convert(Grade::Kurtyka(Kurtyka::ViTwo), GradeSystem::Yosemite), Some(Grade::Yosemite(Yosemite::FiveElevenA)));
+        assert_eq!(convert(Grade::Brazilian(Brazilian::SevenA), GradeSystem::French), Some(Grade::French(French::SixBMinus)));
+    }
+
+    #[test]
+    fn british_technical_grade_shifts_score() {
+        let grade = |text: &str| Grade::British(text.parse::<British>().unwrap());
+        assert!(score(grade("E3 6a")) > score(grade("E3 5c")));
+        assert!(score(grade("E3 6a")) < score(grade("E4 6a")));
+        assert_eq!(convert(grade("HVS 5a"), GradeSystem::Yosemite), Some(Grade::Yosemite(Yosemite::FiveTenA)));
+    }
+
+    #[test]
+    fn stored_grades_in_preferred_systems() {
+        assert_eq!(index_name(Yosemite::FiveTenA as i32, false, GradeSystem::French), "6a");
+        assert_eq!(index_name(Hueco::V6 as i32 - 1, true, GradeSystem::Font), "7A");
+        assert_eq!(index_name(Yosemite::FiveTenA as i32, false, GradeSystem::Yosemite), "5.10a");
+        for system in GradeSystem::iter() {
+            assert_eq!(GradeSystem::from_str(&system.to_string()), Ok(system));
+        }
+    }
+
+    #[test]
+    fn ranges_parse_in_any_system() {
+        let range = GradeSystem::French.parse_range("6a+/b").unwrap();
+        assert_eq!((range.low, range.high), (Grade::French(French::SixAPlus), Grade::French(French::SixB)));
+        let range = GradeSystem::Hueco.parse_range("V4-5").unwrap();
+        assert_eq!((range.low, range.high), (Grade::Hueco(Hueco::V4), Grade::Hueco(Hueco::V5)));
+        let range = GradeSystem::Yosemite.parse_range("5.10c/d").unwrap();
+        assert_eq!((range.low, range.high), (Grade::Yosemite(Yosemite::FiveTenC), Grade::Yosemite(Yosemite::FiveTenD)));
+        assert!(GradeSystem::Yosemite.parse_range("V4").is_err());
+    }
+
+    #[test]
+    fn lengths_parse_with_either_unit() {
+        assert_eq!(LengthUnit::Feet.parse("100"), Ok(3048));
+        assert_eq!(LengthUnit::Feet.parse("30m"), Ok(3000));
+        assert_eq!(LengthUnit::Metres.parse("100 ft"), Ok(3048));
+        assert_eq!(LengthUnit::Metres.parse(" 25.5 M "), Ok(2550));
+        assert!(LengthUnit::Feet.parse("-10").is_err());
+        assert!(LengthUnit::Feet.parse("tall").is_err());
+        assert_eq!(LengthUnit::Feet.format(3048), "100 ft");
+        assert_eq!(LengthUnit::Metres.format(3048), "30.5 m");
+        assert_eq!(LengthUnit::Metres.format(3000), "30 m");
+        for unit in LengthUnit::iter() {
+            assert_eq!(LengthUnit::from_str(&unit.to_string()), Ok(unit));
+        }
+    }
+
+    #[test]
+    fn picked_grades_store_as_yosemite_or_hueco() {
+        let french = |text: &str| Grade::French(text.parse::<French>().unwrap());
+        assert_eq!(stored_range(french("6a"), french("6a"), false), ("5.10a".to_string(), None));
+        assert_eq!(stored_range(french("6b"), french("6a+"), false), ("5.10b".to_string(), Some("5.10c".to_string())));
+        let font = Grade::Font(Font::SevenA);
+        assert_eq!(stored_range(font, font, true), ("V6".to_string(), None));
+    }
+
+    #[test]
+    fn backfill_migration_matches_the_conversions() {
+        // The migration keeps its own copy of the conversions, it has to agree with the table it was made from
+        let tall = GradeSystem::Yosemite.grades().into_iter().filter_map(|grade| score(grade).map(|_| grade_row(grade)));
+        assert_eq!(tall.clone().count(), migration::TALL_EQUIVALENTS.len());
+        for (row, (yosemite, equivalents)) in tall.zip(migration::TALL_EQUIVALENTS) {
+            assert_eq!(row.yosemite.as_deref(), Some(yosemite));
+            let converted = [row.french, row.uiaa, row.british, row.ewbank, row.saxon, row.brazilian, row.kurtyka];
+            for ((column, found), expected) in migration::TALL_COLUMNS.into_iter().zip(converted).zip(equivalents) {
+                assert_eq!(found.as_deref(), Some(expected), "{yosemite} in {column}");
+            }
+        }
+        let boulder = GradeSystem::Hueco.grades().into_iter().map(grade_row);
+        assert_eq!(boulder.clone().count(), migration::BOULDER_EQUIVALENTS.len());
+        for (row, (hueco, font)) in boulder.zip(migration::BOULDER_EQUIVALENTS) {
+            assert_eq!((row.hueco.as_deref(), row.font.as_deref()), (Some(hueco), Some(font)));
+        }
+    }
+
+    #[test]
+    fn grade_rows_stay_in_their_discipline() {
+        let row = grade_row(Grade::French(French::SixA));
+        assert_eq!((row.yosemite.as_deref(), row.french.as_deref(), row.hueco), (Some("5.10a"), Some("6a"), None));
+        let row = grade_row(Grade::Font(Font::SevenA));
+        assert_eq!((row.hueco.as_deref(), row.font.as_deref(), row.yosemite), (Some("V6"), Some("7A"), None));
+    }
+}
@@ -0,0 +1,249 @@
+use std::{fmt::{self, Display}, str::FromStr};
+use chrono::NaiveDate;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sea_orm::DbErr;
+use crate::climbing::{Danger, Discipline, GradeOpinion, SendType, Style};
+use crate::conversion::{self, GradeSystem};
+use crate::export::ExportedRoute;
+use crate::routes_db::entities::routes::Model as RouteModel;
+use crate::store::LogbookStore;
+use crate::settings::Settings;
+
+// Adding routes and logging sessions from typed text, shared by the command line and the server
+// Grades and lengths are read the same way as on the add route page, in the preferred system and unit
+
+/// Why an entry couldn't be added.
+#[derive(Debug)]
+pub enum EntryError {
+    /// Something typed didn't parse, like an unknown grade or style.
+    Invalid(String),
+    /// A route the entry refers to isn't in the logbook.
+    NotFound(String),
+    /// A route with the same name is already in the logbook.
+    Exists(String),
+    /// The store failed.
+    Db(DbErr),
+}
+impl Display for EntryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EntryError::Invalid(message) | EntryError::NotFound(message) | EntryError::Exists(message) => write!(f, "{message}"),
+            EntryError::Db(error) => write!(f, "database error: {error}"),
+        }
+    }
+}
+impl From<DbErr> for EntryError {
+    fn from(error: DbErr) -> EntryError {
+        EntryError::Db(error)
+    }
+}
+
+/// The option whose name matches the text, ignoring case, spaces and punctuation.
+///
+/// # Errors
+///
+/// Fails with [`EntryError::Invalid`] naming the kind when nothing matches.
+pub fn find_named<T: Display>(options: impl Iterator<Item = T>, kind: &str, text: &str) -> Result<T, EntryError> {
+    // Case and spacing don't matter, so "top-rope" finds Top Rope
+    let key = |text: &str| text.chars().filter(char::is_ascii_alphanumeric).collect::<String>().to_lowercase();
+    let mut options = options;
+    options.find(|option| key(&option.to_string()) == key(text)).ok_or_else(|| EntryError::Invalid(format!("unknown {kind}: {text}")))
+}
+
+fn default_pitches() -> i32 {
+    1
+}
+
+/// A route as typed, before its grade, length and styles are checked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct NewRoute {
+    /// Name, which no other route can have.
+    pub name: String,
+    /// Preferred grading system or Yosemite/Hueco, slash grades and ranges like 5.10c/d work too.
+    pub grade: String,
+    /// "30m" or "100ft", bare numbers use the units setting.
+    pub length: String,
+    /// Pitches, 1 if left out. Boulders always have none.
+    #[serde(default = "default_pitches")]
+    pub pitches: i32,
+    /// Crag, left out or blank for none.
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Style names, at least one.
+    pub styles: Vec<String>,
+    /// Whether it's a boulder problem rather than a tall wall route.
+    #[serde(default)]
+    pub boulder: bool,
+    /// G, PG, PG-13, R or X.
+    #[serde(default)]
+    pub danger: Option<String>,
+}
+impl NewRoute {
+    /// Checks the route and adds it to the logbook.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`EntryError::Exists`] when the name is taken, [`EntryError::Invalid`] when anything typed doesn't parse, and [`EntryError::Db`] when the store fails.
+    pub async fn add(self, db: impl LogbookStore, settings: &Settings) -> Result<ExportedRoute, EntryError> {
+        if self.name.trim().is_empty() {
+            return Err(EntryError::Invalid("routes need a name".to_string()));
+        }
+        if db.clone().find_route_name(&self.name).await?.is_some() {
+            return Err(EntryError::Exists(format!("a route named {} already exists", self.name)));
+        }
+        let styles = self.styles.iter().map(|style| find_named(Style::iter(), "style", style)).collect::<Result<Vec<Style>, EntryError>>()?;
+        if styles.is_empty() {
+            return Err(EntryError::Invalid("routes need at least one style".to_string()));
+        }
+        let danger = self.danger.as_deref()
+            .map(|danger| danger.parse::<Danger>().map_err(|error| EntryError::Invalid(error.to_string())))
+            .transpose()?;
+
+        // Grades are stored as Yosemite/Hueco whichever system they were typed in
+        let stored_system = if self.boulder { GradeSystem::Hueco } else { GradeSystem::Yosemite };
+        let range = settings.grade_system(self.boulder).parse_range(&self.grade)
+            .or_else(|_| stored_system.parse_range(&self.grade))
+            .map_err(|error| EntryError::Invalid(error.to_string()))?;
+        let (grade, grade_high) = conversion::stored_range(range.low, range.high, self.boulder);
+        let grade_id = db.clone().get_all_grades().await?.into_iter()
+            .find(|found| found.yosemite.as_deref() == Some(grade.as_str()) || found.hueco.as_deref() == Some(grade.as_str()))
+            .map(|found| found.id)
+            .ok_or_else(|| EntryError::Invalid(format!("{grade} isn't in the grades table yet")))?;
+        let length = settings.units.parse(&self.length).map_err(EntryError::Invalid)?;
+        let discipline = if self.boulder { Discipline::Boulder } else { Discipline::TallWall };
+
+        let route = RouteModel {
+            id: 0,
+            name: self.name.clone(),
+            length,
+            pitches: if self.boulder { 0 } else { self.pitches.max(1) },
+            grade_id,
+            location: self.location.filter(|location| !location.trim().is_empty()),
+            ice_grade: None,
+            mixed_grade: None,
+            aid_grade: None,
+            commitment_grade: None,
+            ifas_grade: None,
+            danger: danger.filter(|_| !self.boulder).map(|danger| danger.to_string()),
+            grade_high,
+            discipline: discipline.to_string(),
+        };
+        let style_names: Vec<String> = styles.iter().map(ToString::to_string).collect();
+        db.clone().add_route(route, style_names.clone()).await?;
+        let (route, grade) = db.find_route_and_grade(&self.name).await?;
+        Ok(ExportedRoute::new(&route, &grade, &style_names))
+    }
+}
+
+/// A send as typed, of a route already in the logbook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct NewSend {
+    /// Route name.
+    pub route: String,
+    /// Onsight if left out, like the log session page.
+    #[serde(default, rename = "type")]
+    pub send_type: Option<String>,
+    /// Tries it took, 1 if left out.
+    #[serde(default)]
+    pub attempts: Option<i32>,
+    /// Notes, left out or blank for none.
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Partner names, new names are added as partners.
+    #[serde(default)]
+    pub partners: Vec<String>,
+    /// "Soft", "Hard" or the climber's own grade.
+    #[serde(default)]
+    pub felt: Option<String>,
+}
+
+/// A day's sends as typed, logged together as one session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct NewSession {
+    /// YYYY-MM-DD, today if left out.
+    #[serde(default)]
+    pub date: Option<String>,
+    /// Add to an existing session instead of starting a new one.
+    #[serde(default)]
+    pub session: Option<i32>,
+    /// Climber name, whoever is logging if left out.
+    #[serde(default)]
+    pub climber: Option<String>,
+    /// The sends, at least one.
+    pub sends: Vec<NewSend>,
+}
+
+/// Where a logged session ended up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LoggedSession {
+    /// Session id the sends were logged under.
+    pub session: i32,
+    /// Day logged, YYYY-MM-DD.
+    pub date: String,
+    /// How many sends were logged.
+    pub sends: usize,
+}
+
+impl NewSession {
+    /// Checks every send and logs them all, or none of them if any is wrong.
+    ///
+    /// Sends go to the named climber, or to `climber` when the session doesn't name one.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`EntryError::NotFound`] when a route or the climber isn't in the logbook, [`EntryError::Invalid`] when anything typed doesn't parse, and [`EntryError::Db`] when the store fails.
+    pub async fn log(self, db: impl LogbookStore, climber: i32) -> Result<LoggedSession, EntryError> {
+        if self.sends.is_empty() {
+            return Err(EntryError::Invalid("sessions need at least one send".to_string()));
+        }
+        let date = match self.date.as_deref() {
+            Some(date) => date.trim().parse::<NaiveDate>().map_err(|_| EntryError::Invalid(format!("dates are YYYY-MM-DD, not {date}")))?,
+            None => chrono::Local::now().date_naive(),
+        };
+
+        // Check everything first, so a typo doesn't leave half a session behind
+        let climber = match self.climber.as_deref() {
+            Some(name) => db.clone().get_all_climbers().await?.into_iter()
+                .find(|found| found.name.eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| EntryError::NotFound(format!("no climber named {name}")))?.id,
+            None => climber,
+        };
+        let mut checked = Vec::new();
+        for send in &self.sends {
+            let route = db.clone().find_route_name(&send.route).await?.ok_or_else(|| EntryError::NotFound(format!("no route named {}", send.route)))?;
+            let send_type = match send.send_type.as_deref() {
+                Some(send_type) => find_named(SendType::iter(), "send type", send_type)?,
+                None => SendType::Onsight,
+            };
+            let opinion = match send.felt.as_deref().map(str::trim) {
+                Some(felt) if felt.eq_ignore_ascii_case("soft") => GradeOpinion::Soft,
+                Some(felt) if felt.eq_ignore_ascii_case("hard") => GradeOpinion::Hard,
+                felt => GradeOpinion::from(felt),
+            };
+            if let GradeOpinion::Grade(grade) = &opinion {
+                // Checked against any system for the route's discipline, stats read it in the preferred one first
+                let boulder = Discipline::from_str(&route.discipline) == Ok(Discipline::Boulder);
+                let preferred = if boulder { GradeSystem::Hueco } else { GradeSystem::Yosemite };
+                if conversion::parse_grade(grade, boulder, preferred).is_none() {
+                    return Err(EntryError::Invalid(format!("{grade} is not a grade for {}", send.route)));
+                }
+            }
+            checked.push((route, send_type, opinion));
+        }
+
+        let session = match self.session {
+            Some(session) => session,
+            None => db.clone().get_next_session_id().await?,
+        };
+        for (send, (route, send_type, opinion)) in self.sends.iter().zip(checked) {
+            let mut partners = Vec::new();
+            for name in send.partners.iter().map(|name| name.trim()).filter(|name| !name.is_empty()) {
+                partners.push(db.clone().add_partner(name).await?);
+            }
+            let notes = send.notes.clone().filter(|notes| !notes.trim().is_empty());
+            db.clone().add_send(climber, session, route, date.to_string(), partners, send_type.to_string(), send.attempts.unwrap_or(1).max(1), notes, opinion.stored()).await?;
+        }
+        Ok(LoggedSession { session, date: date.to_string(), sends: self.sends.len() })
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sea_orm::DbErr;
+use crate::logbook::{Logbook, LogbookData};
+use crate::store::LogbookStore;
+use crate::routes_db::entities::{climbers::Model as ClimberModel, grades::Model as GradeModel, partners::Model as PartnerModel, routes::Model as RouteModel, send_partners::Model as SendPartnerModel, sends::Model as SendModel};
+
+// Whole logbook as a single JSON file
+// Routes, grades, climbers and partners go by name instead of id, so an export can be merged into a different logbook
+// Version 2 added the climber on each send, version 1 files import as the climber doing the import
+
+/// Version written into new exports. Older versions still import.
+pub const EXPORT_VERSION: u32 = 2;
+
+/// A route as exported, with its grade and styles by name so it can go into another logbook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExportedRoute {
+    /// The route, with its grade id only meaningful in the logbook it came from.
+    #[serde(flatten)]
+    pub route: RouteModel,
+    /// Yosemite grade for tall walls, Hueco for boulders.
+    pub grade: String,
+    /// Style names.
+    pub styles: Vec<String>,
+}
+impl ExportedRoute {
+    /// A route with its grade and style names, the grade in the stored system of its discipline.
+    pub fn new(route: &RouteModel, grade: &GradeModel, styles: &[String]) -> ExportedRoute {
+        let stored = if Logbook::is_boulder(route) { grade.hueco.clone() } else { grade.yosemite.clone() };
+        ExportedRoute {
+            route: route.clone(),
+            grade: stored.or_else(|| grade.yosemite.clone()).or_else(|| grade.hueco.clone()).unwrap_or_default(),
+            styles: styles.to_vec(),
+        }
+    }
+}
+
+/// A send as exported, with its route, climber and partners by name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExportedSend {
+    /// The send, with its ids only meaningful in the logbook it came from.
+    #[serde(flatten)]
+    pub send: SendModel,
+    /// Name of the route sent.
+    pub route_name: String,
+    /// Empty in version 1 exports.
+    #[serde(default)]
+    pub climber_name: String,
+    /// Partner names.
+    pub partners: Vec<String>,
+}
+impl ExportedSend {
+    /// A send with its names looked up in a loaded logbook.
+    pub fn from_data(send: &SendModel, data: &LogbookData) -> ExportedSend {
+        ExportedSend::new(send, &data.routes, &data.climbers, &data.partners, &data.send_partners)
+    }
+
+    /// A send with its names looked up in the given tables, empty where an id isn't found.
+    pub fn new(send: &SendModel, routes: &[(RouteModel, GradeModel)], climbers: &[ClimberModel], partners: &[PartnerModel], send_partners: &[SendPartnerModel]) -> ExportedSend {
+        ExportedSend {
+            send: send.clone(),
+            route_name: routes.iter().find(|(route, _)| route.id == send.route).map_or_else(String::new, |(route, _)| route.name.clone()),
+            climber_name: climbers.iter().find(|climber| climber.id == send.climber).map_or_else(String::new, |climber| climber.name.clone()),
+            partners: send_partners.iter()
+                .filter(|link| link.send == send.id)
+                .filter_map(|link| partners.iter().find(|partner| partner.id == link.partner).map(|partner| partner.name.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// What an import added, and what it left alone.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ImportSummary {
+    /// New routes added.
+    pub routes_added: usize,
+    /// Routes already in the logbook under the same name, left as they were.
+    pub routes_kept: usize,
+    /// Sessions added, each with a new id.
+    pub sessions_added: usize,
+    /// Sends added.
+    pub sends_added: usize,
+}
+
+/// A whole logbook, written and read as JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogbookExport {
+    /// Export format version, see [`EXPORT_VERSION`].
+    pub version: u32,
+    /// Every route.
+    pub routes: Vec<ExportedRoute>,
+    /// Every send, or one climber's.
+    pub sends: Vec<ExportedSend>,
+}
+impl LogbookExport {
+    /// Collects everything in the logbook, or every route and one climber's sends.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the logbook can't be read.
+    pub async fn build(db: impl LogbookStore, climber: Option<i32>) -> Result<LogbookExport, DbErr> {
+        let mut data = LogbookData::load(db).await?;
+        if let Some(climber) = climber {
+            data = data.for_climber(climber);
+        }
+        Ok(LogbookExport {
+            version: EXPORT_VERSION,
+            routes: data.routes.iter().map(|(route, grade)| ExportedRoute::new(route, grade, &data.style_names(route.id))).collect(),
+            sends: data.sends.iter().map(|send| ExportedSend::from_data(send, &data)).collect(),
+        })
+    }
+
+    /// The export as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Fails when serialising fails, which plain data like this never does.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Reads an export written by [`LogbookExport::to_json`], of any version.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the text isn't an export.
+    pub fn from_json(json: &str) -> serde_json::Result<LogbookExport> {
+        serde_json::from_str(json)
+    }
+
+    /// Merges the export into a logbook, keeping routes it already has.
+    ///
+    /// Sends go to the climber of the same name, added if they're new, and sends without one go to `climber`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`DbErr::RecordNotFound`] when a route's grade isn't in the logbook's grades table, or when the store fails. Whatever was added before that stays.
+    pub async fn import(self, db: impl LogbookStore, climber: i32) -> Result<ImportSummary, DbErr> {
+        // Adds everything in the export to the logbook, sessions get new ids so they never merge with existing ones
+        let mut summary = ImportSummary::default();
+        let grades = db.clone().get_all_grades().await?;
+        for exported in self.routes {
+            if db.clone().find_route_name(&exported.route.name).await?.is_some() {
+                summary.routes_kept += 1;
+                continue;
+            }
+            let grade = grades.iter()
+                .find(|grade| grade.yosemite.as_deref() == Some(exported.grade.as_str()) || grade.hueco.as_deref() == Some(exported.grade.as_str()))
+                .ok_or_else(|| DbErr::RecordNotFound(format!("Grade {} of {}", exported.grade, exported.route.name)))?;
+            let route = RouteModel { grade_id: grade.id, ..exported.route };
+            db.clone().add_route(route, exported.styles).await?;
+            summary.routes_added += 1;
+        }
+
+        let mut sends = self.sends;
+        sends.sort_by_key(|exported| (exported.send.session, exported.send.id));
+        let mut sessions: HashMap<i32, i32> = HashMap::new();
+        for exported in sends {
+            let route = db.clone().find_route_name(&exported.route_name).await?
+                .ok_or_else(|| DbErr::RecordNotFound(format!("Route {}", exported.route_name)))?;
+            let session = if let Some(session) = sessions.get(&exported.send.session) {
+                *session
+            } else {
+                let session = db.clone().get_next_session_id().await?;
+                sessions.insert(exported.send.session, session);
+                summary.sessions_added += 1;
+                session
+            };
+            let mut partners = Vec::new();
+            for name in &exported.partners {
+                partners.push(db.clone().add_partner(name).await?);
+            }
+            let send_climber = match exported.climber_name.trim() {
+                "" => climber,
+                name => db.clone().add_climber(name).await?,
+            };
+            let send = exported.send;
+            db.clone().add_send(send_climber, session, route, send.date, partners, send.r#type, send.attempts, send.notes, send.personal_grade).await?;
+            summary.sends_added += 1;
+        }
+        Ok(summary)
+    }
+}
@@ -0,0 +1,453 @@
+use std::{collections::BTreeMap, fmt::{self, Display}};
+use chrono::NaiveDate;
+use sea_orm::{sea_query::{Expr, Func, LikeExpr, Query, SimpleExpr}, ColumnTrait, Condition, DbErr};
+use crate::climbing::{Discipline, Hueco, SendType, Style, Yosemite};
+use crate::conversion::{self, GradeSystem};
+use crate::entry::{self, EntryError};
+use crate::logbook::Logbook;
+use crate::settings::Settings;
+use crate::routes_db::entities::{grades, partners, route_styles, routes, send_partners, sends, styles};
+use crate::store::LogbookStore;
+
+// Filter expressions like `grade>=5.11a style:trad type:onsight crag:"Red River Gorge" after:2024-01-01`
+// Every term has to match, and a leading - turns a term into an exclusion
+// RoutesDb compiles filters into SeaORM conditions over sends joined to their routes and grades,
+// MemoryStore checks the same terms in Rust
+
+/// Settings key the saved filters are kept under, as a JSON object of name to filter.
+pub const SAVED_FILTERS_KEY: &str = "saved_filters";
+
+/// Fields a filter can use, for help text and errors.
+pub const FIELDS: &str = "grade, style, discipline, crag, route, pitches, type, partner, notes, attempts, date, after, before";
+
+/// Why a filter couldn't be read, as a message for the climber.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterError(pub String);
+impl Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl From<FilterError> for EntryError {
+    fn from(error: FilterError) -> EntryError {
+        EntryError::Invalid(error.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq, // : or =
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+impl Op {
+    fn compare<T: Ord + ?Sized>(self, left: &T, right: &T) -> bool {
+        match self {
+            Op::Eq => left == right,
+            Op::Ne => left != right,
+            Op::Gt => left > right,
+            Op::Ge => left >= right,
+            Op::Lt => left < right,
+            Op::Le => left <= right,
+        }
+    }
+
+    fn column<C: ColumnTrait, V: Into<sea_orm::Value>>(self, column: C, value: V) -> SimpleExpr {
+        match self {
+            Op::Eq => column.eq(value),
+            Op::Ne => column.ne(value),
+            Op::Gt => column.gt(value),
+            Op::Ge => column.gte(value),
+            Op::Lt => column.lt(value),
+            Op::Le => column.lte(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    // On the route
+    Grade(Op, bool, i32), // Whether it's a boulder grade, and its stored index, typed in any system. Ranges count by their low grade
+    Style(Style),
+    Discipline(Discipline),
+    Crag(String), // Lowercase, anywhere in the location
+    Route(String), // Lowercase, anywhere in the name
+    Text(String), // A bare word, anywhere in the name or location
+    Pitches(Op, i32),
+    // On the send
+    SendType(SendType),
+    Partner(String), // Lowercase, the whole name
+    Notes(String), // Lowercase, anywhere in the notes
+    Attempts(Op, i32),
+    Date(Op, NaiveDate),
+}
+
+fn contains(column: impl sea_orm::sea_query::IntoColumnRef, text: &str) -> SimpleExpr {
+    // Any case, with LIKE's wildcards in the text taken literally
+    let escaped = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    Expr::expr(Func::lower(Expr::col(column))).like(LikeExpr::new(format!("%{escaped}%")).escape('\\'))
+}
+
+impl Term {
+    fn on_send(&self) -> bool {
+        matches!(self, Term::SendType(_) | Term::Partner(_) | Term::Notes(_) | Term::Attempts(..) | Term::Date(..))
+    }
+
+    fn expr(&self) -> SimpleExpr {
+        // Nullable columns are checked for NULL first, so excluding a term keeps the rows it can't say anything about
+        match self {
+            Term::Grade(op, boulder, index) => {
+                let (column, names): (grades::Column, Vec<String>) = if *boulder {
+                    (grades::Column::Hueco, Hueco::iter().filter(|grade| op.compare(&(*grade as i32 - 1), index)).map(|grade| grade.to_string()).collect())
+                } else {
+                    (grades::Column::Yosemite, Yosemite::iter().filter(|grade| op.compare(&(*grade as i32), index)).map(|grade| grade.to_string()).collect())
+                };
+                column.is_not_null().and(column.is_in(names))
+            }
+            Term::Style(style) => routes::Column::Id.in_subquery(Query::select()
+                .column((route_styles::Entity, route_styles::Column::Route))
+                .from(route_styles::Entity)
+                .inner_join(styles::Entity, Expr::col((styles::Entity, styles::Column::Id)).equals((route_styles::Entity, route_styles::Column::Style)))
+                .and_where(styles::Column::Name.eq(style.to_string()))
+                .to_owned()),
+            Term::Discipline(discipline) => routes::Column::Discipline.eq(discipline.to_string()),
+            Term::Crag(text) => routes::Column::Location.is_not_null().and(contains((routes::Entity, routes::Column::Location), text)),
+            Term::Route(text) => contains((routes::Entity, routes::Column::Name), text),
+            Term::Text(text) => Term::Route(text.clone()).expr().or(Term::Crag(text.clone()).expr()),
+            Term::Pitches(op, pitches) => op.column(routes::Column::Pitches, *pitches),
+            Term::SendType(send_type) => sends::Column::Type.eq(send_type.to_string()),
+            Term::Partner(name) => sends::Column::Id.in_subquery(Query::select()
+                .column((send_partners::Entity, send_partners::Column::Send))
+                .from(send_partners::Entity)
+                .inner_join(partners::Entity, Expr::col((partners::Entity, partners::Column::Id)).equals((send_partners::Entity, send_partners::Column::Partner)))
+                .and_where(Expr::expr(Func::lower(Expr::col((partners::Entity, partners::Column::Name)))).eq(name.as_str()))
+                .to_owned()),
+            Term::Notes(text) => sends::Column::Notes.is_not_null().and(contains((sends::Entity, sends::Column::Notes), text)),
+            Term::Attempts(op, attempts) => op.column(sends::Column::Attempts, *attempts),
+            // Dates are stored as YYYY-MM-DD, so comparing the text keeps them in order
+            Term::Date(op, date) => op.column(sends::Column::Date, date.to_string()),
+        }
+    }
+
+    fn matches(&self, route: &routes::Model, grade: &grades::Model, styles: &[Style], send: Option<(&sends::Model, &[String])>) -> bool {
+        let has = |text: Option<&str>, wanted: &str| text.is_some_and(|text| text.to_lowercase().contains(wanted));
+        match self {
+            Term::Grade(op, boulder, index) => {
+                let found = if *boulder { grade.hueco.as_deref().and_then(Logbook::parse_hueco) } else { grade.yosemite.as_deref().and_then(Logbook::parse_yosemite) };
+                found.is_some_and(|found| op.compare(&found, index))
+            }
+            Term::Style(style) => styles.contains(style),
+            Term::Discipline(discipline) => route.discipline == discipline.to_string(),
+            Term::Crag(text) => has(route.location.as_deref(), text),
+            Term::Route(text) => has(Some(&route.name), text),
+            Term::Text(text) => has(Some(&route.name), text) || has(route.location.as_deref(), text),
+            Term::Pitches(op, pitches) => op.compare(&route.pitches, pitches),
+            Term::SendType(send_type) => send.is_some_and(|(send, _)| send.r#type == send_type.to_string()),
+            Term::Partner(name) => send.is_some_and(|(_, partners)| partners.iter().any(|partner| partner.to_lowercase() == *name)),
+            Term::Notes(text) => send.is_some_and(|(send, _)| has(send.notes.as_deref(), text)),
+            Term::Attempts(op, attempts) => send.is_some_and(|(send, _)| op.compare(&send.attempts, attempts)),
+            Term::Date(op, date) => send.is_some_and(|(send, _)| op.compare(send.date.as_str(), date.to_string().as_str())),
+        }
+    }
+}
+
+fn tokens(text: &str) -> Result<Vec<(String, String)>, FilterError> {
+    // Split on spaces outside quotes, as (token without the quotes, token as typed)
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut typed = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                typed.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !typed.is_empty() {
+                    tokens.push((std::mem::take(&mut current), std::mem::take(&mut typed)));
+                }
+            }
+            c => {
+                current.push(c);
+                typed.push(c);
+            }
+        }
+    }
+    if quoted {
+        return Err(FilterError("a quote is never closed".to_string()));
+    }
+    if !typed.is_empty() {
+        tokens.push((current, typed));
+    }
+    Ok(tokens.into_iter().filter(|(token, _)| !token.is_empty()).collect())
+}
+
+fn named<T: Display>(options: impl Iterator<Item = T>, kind: &str, text: &str) -> Result<T, FilterError> {
+    entry::find_named(options, kind, text).map_err(|error| FilterError(error.to_string()))
+}
+
+fn term(field: &str, op: Op, value: &str, settings: &Settings) -> Result<Term, FilterError> {
+    let equality = |term: Term| match op {
+        Op::Eq => Ok(term),
+        _ => Err(FilterError(format!("{field} can only be matched with : or !="))),
+    };
+    let number = |value: &str| value.parse::<i32>().map_err(|_| FilterError(format!("{field} needs a number, not {value}")));
+    let date = |value: &str| NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| FilterError(format!("{field} needs a YYYY-MM-DD date, not {value}")));
+    match field.to_lowercase().as_str() {
+        // In the preferred grading systems first, like search bounds
+        "grade" => settings.any_grade_index(value).map(|(boulder, index)| Term::Grade(op, boulder, index))
+            .ok_or_else(|| FilterError(format!("unknown grade: {value}"))),
+        "style" => equality(Term::Style(named(Style::iter(), "style", value)?)),
+        "discipline" => equality(Term::Discipline(named([Discipline::Boulder, Discipline::TallWall].into_iter(), "discipline", value)?)),
+        "crag" | "location" => equality(Term::Crag(value.to_lowercase())),
+        "route" | "name" => equality(Term::Route(value.to_lowercase())),
+        "pitches" => Ok(Term::Pitches(op, number(value)?)),
+        "type" => equality(Term::SendType(named(SendType::iter(), "send type", value)?)),
+        "partner" | "with" => equality(Term::Partner(value.to_lowercase())),
+        "notes" => equality(Term::Notes(value.to_lowercase())),
+        "attempts" => Ok(Term::Attempts(op, number(value)?)),
+        "date" => Ok(Term::Date(op, date(value)?)),
+        // On or after the day, and before it
+        "after" => equality(Term::Date(Op::Ge, date(value)?)),
+        "before" => equality(Term::Date(Op::Lt, date(value)?)),
+        _ => Err(FilterError(format!("unknown field: {field}, use one of {FIELDS}"))),
+    }
+}
+
+/// A parsed filter expression. The empty filter lets everything through.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter {
+    text: String, // As typed
+    stored: String, // With grades in the stored Yosemite/Hueco names, so it reads the same whatever the settings
+    terms: Vec<(bool, Term)>, // Whether the term is excluded, and the term
+}
+
+impl Filter {
+    /// Parses a filter expression, reading grades in the settings' grading systems first.
+    ///
+    /// # Errors
+    ///
+    /// Fails when a term has an unknown field, an operator the field doesn't take, or a value that doesn't parse, like an unknown grade.
+    pub fn parse(text: &str, settings: &Settings) -> Result<Filter, FilterError> {
+        let mut terms = Vec::new();
+        let mut stored = Vec::new();
+        for (token, typed) in tokens(text)? {
+            let (excluded, token) = match token.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, token.as_str()),
+            };
+            // A field name, an operator and a value, or else a bare word
+            let field_end = token.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(token.len());
+            let (field, rest) = token.split_at(field_end);
+            let op = [(">=", Op::Ge), ("<=", Op::Le), ("!=", Op::Ne), (">", Op::Gt), ("<", Op::Lt), (":", Op::Eq), ("=", Op::Eq)]
+                .into_iter().find(|(symbol, _)| rest.starts_with(symbol));
+            match op {
+                Some((symbol, op)) if !field.is_empty() => {
+                    let value = rest[symbol.len()..].trim();
+                    if value.is_empty() {
+                        return Err(FilterError(format!("{field}{symbol} needs a value")));
+                    }
+                    // != on a field that can only be matched is the same as excluding it
+                    match term(field, op, value, settings) {
+                        Err(_) if op == Op::Ne => terms.push((!excluded, term(field, Op::Eq, value, settings)?)),
+                        Ok(Term::Grade(op, boulder, index)) => {
+                            let system = if boulder { GradeSystem::Hueco } else { GradeSystem::Yosemite };
+                            let sign = if excluded { "-" } else { "" };
+                            stored.push(format!("{sign}{field}{symbol}{}", conversion::index_name(index, boulder, system)));
+                            terms.push((excluded, Term::Grade(op, boulder, index)));
+                            continue;
+                        }
+                        found => terms.push((excluded, found?)),
+                    }
+                }
+                _ => terms.push((excluded, Term::Text(token.to_lowercase()))),
+            }
+            stored.push(typed);
+        }
+        Ok(Filter { text: text.trim().to_string(), stored: stored.join(" "), terms })
+    }
+
+    /// The filter with its grades in the stored Yosemite/Hueco names, which parses to the same filter under any settings.
+    pub fn stored(&self) -> &str {
+        &self.stored
+    }
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+fn all(terms: &[&(bool, Term)]) -> Condition {
+    terms.iter().fold(Condition::all(), |condition, (excluded, term)| {
+        let found = Condition::all().add(term.expr());
+        condition.add(if *excluded { found.not() } else { found })
+    })
+}
+
+impl Filter {
+    /// Whether there are no terms, so everything gets through.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Condition over sends joined to their routes and those routes' grades.
+    pub fn send_condition(&self) -> Condition {
+        all(&self.terms.iter().collect::<Vec<_>>())
+    }
+
+    /// Condition over routes joined to their grades. Terms on sends match routes the climber has a send on matching all of them.
+    pub fn route_condition(&self, climber: i32) -> Condition {
+        let (on_sends, on_routes): (Vec<_>, Vec<_>) = self.terms.iter().partition(|(_, term)| term.on_send());
+        let mut condition = all(&on_routes);
+        if !on_sends.is_empty() {
+            condition = condition.add(routes::Column::Id.in_subquery(Query::select()
+                .column((sends::Entity, sends::Column::Route))
+                .from(sends::Entity)
+                .cond_where(all(&on_sends).add(sends::Column::Climber.eq(climber)))
+                .to_owned()));
+        }
+        condition
+    }
+
+    /// The same as `send_condition`, for stores without SQL.
+    pub fn matches_send(&self, route: &routes::Model, grade: &grades::Model, styles: &[Style], send: &sends::Model, partners: &[String]) -> bool {
+        self.terms.iter().all(|(excluded, term)| *excluded != term.matches(route, grade, styles, Some((send, partners))))
+    }
+
+    /// The same as `route_condition`, for stores without SQL. Sends are the climber's on the route, with their partners.
+    pub fn matches_route(&self, route: &routes::Model, grade: &grades::Model, styles: &[Style], sends: &[(&sends::Model, Vec<String>)]) -> bool {
+        let (on_sends, on_routes): (Vec<_>, Vec<_>) = self.terms.iter().partition(|(_, term)| term.on_send());
+        on_routes.iter().all(|(excluded, term)| *excluded != term.matches(route, grade, styles, None))
+            && (on_sends.is_empty() || sends.iter().any(|(send, partners)| {
+                on_sends.iter().all(|(excluded, term)| *excluded != term.matches(route, grade, styles, Some((send, partners))))
+            }))
+    }
+}
+
+/// Filters saved by name, kept together in the settings table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SavedFilters(pub BTreeMap<String, String>);
+
+impl SavedFilters {
+    /// Reads the saved filters, none if nothing's been saved or the setting can't be read.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the setting can't be read.
+    pub async fn load(db: impl LogbookStore) -> Result<SavedFilters, DbErr> {
+        let saved = db.get_setting(SAVED_FILTERS_KEY).await?;
+        Ok(SavedFilters(saved.and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default()))
+    }
+
+    /// Keeps the saved filters in the settings table.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the setting can't be written.
+    pub async fn save(&self, db: impl LogbookStore) -> Result<(), DbErr> {
+        let json = serde_json::to_string(&self.0).map_err(|error| DbErr::Custom(error.to_string()))?;
+        db.set_setting(SAVED_FILTERS_KEY, json).await
+    }
+
+    /// Saves a filter under a name, replacing one already called that. Filters that don't parse aren't saved.
+    ///
+    /// Grades are saved in their stored names, so changing the grading systems doesn't change what the filter picks.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the name is empty or starts with @, or the filter doesn't parse.
+    pub fn insert(&mut self, name: &str, text: &str, settings: &Settings) -> Result<(), FilterError> {
+        let name = name.trim();
+        if name.is_empty() || name.starts_with('@') {
+            return Err(FilterError("saved filters need a name that doesn't start with @".to_string()));
+        }
+        let filter = Filter::parse(text, settings)?;
+        self.0.insert(name.to_string(), filter.stored().to_string());
+        Ok(())
+    }
+
+    /// Parses a filter, where `@name` is the filter saved as name.
+    ///
+    /// # Errors
+    ///
+    /// Fails when there's no filter saved under the name, or the filter doesn't parse.
+    pub fn resolve(&self, text: &str, settings: &Settings) -> Result<Filter, FilterError> {
+        match text.trim().strip_prefix('@') {
+            Some(name) => Filter::parse(self.0.get(name).ok_or_else(|| FilterError(format!("no saved filter named {name}")))?, settings),
+            None => Filter::parse(text, settings),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::climbing::Grade;
+
+    fn parse(text: &str) -> Result<Filter, FilterError> {
+        Filter::parse(text, &Settings::default())
+    }
+
+    #[test]
+    fn parses_fields_operators_and_quotes() {
+        let filter = parse("grade>=5.11a style:trad type:onsight crag:\"Red River Gorge\" after:2024-01-01 -with:Sam crimpy").unwrap();
+        let after = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        assert_eq!(filter.terms, vec![
+            (false, Term::Grade(Op::Ge, false, Logbook::parse_yosemite("5.11a").unwrap())),
+            (false, Term::Style(Style::Trad)),
+            (false, Term::SendType(SendType::Onsight)),
+            (false, Term::Crag("red river gorge".to_string())),
+            (false, Term::Date(Op::Ge, after)),
+            (true, Term::Partner("sam".to_string())),
+            (false, Term::Text("crimpy".to_string())),
+        ]);
+        assert_eq!(parse("Style:Top-Rope").unwrap().terms, vec![(false, Term::Style(Style::TopRope))]);
+        assert_eq!(parse("type!=flash").unwrap().terms, vec![(true, Term::SendType(SendType::Flash))]);
+        assert!(parse("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_what_it_cant_read() {
+        for text in ["grade>=5.99", "style>trad", "colour:red", "crag:\"red", "date:yesterday", "attempts>=lots", "grade:"] {
+            assert!(parse(text).is_err(), "{text} parsed");
+        }
+    }
+
+    #[test]
+    fn reads_grades_in_the_preferred_systems() {
+        let french = Settings { rope_system: GradeSystem::French, ..Settings::default() };
+        let font = Settings { boulder_system: GradeSystem::Font, ..Settings::default() };
+        let crack = conversion::convert(Grade::Yosemite(Yosemite::FiveElevenA), GradeSystem::French).unwrap().to_string();
+        let eleven = Logbook::parse_yosemite("5.11a").unwrap();
+        assert_eq!(Filter::parse(&format!("grade>={crack}"), &french).unwrap().terms, vec![(false, Term::Grade(Op::Ge, false, eleven))]);
+        assert_eq!(Filter::parse("grade>=5.11a", &french).unwrap().terms, vec![(false, Term::Grade(Op::Ge, false, eleven))], "Yosemite still works");
+        // 6A is a French route grade unless boulders are graded in Font
+        assert!(matches!(Filter::parse("grade<=6A", &font).unwrap().terms[..], [(false, Term::Grade(Op::Le, true, _))]));
+        assert!(matches!(parse("grade<=6A").unwrap().terms[..], [(false, Term::Grade(Op::Le, false, _))]));
+        assert!(Filter::parse("grade>=5.99", &french).is_err());
+    }
+
+    #[test]
+    fn saved_filters_keep_their_grades_when_the_settings_change() {
+        let font = Settings { boulder_system: GradeSystem::Font, ..Settings::default() };
+        let french = Settings { rope_system: GradeSystem::French, ..Settings::default() };
+        let route = parse("grade>=6A style:trad").unwrap().terms;
+        let boulder = Filter::parse("-grade<=6A crag:\"Red River\"", &font).unwrap().terms;
+        let mut saved = SavedFilters::default();
+        saved.insert("route", "grade>=6A style:trad", &Settings::default()).unwrap();
+        saved.insert("boulder", "-grade<=6A crag:\"Red River\"", &font).unwrap();
+        assert_eq!(saved.0["route"], "grade>=5.10a style:trad");
+        assert_eq!(saved.0["boulder"], "-grade<=V3- crag:\"Red River\"");
+        // 6A would be a Font boulder grade now, and a French route grade, but the saved filters still mean what they did
+        for settings in [Settings::default(), font, french] {
+            assert_eq!(saved.resolve("@route", &settings).unwrap().terms, route);
+            assert_eq!(saved.resolve("@boulder", &settings).unwrap().terms, boulder);
+        }
+    }
+}
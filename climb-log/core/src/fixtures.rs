@@ -0,0 +1,40 @@
+// Small rows for unit tests that don't need a whole store
+
+use crate::climbing::{Discipline, SendType};
+use crate::routes_db::entities::{grades::Model as GradeModel, routes::Model as RouteModel, sends::Model as SendModel};
+
+pub fn send(id: i32, route: i32, date: &str, send_type: SendType) -> SendModel {
+    SendModel {
+        id,
+        session: id,
+        date: date.to_string(),
+        r#type: send_type.to_string(),
+        attempts: 1,
+        notes: None,
+        route,
+        personal_grade: None,
+        climber: 1,
+    }
+}
+
+pub fn route(id: i32, name: &str, location: Option<&str>) -> (RouteModel, GradeModel) {
+    // A single pitch route, the grade row is only there to fill the pair
+    let route = RouteModel {
+        id,
+        name: name.to_string(),
+        length: 3000,
+        pitches: 1,
+        grade_id: id,
+        location: location.map(str::to_string),
+        ice_grade: None,
+        mixed_grade: None,
+        aid_grade: None,
+        commitment_grade: None,
+        ifas_grade: None,
+        danger: None,
+        grade_high: None,
+        discipline: Discipline::TallWall.to_string(),
+    };
+    let grade = GradeModel { id, yosemite: Some("5.10a".to_string()), ..GradeModel::default() };
+    (route, grade)
+}
@@ -0,0 +1,63 @@
+//! Core of the Ascent climbing log, everything but the window.
+//!
+//! Grades, routes and sends, the `SQLite` or Postgres logbook they're stored in, the stats worked out from them
+//! and JSON import/export. The app, the command line and the local server are all frontends over
+//! this crate, so anything built on it sees the same numbers they do. Nothing here depends on egui.
+//!
+//! ```no_run
+//! use climb_log_core::{logbook::LogbookData, routes_db::RoutesDb, settings::Settings};
+//!
+//! # async fn example() -> Result<(), sea_orm::DbErr> {
+//! let db = RoutesDb::open("sqlite:./logbook.db?mode=rwc").await?;
+//! let settings = Settings::load(db.clone()).await?;
+//! for (label, value) in LogbookData::load(db).await?.summary(&settings) {
+//!     println!("{label}: {value}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#![warn(clippy::pedantic)]
+#![allow(clippy::match_same_arms, clippy::must_use_candidate)]
+// Everything public is documented but the grades' variants and the generated entities, which say what they are
+
+/// Grading systems, routes, sends and the other climbing terms the logbook is written in.
+pub mod climbing;
+/// Converting grades between systems over one shared difficulty scale, and route lengths between units.
+#[warn(missing_docs)]
+pub mod conversion;
+/// Adding routes and logging sessions from typed text, the way the command line and server take them.
+#[warn(missing_docs)]
+pub mod entry;
+/// The whole logbook as one JSON file, and merging such a file back into a logbook.
+#[warn(missing_docs)]
+pub mod export;
+/// Filter expressions over sends and routes, like `grade>=5.11a style:trad after:2024-01-01`, and saved filters.
+#[warn(missing_docs)]
+pub mod filter;
+/// The stats page's numbers, worked out from logged sessions without any UI.
+#[warn(missing_docs)]
+pub mod logbook;
+/// Glicko style climbing rating over time.
+#[warn(missing_docs)]
+pub mod rating;
+/// Year in review style recaps of a date range, with an HTML export.
+#[warn(missing_docs)]
+pub mod recap;
+/// The logbook database: routes, grades, sends, partners and settings, migrated on open.
+pub mod routes_db;
+/// Full-text search over routes, notes and partners, with filters for grade, style, send type and date.
+#[warn(missing_docs)]
+pub mod search;
+/// The `LogbookStore` trait every backend implements, and an in-memory store for tests.
+#[warn(missing_docs)]
+pub mod store;
+/// Climber preferences, stored in the logbook's settings table.
+#[warn(missing_docs)]
+pub mod settings;
+/// Grade estimates, pyramids, crag and partner summaries over logged sends.
+#[warn(missing_docs)]
+pub mod stats;
+
+#[cfg(test)]
+mod fixtures;
@@ -0,0 +1,582 @@
+use std::{collections::{BTreeMap, HashMap}, str::FromStr};
+use sea_orm::DbErr;
+use schemars::JsonSchema;
+use serde::Serialize;
+use crate::climbing::{Danger, Discipline, Grade, GradeOpinion, GradeRange, Hueco, Style, Yosemite};
+use crate::conversion;
+use crate::store::LogbookStore;
+use crate::settings::Settings;
+use crate::stats::{self, GradeEstimate};
+use crate::routes_db::entities::{climbers::Model as ClimberModel, grades::Model as GradeModel, partners::Model as PartnerModel, route_styles::Model as RouteStyleModel, routes::Model as RouteModel, send_partners::Model as SendPartnerModel, sends::Model as SendModel, styles::Model as StyleModel};
+
+// Everything the stats page works out, kept apart from egui so the command line and server give the same numbers
+// Logbook borrows the logged data, either from the app's buffers or from a LogbookData loaded in one go
+
+/// Stats compared side by side on the team view, picked from the stats page.
+pub const TEAM_STATS: [&str; 9] = [
+    "Total Sends",
+    "Total Sessions",
+    "Average Attempts per Send",
+    "Flash Grade (Tall Wall)",
+    "Flash Grade (Boulder)",
+    "Redpoint Grade (Tall Wall)",
+    "Redpoint Grade (Boulder)",
+    "Top Tall Wall Grade",
+    "Top Boulder Grade",
+];
+
+/// Every route, send, climber and partner in a logbook, loaded in one go.
+pub struct LogbookData { // Owned copy of everything the stats read
+    /// Every climber's sends, or one climber's after `for_climber`.
+    pub sends: Vec<SendModel>,
+    /// Everyone sharing the logbook.
+    pub climbers: Vec<ClimberModel>,
+    /// Every route with its grade.
+    pub routes: Vec<(RouteModel, GradeModel)>,
+    /// Styles of every route by route id.
+    pub route_styles: HashMap<i32, Vec<Style>>,
+    /// Every partner.
+    pub partners: Vec<PartnerModel>,
+    /// Which partners were on which sends.
+    pub send_partners: Vec<SendPartnerModel>,
+}
+impl LogbookData {
+    /// Reads the whole logbook.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the logbook can't be read.
+    pub async fn load(db: impl LogbookStore) -> Result<LogbookData, DbErr> {
+        Ok(LogbookData {
+            sends: db.clone().get_all_sends().await?,
+            climbers: db.clone().get_all_climbers().await?,
+            routes: db.clone().find_all_routes_and_grade().await?,
+            route_styles: styles_by_route(db.clone().get_all_route_styles().await?),
+            partners: db.clone().get_all_partners().await?,
+            send_partners: db.get_all_send_partners().await?,
+        })
+    }
+
+    /// Only one climber's sends, with the routes, climbers and partners left as they are.
+    #[must_use]
+    pub fn for_climber(mut self, climber: i32) -> LogbookData {
+        self.sends.retain(|send| send.climber == climber);
+        let kept: Vec<i32> = self.sends.iter().map(|send| send.id).collect();
+        self.send_partners.retain(|link| kept.contains(&link.send));
+        self
+    }
+
+    /// Id of the climber with a name, ignoring case.
+    pub fn climber_id(&self, name: &str) -> Option<i32> {
+        self.climbers.iter().find(|climber| climber.name.eq_ignore_ascii_case(name.trim())).map(|climber| climber.id)
+    }
+
+    /// Name of a climber by id, empty if there's no such climber.
+    pub fn climber_name(&self, climber: i32) -> String {
+        self.climbers.iter().find(|found| found.id == climber).map_or_else(String::new, |found| found.name.clone())
+    }
+
+    /// Stats over the loaded logbook, shown with the settings.
+    pub fn logbook<'a>(&'a self, settings: &'a Settings) -> Logbook<'a> {
+        Logbook { sends: &self.sends, routes: &self.routes, settings }
+    }
+
+    /// The stats page as label and value pairs, in the order the app shows them.
+    pub fn summary(&self, settings: &Settings) -> Vec<(&'static str, String)> {
+        self.logbook(settings).summary(&self.route_styles, &self.partners, &self.send_partners)
+    }
+
+    /// The stats page as numbers and grades, for the JSON API and `--json`.
+    pub fn stats(&self, settings: &Settings) -> StatsSummary {
+        self.logbook(settings).stats(&self.route_styles, &self.partners, &self.send_partners)
+    }
+
+    /// Every climber's `stats` by name.
+    pub fn team_stats(&self, settings: &Settings) -> BTreeMap<String, StatsSummary> {
+        self.climbers.iter().map(|climber| {
+            let sends: Vec<SendModel> = self.sends.iter().filter(|send| send.climber == climber.id).cloned().collect();
+            let logbook = Logbook { sends: &sends, routes: &self.routes, settings };
+            (climber.name.clone(), logbook.stats(&self.route_styles, &self.partners, &self.send_partners))
+        }).collect()
+    }
+
+    /// The team view, each climber's `TEAM_STATS` in the order they joined.
+    pub fn team(&self, settings: &Settings) -> Vec<(String, Vec<(&'static str, String)>)> {
+        self.logbook(settings).team(&self.climbers, &self.route_styles, &self.partners, &self.send_partners)
+    }
+
+    /// Style names of a route.
+    pub fn style_names(&self, route: i32) -> Vec<String> {
+        self.route_styles.get(&route).into_iter().flatten().map(std::string::ToString::to_string).collect()
+    }
+}
+
+/// Stats over borrowed sends and routes, with grades shown in the climber's preferred systems.
+pub struct Logbook<'a> {
+    /// The sends the stats are over.
+    pub sends: &'a [SendModel],
+    /// Every route with its grade, for the routes the sends are on.
+    pub routes: &'a [(RouteModel, GradeModel)],
+    /// Settings the stats are worked out and shown with.
+    pub settings: &'a Settings,
+}
+
+impl<'a> Logbook<'a> {
+    /// Every line of the stats page as (label, value), in the order shown.
+    pub fn summary(&self, route_styles: &HashMap<i32, Vec<Style>>, partners: &[PartnerModel], send_partners: &[SendPartnerModel]) -> Vec<(&'static str, String)> {
+        let (tall_grade, tall_route) = self.top_tall_grade();
+        let (boulder_grade, boulder_route) = self.top_boulder_grade();
+        vec![
+            ("Total Sends", self.total_sends().to_string()),
+            ("Total Sessions", self.total_sessions().to_string()),
+            ("Average Sends per Session", self.avg_sends().to_string()),
+            ("Average Attempts per Send", self.avg_attempts().to_string()),
+            ("Average Tall Wall Grade", self.avg_grade(false).unwrap_or_else(|| Yosemite::None.to_string())),
+            ("Average Boulder Grade", self.avg_grade(true).unwrap_or_else(|| Hueco::None.to_string())),
+            ("Favorite Style", self.fav_style(route_styles).unwrap_or_default()),
+            ("Favorite Route", self.fav_route().unwrap_or_default()),
+            ("Favorite Partner", self.fav_partner(partners, send_partners)),
+            ("Favorite Crag", self.fav_crag().unwrap_or_else(|| "None".to_string())),
+            ("Flash Grade (Tall Wall)", self.describe_estimate(self.flash_grade_tall(), false)),
+            ("Flash Grade (Boulder)", self.describe_estimate(self.flash_grade_boulder(), true)),
+            ("Redpoint Grade (Tall Wall)", self.describe_estimate(self.redpoint_grade_tall(), false)),
+            ("Redpoint Grade (Boulder)", self.describe_estimate(self.redpoint_grade_boulder(), true)),
+            ("Top Tall Wall Grade", format!("{tall_grade} ({tall_route})")),
+            ("Top Boulder Grade", format!("{boulder_grade} ({boulder_route})")),
+            ("Hardest Lead at R or Worse", self.describe_hardest_lead(Danger::R)),
+            ("Hardest X-rated Lead", self.describe_hardest_lead(Danger::X)),
+            ("Other Stats", Self::other_stats()),
+        ]
+    }
+
+    /// The same stats as summary, left as numbers and grades.
+    pub fn stats(&self, route_styles: &HashMap<i32, Vec<Style>>, partners: &[PartnerModel], send_partners: &[SendPartnerModel]) -> StatsSummary {
+        StatsSummary {
+            total_sends: self.total_sends(),
+            total_sessions: self.total_sessions(),
+            average_sends_per_session: self.avg_sends(),
+            average_attempts_per_send: self.avg_attempts(),
+            average_tall_wall_grade: self.avg_grade(false),
+            average_boulder_grade: self.avg_grade(true),
+            favorite_style: self.fav_style(route_styles),
+            favorite_route: self.fav_route(),
+            favorite_partner: (!self.sends.is_empty()).then(|| self.fav_partner(partners, send_partners)),
+            favorite_crag: self.fav_crag(),
+            flash_grade_tall_wall: self.estimate_summary(self.flash_grade_tall(), false),
+            flash_grade_boulder: self.estimate_summary(self.flash_grade_boulder(), true),
+            redpoint_grade_tall_wall: self.estimate_summary(self.redpoint_grade_tall(), false),
+            redpoint_grade_boulder: self.estimate_summary(self.redpoint_grade_boulder(), true),
+            top_tall_wall_grade: self.top_grade(false),
+            top_boulder_grade: self.top_grade(true),
+            hardest_lead_at_r_or_worse: self.hardest_lead(Danger::R),
+            hardest_x_rated_lead: self.hardest_lead(Danger::X),
+        }
+    }
+
+    /// Each climber's stats worked out over only their sends, keeping the ones the team view compares.
+    pub fn team(&self, climbers: &[ClimberModel], route_styles: &HashMap<i32, Vec<Style>>, partners: &[PartnerModel], send_partners: &[SendPartnerModel]) -> Vec<(String, Vec<(&'static str, String)>)> {
+        climbers.iter().map(|climber| {
+            let sends: Vec<SendModel> = self.sends.iter().filter(|send| send.climber == climber.id).cloned().collect();
+            let logbook = Logbook { sends: &sends, routes: self.routes, settings: self.settings };
+            let stats = logbook.summary(route_styles, partners, send_partners).into_iter().filter(|(label, _)| TEAM_STATS.contains(label)).collect();
+            (climber.name.clone(), stats)
+        }).collect()
+    }
+
+    fn total_sends(&self) -> i32 {
+        // Get the total number of sends
+        i32::try_from(self.sends.len()).unwrap_or(i32::MAX)
+    }
+
+    fn total_sessions(&self) -> i32 {
+        // Get the total number of sessions, counting unique session ids
+        let mut total_sessions: Vec<i32> = Vec::new();
+        for session in self.sends {
+            if !total_sessions.contains(&session.session) {
+                total_sessions.push(session.session);
+            }
+        }
+
+        i32::try_from(total_sessions.len()).unwrap_or(i32::MAX)
+    }
+
+    fn avg_sends(&self) -> f64 {
+        // Get the average number of sends per session
+        if self.total_sessions() == 0 {
+            return 0.0;
+        }
+        f64::from(self.total_sends()) / f64::from(self.total_sessions())
+    }
+
+    fn avg_attempts(&self) -> f64 {
+        // Get the average number of attempts per send
+        if self.total_sends() == 0 {
+            return 0.0;
+        }
+        f64::from(self.sends.iter().map(|session| session.attempts).sum::<i32>()) / f64::from(self.total_sends())
+    }
+
+    fn avg_grade(&self, boulder: bool) -> Option<String> {
+        // Get the average tall wall or boulder grade, none without sends
+        let samples = if boulder { self.boulder_samples() } else { self.tall_samples() };
+        if samples.is_empty() {
+            return None;
+        }
+        let grade_sum: i32 = samples.iter().map(|(grade, _)| grade).sum();
+        Some(self.settings.grade_name(grade_sum / i32::try_from(samples.len()).unwrap_or(i32::MAX), boulder))
+    }
+
+    fn fav_style(&self, route_styles: &HashMap<i32, Vec<Style>>) -> Option<String> {
+        // Get the favorite climbing style
+        
+        // Each send counts once for every style its route has
+        let mut style_map = HashMap::new();
+        for session in self.sends {
+            for style in route_styles.get(&session.route).into_iter().flatten() {
+                let count = style_map.entry(*style).or_insert(0);
+                *count += 1;
+            }
+        }
+        let mut max = 0;
+        let mut fav = None;
+        for (style, count) in style_map {
+            if count > max {
+                max = count;
+                fav = Some(style.to_string());
+            }
+        }
+        fav
+    }
+
+    fn fav_route(&self) -> Option<String> {
+        // Get the favorite route
+        let mut routes: Vec<RouteModel> = Vec::new();
+        for session in self.sends {
+            let route = self.routes.iter().find(|(route, _)| route.id == session.route).unwrap().0.clone();
+            routes.push(route);
+        }
+        let mut route_map = HashMap::new();
+        for route in routes {
+            let count = route_map.entry(route.name.clone()).or_insert(0);
+            *count += 1;
+        }
+        let mut max = 0;
+        let mut fav = None;
+        for (route, count) in route_map {
+            if count > max {
+                max = count;
+                fav = Some(route);
+            }
+        }
+        fav
+    }
+
+    fn fav_partner(&self, all_partners: &[PartnerModel], send_partners: &[SendPartnerModel]) -> String {
+        // Get the favorite partner
+        let partners = self.sends.iter().flat_map(|session| {
+            let names: Vec<String> = send_partners.iter()
+                .filter(|link| link.send == session.id)
+                .filter_map(|link| all_partners.iter().find(|partner| partner.id == link.partner).map(|partner| partner.name.clone()))
+                .collect();
+            if names.is_empty() { vec!["Solo".to_string()] } else { names }
+        });
+        let mut partner_map = HashMap::new();
+        for partner in partners {
+            let count = partner_map.entry(partner).or_insert(0);
+            *count += 1;
+        }
+        let mut max = 0;
+        let mut fav = "Solo".to_string();
+        for (partner, count) in partner_map {
+            if count > max {
+                max = count;
+                fav = partner;
+            }
+        }
+        fav
+    }
+
+    fn fav_crag(&self) -> Option<String> {
+        // Get the favorite crag, the one with the most days climbed
+        let summaries = stats::crag_summaries(&self.tall_samples(), &self.boulder_samples(), self.routes, None);
+        summaries.into_iter().find(|summary| !summary.visits.is_empty()).map(|summary| summary.name)
+    }
+
+    /// Every tall wall send, paired with the Yosemite index of its route's grade.
+    pub fn tall_samples(&self) -> Vec<(i32, &'a SendModel)> {
+        self.sends.iter().filter_map(|session| {
+            let (route, grade) = self.routes.iter().find(|(route, _)| route.id == session.route)?;
+            if Self::is_boulder(route) {
+                return None;
+            }
+            let consensus = self.tall_index(route, grade)?;
+            Some((self.counted_grade(session, consensus, false), session))
+        }).collect()
+    }
+
+    /// Every boulder send, paired with the Hueco index of its problem's grade.
+    pub fn boulder_samples(&self) -> Vec<(i32, &'a SendModel)> {
+        self.sends.iter().filter_map(|session| {
+            let (route, grade) = self.routes.iter().find(|(route, _)| route.id == session.route)?;
+            if !Self::is_boulder(route) {
+                return None;
+            }
+            let consensus = self.boulder_index(route, grade)?;
+            Some((self.counted_grade(session, consensus, true), session))
+        }).collect()
+    }
+
+    /// Yosemite index of a route's grade, slash grades and ranges count as picked in settings.
+    pub fn tall_index(&self, route: &RouteModel, grade: &GradeModel) -> Option<i32> {
+        let low = Self::parse_yosemite(grade.yosemite.as_deref()?)?;
+        let high = route.grade_high.as_deref().and_then(Self::parse_yosemite).unwrap_or(low);
+        Some(self.settings.range_grade.pick(low, high))
+    }
+
+    /// Hueco index of a problem's grade, slash grades and ranges count as picked in settings.
+    pub fn boulder_index(&self, route: &RouteModel, grade: &GradeModel) -> Option<i32> {
+        let low = Self::parse_hueco(grade.hueco.as_deref()?)?;
+        let high = route.grade_high.as_deref().and_then(Self::parse_hueco).unwrap_or(low);
+        Some(self.settings.range_grade.pick(low, high))
+    }
+
+    /// Stored index of a Yosemite grade, none if the text isn't one.
+    pub fn parse_yosemite(grade: &str) -> Option<i32> {
+        Yosemite::from_str(grade).ok().map(|grade| grade as i32)
+    }
+
+    /// Stored index of a Hueco grade, none if the text isn't one.
+    pub fn parse_hueco(grade: &str) -> Option<i32> {
+        Hueco::from_str(grade).ok().map(|grade| grade as i32 - 1)
+    }
+
+    /// Stored grade index of a typed grade, like an opinion, in the preferred system or any other for the discipline.
+    pub fn parse_opinion(&self, grade: &str, boulder: bool) -> Option<i32> {
+        self.settings.grade_index(grade, boulder)
+    }
+
+    /// Grade a send counts at in the stats, the climber's own grade if picked in settings.
+    pub fn counted_grade(&self, send: &SendModel, consensus: i32, boulder: bool) -> i32 {
+        if self.settings.personal_grades {
+            // Grades that don't parse were logged before they were checked, they say nothing so the route's grade stands
+            GradeOpinion::from(send.personal_grade.as_deref()).apply(consensus, |grade| self.parse_opinion(grade, boulder)).unwrap_or(consensus)
+        } else {
+            consensus
+        }
+    }
+
+    /// (route's grade, climber's grade, send) for every send with an opinion on the grade.
+    pub fn grade_opinions(&self, boulder: bool) -> Vec<(i32, i32, &'a SendModel)> {
+        self.sends.iter().filter_map(|session| {
+            let opinion = GradeOpinion::from(session.personal_grade.as_deref());
+            if opinion == GradeOpinion::Agree {
+                return None;
+            }
+            let (route, grade) = self.routes.iter().find(|(route, _)| route.id == session.route)?;
+            if Self::is_boulder(route) != boulder {
+                return None;
+            }
+            let consensus = if boulder { self.boulder_index(route, grade)? } else { self.tall_index(route, grade)? };
+            Some((consensus, opinion.apply(consensus, |grade| self.parse_opinion(grade, boulder))?, session))
+        }).collect()
+    }
+
+    fn flash_grade_tall(&self) -> Option<GradeEstimate> {
+        // Get the flash grade for tall walls
+        // Flash grade = hardest grade you send first try often enough, as defined in settings
+        stats::grade_metric(self.tall_samples(), &self.settings.flash, self.settings.z_score(), chrono::Local::now().date_naive())
+    }
+
+    fn flash_grade_boulder(&self) -> Option<GradeEstimate> {
+        // Get the flash grade for bouldering
+        stats::grade_metric(self.boulder_samples(), &self.settings.flash, self.settings.z_score(), chrono::Local::now().date_naive())
+    }
+
+    fn redpoint_grade_tall(&self) -> Option<GradeEstimate> {
+        // Get the redpoint grade
+        // Max grade you can get after projecting, as defined in settings
+        stats::grade_metric(self.tall_samples(), &self.settings.redpoint, self.settings.z_score(), chrono::Local::now().date_naive())
+    }
+
+    fn redpoint_grade_boulder(&self) -> Option<GradeEstimate> {
+        // Get the redpoint grade for bouldering
+        stats::grade_metric(self.boulder_samples(), &self.settings.redpoint, self.settings.z_score(), chrono::Local::now().date_naive())
+    }
+
+    /// Grade with its sample and confidence interval, e.g. "5.10c (8/9 sends, 89%, 95% CI 57-98%)"
+    pub fn describe_estimate(&self, estimate: Option<GradeEstimate>, boulder: bool) -> String {
+        match estimate {
+            Some(estimate) => format!(
+                "{} ({}/{} sends, {:.0}%, {:.0}% CI {:.0}-{:.0}%)",
+                self.settings.grade_name(estimate.grade, boulder),
+                estimate.successes,
+                estimate.total,
+                estimate.rate() * 100.0,
+                self.settings.confidence * 100.0,
+                estimate.low * 100.0,
+                estimate.high * 100.0,
+            ),
+            None => "Not enough data".to_string(),
+        }
+    }
+
+    fn estimate_summary(&self, estimate: Option<GradeEstimate>, boulder: bool) -> Option<EstimateSummary> {
+        estimate.map(|estimate| EstimateSummary {
+            grade: self.settings.grade_name(estimate.grade, boulder),
+            successes: estimate.successes,
+            total: estimate.total,
+            rate: estimate.rate(),
+            confidence: f64::from(self.settings.confidence),
+            low: estimate.low,
+            high: estimate.high,
+        })
+    }
+
+    fn hardest_lead(&self, danger: Danger) -> Option<GradedRoute> {
+        // Hardest lead on a route rated at least this dangerous
+        let samples = self.tall_samples();
+        let (grade, send) = stats::hardest_lead_at_danger(&samples, self.routes, danger)?;
+        let route = self.routes.iter().find(|(route, _)| route.id == send.route).map_or_else(String::new, |(route, _)| route.name.clone());
+        Some(GradedRoute { grade: self.settings.grade_name(grade, false), route })
+    }
+
+    /// Hardest lead on a route rated at least this dangerous, e.g. "5.10c (Route Name)"
+    pub fn describe_hardest_lead(&self, danger: Danger) -> String {
+        self.hardest_lead(danger).map_or_else(|| "None".to_string(), |lead| format!("{} ({})", lead.grade, lead.route))
+    }
+
+    fn top_grade(&self, boulder: bool) -> Option<GradedRoute> {
+        // Get the top tall wall or boulder grade, ties go to the first one logged
+        let samples = if boulder { self.boulder_samples() } else { self.tall_samples() };
+        let (grade, send) = samples.into_iter().rev().max_by_key(|(grade, _)| *grade)?;
+        Some(GradedRoute { grade: self.settings.grade_name(grade, boulder), route: self.route_name(send.route) })
+    }
+
+    /// The top tall wall grade and its route, as shown on the stats page.
+    pub fn top_tall_grade(&self) -> (String, String) {
+        match self.top_grade(false) {
+            Some(top) => (top.grade, top.route),
+            None => (Yosemite::None.to_string(), "None".to_string()),
+        }
+    }
+
+    /// The top boulder grade and its route, as shown on the stats page.
+    pub fn top_boulder_grade(&self) -> (String, String) {
+        match self.top_grade(true) {
+            Some(top) => (top.grade, top.route),
+            None => (Hueco::None.to_string(), "None".to_string()),
+        }
+    }
+
+    fn other_stats() -> String {
+        // Get other stats from notes
+        "Other stats may be added in the future.".to_string()
+    }
+
+
+    /// Route's grade in the preferred system, "5.10c/d" or "V4-5" for slash grades and ranges.
+    pub fn grade_label(&self, route: &RouteModel, grade: &GradeModel) -> String {
+        let boulder = Self::is_boulder(route);
+        let stored = if boulder { grade.hueco.clone() } else { grade.yosemite.clone() };
+        let parse: fn(&str) -> Option<i32> = if boulder { Self::parse_hueco } else { Self::parse_yosemite };
+        let Some(low) = stored.as_deref().and_then(parse) else { return stored.unwrap_or_default() };
+        let high = route.grade_high.as_deref().and_then(parse).unwrap_or(low);
+        let system = self.settings.grade_system(boulder);
+        let shown = |index: i32| {
+            let grade = if boulder { Grade::Hueco(Hueco::from(index)) } else { Grade::Yosemite(Yosemite::from(index)) };
+            conversion::convert(grade, system).unwrap_or(grade)
+        };
+        GradeRange { low: shown(low), high: shown(high) }.to_string()
+    }
+
+    /// Name of a route by id, Unknown if there's no such route.
+    pub fn route_name(&self, route: i32) -> String {
+        self.routes.iter().find(|(found, _)| found.id == route).map_or("Unknown".to_string(), |(found, _)| found.name.clone())
+    }
+
+    /// Whether a route is a boulder problem.
+    pub fn is_boulder(route: &RouteModel) -> bool {
+        Discipline::from_str(&route.discipline) == Ok(Discipline::Boulder)
+    }
+
+}
+
+/// Styles of every route by route id, from the `route_styles` join.
+pub fn styles_by_route(links: Vec<(RouteStyleModel, Option<StyleModel>)>) -> HashMap<i32, Vec<Style>> {
+    let mut styles: HashMap<i32, Vec<Style>> = HashMap::new();
+    for (link, style) in links {
+        if let Some(style) = style.and_then(|style| Style::from_str(&style.name).ok()) {
+            styles.entry(link.route).or_default().push(style);
+        }
+    }
+    styles
+}
+
+/// A grade in the climber's preferred system, with the route it was climbed on.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct GradedRoute {
+    /// The grade.
+    pub grade: String,
+    /// Name of the route.
+    pub route: String,
+}
+
+/// A flash or redpoint grade, with the sends it was worked out from.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct EstimateSummary {
+    /// The grade.
+    pub grade: String,
+    /// Sends at the grade that counted as a success.
+    pub successes: i32,
+    /// All sends counted at the grade.
+    pub total: i32,
+    /// Share of the sends that counted, from 0 to 1.
+    pub rate: f64, // successes / total, from 0 to 1
+    /// Confidence level of the interval, from settings.
+    pub confidence: f64,
+    /// Bottom of the confidence interval for the rate.
+    pub low: f64, // Confidence interval for the rate
+    /// Top of the confidence interval for the rate.
+    pub high: f64,
+}
+
+/// The stats page as numbers and grades, for the JSON API and `--json`. Grades are in the climber's preferred systems, and stats without the sends to work them out are null.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct StatsSummary {
+    /// Every send and attempt.
+    pub total_sends: i32,
+    /// Distinct sessions.
+    pub total_sessions: i32,
+    /// Sends per session, 0 without any.
+    pub average_sends_per_session: f64,
+    /// Attempts per send, 0 without any.
+    pub average_attempts_per_send: f64,
+    /// Average tall wall grade.
+    pub average_tall_wall_grade: Option<String>,
+    /// Average boulder grade.
+    pub average_boulder_grade: Option<String>,
+    /// Style with the most sends.
+    pub favorite_style: Option<String>,
+    /// Route with the most sends.
+    pub favorite_route: Option<String>,
+    /// "Solo" when most sends had no partner.
+    pub favorite_partner: Option<String>,
+    /// Crag with the most days climbed.
+    pub favorite_crag: Option<String>,
+    /// Flash grade on tall walls.
+    pub flash_grade_tall_wall: Option<EstimateSummary>,
+    /// Flash grade on boulders.
+    pub flash_grade_boulder: Option<EstimateSummary>,
+    /// Redpoint grade on tall walls.
+    pub redpoint_grade_tall_wall: Option<EstimateSummary>,
+    /// Redpoint grade on boulders.
+    pub redpoint_grade_boulder: Option<EstimateSummary>,
+    /// Hardest tall wall send.
+    pub top_tall_wall_grade: Option<GradedRoute>,
+    /// Hardest boulder send.
+    pub top_boulder_grade: Option<GradedRoute>,
+    /// Hardest lead on a route rated R or X.
+    pub hardest_lead_at_r_or_worse: Option<GradedRoute>,
+    /// Hardest lead on a route rated X.
+    pub hardest_x_rated_lead: Option<GradedRoute>,
+}
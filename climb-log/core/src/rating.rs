@@ -0,0 +1,134 @@
+use std::{collections::BTreeMap, f64::consts::{LN_10, PI}, str::FromStr};
+use chrono::NaiveDate;
+use crate::climbing::SendType;
+use crate::routes_db::entities::sends::Model as SendModel;
+
+// Glicko style rating, every send or attempt is a match between the climber and the route's grade
+// Ratings are kept on an Elo-like scale with one grade step worth GRADE_POINTS, and converted back to grade steps on the way out
+const GRADE_POINTS: f64 = 100.0; // Rating points per step of the grade system, one grade harder = ~36% expected success
+const START_DEVIATION: f64 = 350.0; // Uncertainty for a climber with no history, about 3.5 grades
+const ROUTE_DEVIATION: f64 = 100.0; // Grades are opinions, treat each as about one grade uncertain
+const DRIFT_PER_DAY: f64 = 12.0; // How fast uncertainty grows between sessions, back to START_DEVIATION after ~2 years off
+const MAX_LOSSES_PER_SEND: usize = 5; // Caps how many failed attempts a single logged send can count as
+const Q: f64 = LN_10 / 400.0;
+
+/// Personal rating after a day of climbing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RatingPoint {
+    /// Day the rating is from.
+    pub date: NaiveDate,
+    /// Rating in grade steps of the discipline's grade system.
+    pub rating: f64,
+    /// Standard deviation of the rating, also in grade steps.
+    pub deviation: f64,
+}
+
+fn g(deviation: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * Q * Q * deviation * deviation / (PI * PI)).sqrt()
+}
+
+fn expected(rating: f64, route: f64, route_g: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-route_g * (rating - route) / 400.0))
+}
+
+fn matches(send: &SendModel) -> Vec<f64> {
+    // Scores for the matches a send stands for: 1.0 for a clean send, 0.5 for a top with falls, 0.0 per failed attempt
+    let attempts = usize::try_from(send.attempts).unwrap_or(1).clamp(1, MAX_LOSSES_PER_SEND + 1);
+    match SendType::from_str(&send.r#type).unwrap_or_default() {
+        SendType::Repeat => Vec::new(), // Repeats don't say anything new about the climber
+        SendType::Attempt => vec![0.0; attempts.min(MAX_LOSSES_PER_SEND)],
+        SendType::Top => vec![0.5],
+        SendType::Flash | SendType::Onsight | SendType::FreeSolo => vec![1.0],
+        SendType::Redpoint | SendType::Pinkpoint => {
+            let mut scores = vec![0.0; attempts - 1];
+            scores.push(1.0);
+            scores
+        }
+    }
+}
+
+/// Runs the rating model over a discipline's sends in date order, one rating period per day.
+pub fn rating_history<'a>(samples: impl IntoIterator<Item = (i32, &'a SendModel)>) -> Vec<RatingPoint> {
+    let mut days: BTreeMap<NaiveDate, Vec<(f64, f64)>> = BTreeMap::new(); // date -> (route rating, score)
+    for (grade, send) in samples {
+        let Ok(date) = NaiveDate::from_str(&send.date) else { continue };
+        let route = f64::from(grade) * GRADE_POINTS;
+        days.entry(date).or_default().extend(matches(send).into_iter().map(|score| (route, score)));
+    }
+
+    let route_g = g(ROUTE_DEVIATION);
+    let mut history: Vec<RatingPoint> = Vec::new();
+    let mut rating: Option<f64> = None;
+    let mut deviation = START_DEVIATION;
+    let mut last_day: Option<NaiveDate> = None;
+    for (date, games) in days {
+        if games.is_empty() {
+            continue;
+        }
+        // Start from the average grade of the first day, then let uncertainty drift up while not climbing
+        let current = *rating.get_or_insert_with(|| games.iter().map(|(route, _)| route).sum::<f64>() / f64::from(u32::try_from(games.len()).unwrap_or(u32::MAX)));
+        if let Some(last) = last_day {
+            let idle = f64::from(i32::try_from((date - last).num_days()).unwrap_or(i32::MAX));
+            deviation = (deviation * deviation + DRIFT_PER_DAY * DRIFT_PER_DAY * idle).sqrt().min(START_DEVIATION);
+        }
+
+        let mut variance_inv = 0.0;
+        let mut delta = 0.0;
+        for (route, score) in &games {
+            let e = expected(current, *route, route_g);
+            variance_inv += route_g * route_g * e * (1.0 - e);
+            delta += route_g * (score - e);
+        }
+        variance_inv *= Q * Q;
+        let precision = 1.0 / (deviation * deviation) + variance_inv;
+        rating = Some(current + Q / precision * delta);
+        deviation = (1.0 / precision).sqrt();
+        last_day = Some(date);
+
+        history.push(RatingPoint {
+            date,
+            rating: rating.unwrap_or(current) / GRADE_POINTS,
+            deviation: deviation / GRADE_POINTS,
+        });
+    }
+    history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::send;
+
+    #[test]
+    fn no_sends_no_history() {
+        assert!(rating_history(std::iter::empty()).is_empty());
+        let repeat = send(1, 1, "2024-05-01", SendType::Repeat);
+        assert!(rating_history([(10, &repeat)]).is_empty(), "repeats alone are not a rating period");
+    }
+
+    #[test]
+    fn sending_above_the_rating_raises_it() {
+        let first = [send(1, 1, "2024-05-01", SendType::Flash), send(2, 2, "2024-05-01", SendType::Attempt)];
+        let hard = send(3, 3, "2024-05-02", SendType::Onsight);
+        let history = rating_history(first.iter().map(|send| (10, send)).chain([(14, &hard)]));
+        assert_eq!(history.len(), 2);
+        assert!((history[0].rating - 10.0).abs() < 0.5, "one win and one loss at 10 stays near 10, got {}", history[0].rating);
+        assert!(history[1].rating > history[0].rating);
+        assert!(history[1].rating < 14.0, "one send doesn't jump straight to its grade");
+    }
+
+    #[test]
+    fn deviation_shrinks_with_activity() {
+        let sends: Vec<SendModel> = (1..=5).map(|day| send(day, day, &format!("2024-05-0{day}"), SendType::Redpoint)).collect();
+        let history = rating_history(sends.iter().map(|send| (10, send)));
+        assert!(history[0].deviation < START_DEVIATION / GRADE_POINTS);
+        for pair in history.windows(2) {
+            assert!(pair[1].deviation < pair[0].deviation, "deviation grew on {}", pair[1].date);
+        }
+
+        // A long break lets it grow back
+        let later = send(6, 6, "2026-05-01", SendType::Redpoint);
+        let history = rating_history(sends.iter().chain([&later]).map(|send| (10, send)));
+        assert!(history[5].deviation > history[4].deviation);
+    }
+}
@@ -0,0 +1,420 @@
+use std::{collections::{BTreeMap, BTreeSet, HashMap}, fmt::Write, str::FromStr};
+use chrono::{Datelike, NaiveDate};
+use crate::climbing::SendType;
+use crate::conversion::{self, GradeSystem, LengthUnit};
+use crate::routes_db::entities::{grades::Model as GradeModel, partners::Model as PartnerModel, routes::Model as RouteModel, send_partners::Model as SendPartnerModel, sends::Model as SendModel};
+
+// Year in review style report for any date range, shown in app or exported as a single HTML file
+
+const TOP_COUNT: usize = 5; // How many hardest sends and partners to list
+
+/// A notable send in the recap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecapSend {
+    /// Grade index, Yosemite for tall walls and Hueco for boulders.
+    pub grade: i32,
+    /// Grade in the preferred system.
+    pub grade_name: String,
+    /// Route name.
+    pub route: String,
+    /// Day sent, YYYY-MM-DD.
+    pub date: String,
+}
+
+/// Hardest sends in one month of the range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProgressPoint {
+    /// First day of the month.
+    pub month: NaiveDate,
+    /// Hardest tall wall send that month, as a Yosemite index.
+    pub tall: Option<i32>,
+    /// Hardest boulder send that month, as a Hueco index.
+    pub boulder: Option<i32>,
+}
+
+/// Highlights of a date range, ready to show or save as HTML.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recap {
+    /// First day of the range.
+    pub from: NaiveDate,
+    /// Last day of the range, included.
+    pub to: NaiveDate,
+    /// Distinct days with something logged.
+    pub days: usize,
+    /// Sessions with something logged.
+    pub sessions: usize,
+    /// Completed sends, attempts not included.
+    pub sends: usize,
+    /// Attempts across everything logged.
+    pub attempts: i32,
+    /// Length of every completed send added up, in centimetres.
+    pub vertical: i32,
+    /// Hardest tall wall sends, hardest first.
+    pub hardest_tall: Vec<RecapSend>,
+    /// Hardest boulder sends, hardest first.
+    pub hardest_boulder: Vec<RecapSend>,
+    /// Crags first climbed at during the range.
+    pub new_crags: Vec<String>,
+    /// Partners and the days climbed with them, most days first.
+    pub top_partners: Vec<(String, usize)>,
+    /// (days, first day, last day) of the longest run of consecutive climbing days.
+    pub longest_streak: Option<(usize, NaiveDate, NaiveDate)>,
+    /// Hardest sends month by month, every month of the range.
+    pub progression: Vec<ProgressPoint>,
+    /// Sends harder than anything sent in the discipline before them.
+    pub records: Vec<RecapSend>,
+    /// (rope, boulder) grading systems grades are shown in.
+    pub systems: (GradeSystem, GradeSystem),
+    /// Unit the vertical is shown in.
+    pub units: LengthUnit,
+}
+
+fn grade_name(grade: i32, boulder: bool, systems: (GradeSystem, GradeSystem)) -> String {
+    conversion::index_name(grade, boulder, if boulder { systems.1 } else { systems.0 })
+}
+
+fn parse_date(send: &SendModel) -> Option<NaiveDate> {
+    NaiveDate::from_str(&send.date).ok()
+}
+
+fn is_send(send: &SendModel) -> bool {
+    SendType::from_str(&send.r#type).is_ok_and(SendType::is_send)
+}
+
+fn month_of(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn months_since(start: NaiveDate, date: NaiveDate) -> i32 {
+    // Whole calendar months from start's month to date's month
+    let month = |date: NaiveDate| date.year() * 12 + i32::try_from(date.month0()).unwrap_or(0);
+    month(date) - month(start)
+}
+
+impl Recap {
+    /// Works out the recap of the sends logged from one day to another, both included.
+    #[allow(clippy::too_many_arguments, clippy::too_many_lines)] // Each table the recap reads from, and every section of it
+    pub fn build(
+        from: NaiveDate,
+        to: NaiveDate,
+        sends: &[SendModel],
+        tall: &[(i32, &SendModel)],
+        boulder: &[(i32, &SendModel)],
+        routes: &[(RouteModel, GradeModel)],
+        partners: &[PartnerModel],
+        send_partners: &[SendPartnerModel],
+        systems: (GradeSystem, GradeSystem),
+        units: LengthUnit,
+    ) -> Recap {
+        let in_range = |send: &SendModel| parse_date(send).is_some_and(|date| date >= from && date <= to);
+        let route_of = |id: i32| routes.iter().find(|(route, _)| route.id == id).map(|(route, _)| route);
+        let route_name = |id: i32| route_of(id).map_or("Unknown".to_string(), |route| route.name.clone());
+        let crag_of = |id: i32| route_of(id)
+            .and_then(|route| route.location.clone())
+            .map(|location| location.trim().to_string())
+            .filter(|location| !location.is_empty());
+
+        let logged: Vec<&SendModel> = sends.iter().filter(|send| in_range(send)).collect();
+        let dates: BTreeSet<NaiveDate> = logged.iter().filter_map(|send| parse_date(send)).collect();
+        let sessions: BTreeSet<i32> = logged.iter().map(|send| send.session).collect();
+        let completed: Vec<&&SendModel> = logged.iter().filter(|send| is_send(send)).collect();
+        let vertical = completed.iter().filter_map(|send| route_of(send.route)).map(|route| route.length).sum();
+
+        // Hardest sends per discipline
+        let hardest = |samples: &[(i32, &SendModel)], boulder: bool| -> Vec<RecapSend> {
+            let in_range: Vec<(i32, &SendModel)> = samples.iter().filter(|(_, send)| in_range(send)).copied().collect();
+            crate::stats::hardest_sends(&in_range, TOP_COUNT).into_iter().map(|(grade, send)| RecapSend {
+                grade,
+                grade_name: grade_name(grade, boulder, systems),
+                route: route_name(send.route),
+                date: send.date.clone(),
+            }).collect()
+        };
+
+        // Crags with their first ever visit inside the range
+        let mut first_visits: HashMap<String, NaiveDate> = HashMap::new();
+        for send in sends {
+            if let (Some(crag), Some(date)) = (crag_of(send.route), parse_date(send)) {
+                let first = first_visits.entry(crag).or_insert(date);
+                *first = (*first).min(date);
+            }
+        }
+        let mut new_crags: Vec<(NaiveDate, String)> = first_visits.into_iter()
+            .filter(|(_, date)| *date >= from && *date <= to)
+            .map(|(crag, date)| (date, crag))
+            .collect();
+        new_crags.sort();
+
+        // Partners by days climbed together
+        let mut partner_days: BTreeMap<i32, BTreeSet<&str>> = BTreeMap::new();
+        for link in send_partners {
+            if let Some(send) = logged.iter().find(|send| send.id == link.send) {
+                partner_days.entry(link.partner).or_default().insert(send.date.as_str());
+            }
+        }
+        let mut top_partners: Vec<(String, usize)> = partner_days.into_iter()
+            .filter_map(|(id, days)| partners.iter().find(|partner| partner.id == id).map(|partner| (partner.name.clone(), days.len())))
+            .collect();
+        top_partners.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_partners.truncate(TOP_COUNT);
+
+        // Longest run of consecutive days climbing
+        let mut longest_streak: Option<(usize, NaiveDate, NaiveDate)> = None;
+        let mut current: Option<(usize, NaiveDate, NaiveDate)> = None;
+        for date in &dates {
+            current = match current {
+                Some((length, start, end)) if end.succ_opt() == Some(*date) => Some((length + 1, start, *date)),
+                _ => Some((1, *date, *date)),
+            };
+            if current.map(|(length, _, _)| length) > longest_streak.map(|(length, _, _)| length) {
+                longest_streak = current;
+            }
+        }
+
+        // Monthly hardest sends for the progression chart
+        let mut months: BTreeMap<NaiveDate, ProgressPoint> = BTreeMap::new();
+        for (samples, boulder) in [(tall, false), (boulder, true)] {
+            for (grade, send) in samples.iter().filter(|(_, send)| in_range(send) && is_send(send)) {
+                let Some(date) = parse_date(send) else { continue };
+                let month = month_of(date);
+                let point = months.entry(month).or_insert(ProgressPoint { month, tall: None, boulder: None });
+                let best = if boulder { &mut point.boulder } else { &mut point.tall };
+                *best = Some(best.map_or(*grade, |best| best.max(*grade)));
+            }
+        }
+
+        // Personal records, each send that beat every earlier send in its discipline
+        let mut records: Vec<RecapSend> = Vec::new();
+        for (samples, boulder) in [(tall, false), (boulder, true)] {
+            let mut ordered: Vec<(NaiveDate, i32, &SendModel)> = samples.iter()
+                .filter(|(_, send)| is_send(send))
+                .filter_map(|(grade, send)| parse_date(send).map(|date| (date, *grade, *send)))
+                .collect();
+            ordered.sort_by_key(|(date, _, send)| (*date, send.id));
+            let mut best: Option<i32> = None;
+            for (date, grade, send) in ordered {
+                if best.is_none_or(|best| grade > best) {
+                    if best.is_some() && date >= from && date <= to {
+                        records.push(RecapSend { grade, grade_name: grade_name(grade, boulder, systems), route: route_name(send.route), date: send.date.clone() });
+                    }
+                    best = Some(grade);
+                }
+            }
+        }
+        records.sort_by(|a, b| a.date.cmp(&b.date));
+
+        Recap {
+            from,
+            to,
+            days: dates.len(),
+            sessions: sessions.len(),
+            sends: completed.len(),
+            attempts: logged.iter().map(|send| send.attempts).sum(),
+            vertical,
+            hardest_tall: hardest(tall, false),
+            hardest_boulder: hardest(boulder, true),
+            new_crags: new_crags.into_iter().map(|(_, crag)| crag).collect(),
+            top_partners,
+            longest_streak,
+            progression: months.into_values().collect(),
+            records,
+            systems,
+            units,
+        }
+    }
+
+    /// "2024 in Review" for a calendar year, otherwise the dates.
+    pub fn title(&self) -> String {
+        if self.from.month() == 1 && self.from.day() == 1 && self.to.month() == 12 && self.to.day() == 31 && self.from.year() == self.to.year() {
+            format!("{} in Review", self.from.year())
+        } else {
+            format!("Climbing Recap {} to {}", self.from, self.to)
+        }
+    }
+
+    /// Longest streak as shown, like "3 days (2024-05-01 to 2024-05-03)".
+    pub fn describe_streak(&self) -> String {
+        match self.longest_streak {
+            Some((1, start, _)) => format!("1 day ({start})"),
+            Some((days, start, end)) => format!("{days} days ({start} to {end})"),
+            None => "None".to_string(),
+        }
+    }
+
+    /// The recap as one HTML page with inline styles and SVG charts.
+    pub fn to_html(&self) -> String {
+        // A single HTML file with inline styles and SVG charts, no outside resources needed
+        let mut html = String::new();
+        let title = escape(&self.title());
+        let _ = write!(html, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n");
+        html.push_str("<style>\nbody { font-family: sans-serif; max-width: 760px; margin: 2em auto; color: #222; }\n\
+            h1 { border-bottom: 2px solid #444; }\ntable { border-collapse: collapse; margin-bottom: 1em; }\n\
+            td, th { padding: 4px 12px; text-align: left; border-bottom: 1px solid #ddd; }\n\
+            .totals td:first-child { font-weight: bold; }\n</style>\n</head>\n<body>\n");
+        let _ = writeln!(html, "<h1>{title}</h1>");
+
+        html.push_str("<h2>Totals</h2>\n<table class=\"totals\">\n");
+        for (label, value) in [
+            ("Days Climbing", self.days.to_string()),
+            ("Sessions", self.sessions.to_string()),
+            ("Sends", self.sends.to_string()),
+            ("Attempts", self.attempts.to_string()),
+            ("Vertical", self.units.format(self.vertical)),
+            ("Longest Streak", self.describe_streak()),
+        ] {
+            let _ = writeln!(html, "<tr><td>{label}</td><td>{}</td></tr>", escape(&value));
+        }
+        html.push_str("</table>\n");
+
+        for (heading, sends) in [("Hardest Tall Wall Sends", &self.hardest_tall), ("Hardest Boulder Sends", &self.hardest_boulder), ("Personal Records", &self.records)] {
+            let _ = writeln!(html, "<h2>{heading}</h2>");
+            if sends.is_empty() {
+                html.push_str("<p>None</p>\n");
+                continue;
+            }
+            html.push_str("<table>\n<tr><th>Grade</th><th>Route</th><th>Date</th></tr>\n");
+            for send in sends {
+                let _ = writeln!(html, "<tr><td>{}</td><td>{}</td><td>{}</td></tr>", escape(&send.grade_name), escape(&send.route), escape(&send.date));
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("<h2>New Crags</h2>\n");
+        html.push_str(&html_list(self.new_crags.iter().map(|crag| escape(crag))));
+        html.push_str("<h2>Top Partners</h2>\n");
+        html.push_str(&html_list(self.top_partners.iter().map(|(name, days)| format!("{} ({days} days)", escape(name)))));
+
+        html.push_str("<h2>Grade Progression</h2>\n");
+        let tall: Vec<(NaiveDate, i32)> = self.progression.iter().filter_map(|point| point.tall.map(|grade| (point.month, grade))).collect();
+        let boulder: Vec<(NaiveDate, i32)> = self.progression.iter().filter_map(|point| point.boulder.map(|grade| (point.month, grade))).collect();
+        if tall.is_empty() && boulder.is_empty() {
+            html.push_str("<p>No sends to chart.</p>\n");
+        }
+        if !tall.is_empty() {
+            html.push_str("<h3>Tall Wall</h3>\n");
+            html.push_str(&svg_chart(&tall, (self.from, self.to), "#1f77b4", false, self.systems));
+        }
+        if !boulder.is_empty() {
+            html.push_str("<h3>Boulder</h3>\n");
+            html.push_str(&svg_chart(&boulder, (self.from, self.to), "#d62728", true, self.systems));
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn html_list(items: impl Iterator<Item = String>) -> String {
+    let items: Vec<String> = items.map(|item| format!("<li>{item}</li>")).collect();
+    if items.is_empty() {
+        return "<p>None</p>\n".to_string();
+    }
+    format!("<ul>\n{}\n</ul>\n", items.join("\n"))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn svg_chart(points: &[(NaiveDate, i32)], range: (NaiveDate, NaiveDate), color: &str, boulder: bool, systems: (GradeSystem, GradeSystem)) -> String {
+    // Monthly hardest grade as an inline SVG line chart, months without sends leave a gap along the range
+    const WIDTH: i32 = 640;
+    const HEIGHT: i32 = 240;
+    const LEFT: i32 = 60;
+    const BOTTOM: i32 = 30;
+    const TOP: i32 = 10;
+    let low = points.iter().map(|(_, grade)| *grade).min().unwrap_or(0) - 1;
+    let high = points.iter().map(|(_, grade)| *grade).max().unwrap_or(0) + 1;
+    let span = months_since(range.0, range.1);
+    let x = |month: NaiveDate| if span <= 0 { LEFT + (WIDTH - LEFT) / 2 } else { LEFT + months_since(range.0, month) * (WIDTH - LEFT - 20) / span };
+    let y = |grade: i32| TOP + (high - grade) * (HEIGHT - TOP - BOTTOM) / (high - low);
+
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" viewBox=\"0 0 {WIDTH} {HEIGHT}\">\n");
+    let _ = writeln!(svg, "<rect x=\"{LEFT}\" y=\"{TOP}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#444\"/>", WIDTH - LEFT, HEIGHT - TOP - BOTTOM);
+    let step = ((high - low) / 8).max(1);
+    let mut grade = low + 1;
+    while grade < high {
+        let _ = writeln!(svg, "<line x1=\"{LEFT}\" y1=\"{0}\" x2=\"{WIDTH}\" y2=\"{0}\" stroke=\"#ddd\"/><text x=\"{1}\" y=\"{0}\" font-size=\"11\" text-anchor=\"end\" dominant-baseline=\"middle\">{2}</text>",
+            y(grade), LEFT - 6, escape(&grade_name(grade, boulder, systems)));
+        grade += step;
+    }
+    let mut labelled: Option<i32> = None; // x of the last month label, keeps labels from overlapping on long ranges
+    let mut line = Vec::new();
+    for (month, grade) in points {
+        let (px, py) = (x(*month), y(*grade));
+        line.push(format!("{px},{py}"));
+        let _ = writeln!(svg, "<circle cx=\"{px}\" cy=\"{py}\" r=\"3\" fill=\"{color}\"/>");
+        if labelled.is_none_or(|last| px - last >= 60) {
+            labelled = Some(px);
+            let _ = writeln!(svg, "<text x=\"{px}\" y=\"{}\" font-size=\"11\" text-anchor=\"middle\">{}</text>", HEIGHT - BOTTOM + 16, month.format("%b %Y"));
+        }
+    }
+    let _ = writeln!(svg, "<polyline points=\"{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"2\"/>", line.join(" "));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{route, send};
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn builds_a_year_in_review() {
+        let routes = [route(1, "Arete", Some("Smith Rock")), route(2, "Crack", Some("The Gunks")), route(3, "Problem", Some("Smith Rock"))];
+        let sends = [
+            send(1, 1, "2023-06-01", SendType::Flash),
+            send(2, 1, "2024-03-01", SendType::Attempt),
+            send(3, 2, "2024-03-02", SendType::Redpoint),
+            send(4, 2, "2024-03-03", SendType::Flash),
+            send(5, 3, "2024-07-10", SendType::Flash),
+            send(6, 3, "2024-07-11", SendType::Flash),
+            send(7, 1, "2025-01-05", SendType::Flash),
+        ];
+        let tall = [(10, &sends[0]), (10, &sends[1]), (12, &sends[2]), (11, &sends[3]), (14, &sends[6])];
+        let boulder = [(4, &sends[4]), (6, &sends[5])];
+        let partners = [PartnerModel { id: 1, name: "Alex".to_string() }, PartnerModel { id: 2, name: "Sam".to_string() }];
+        let link = |id: i32, send: i32, partner: i32| SendPartnerModel { id, send, partner };
+        let send_partners = [link(1, 3, 1), link(2, 4, 1), link(3, 5, 2), link(4, 1, 2)];
+
+        let recap = Recap::build(date("2024-01-01"), date("2024-12-31"), &sends, &tall, &boulder, &routes, &partners, &send_partners,
+            (GradeSystem::Yosemite, GradeSystem::Hueco), LengthUnit::Metres);
+        assert_eq!(recap.title(), "2024 in Review");
+        assert_eq!((recap.days, recap.sessions, recap.sends, recap.attempts), (5, 5, 4, 5));
+        assert_eq!(recap.vertical, 4 * 3000, "the attempt adds no vertical");
+        let graded = |sends: &[RecapSend]| sends.iter().map(|send| (send.grade, send.route.clone())).collect::<Vec<_>>();
+        assert_eq!(graded(&recap.hardest_tall), [(12, "Crack".to_string()), (11, "Crack".to_string())]);
+        assert_eq!(graded(&recap.hardest_boulder), [(6, "Problem".to_string()), (4, "Problem".to_string())]);
+        assert_eq!(recap.new_crags, ["The Gunks"], "Smith Rock was first climbed the year before");
+        assert_eq!(recap.top_partners, [("Alex".to_string(), 2), ("Sam".to_string(), 1)]);
+        assert_eq!(recap.longest_streak, Some((3, date("2024-03-01"), date("2024-03-03"))));
+        assert_eq!(recap.progression, [
+            ProgressPoint { month: date("2024-03-01"), tall: Some(12), boulder: None },
+            ProgressPoint { month: date("2024-07-01"), tall: None, boulder: Some(6) },
+        ]);
+        assert_eq!(graded(&recap.records), [(12, "Crack".to_string()), (6, "Problem".to_string())], "the first boulder send has nothing to beat");
+    }
+
+    #[test]
+    fn empty_range() {
+        let recap = Recap::build(date("2024-02-01"), date("2024-02-29"), &[], &[], &[], &[], &[], &[], (GradeSystem::French, GradeSystem::Font), LengthUnit::Feet);
+        assert_eq!(recap.title(), "Climbing Recap 2024-02-01 to 2024-02-29");
+        assert_eq!((recap.days, recap.sends, recap.longest_streak), (0, 0, None));
+        assert_eq!(recap.describe_streak(), "None");
+        assert!(recap.to_html().contains("No sends to chart."));
+    }
+
+    #[test]
+    fn chart_spaces_points_by_month() {
+        // March and July of a year long range sit 2 and 6 months along it, not first and last
+        let points = [(date("2024-03-01"), 10), (date("2024-07-01"), 12)];
+        let svg = svg_chart(&points, (date("2024-01-01"), date("2024-12-31")), "#000", false, (GradeSystem::Yosemite, GradeSystem::Hueco));
+        assert!(svg.contains("cx=\"161\""), "{svg}");
+        assert!(svg.contains("cx=\"365\""), "{svg}");
+        let single = svg_chart(&points[..1], (date("2024-03-01"), date("2024-03-31")), "#000", false, (GradeSystem::Yosemite, GradeSystem::Hueco));
+        assert!(single.contains("cx=\"350\""), "{single}");
+    }
+}
@@ -0,0 +1,385 @@
+use sea_orm::{sea_query::OnConflict, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, Database, DatabaseConnection, DbBackend, DbErr, EntityTrait, JoinType, LoaderTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Statement, TransactionTrait};
+use migration::{Migrator, MigratorTrait};
+use crate::filter::Filter;
+use crate::search::{self, MatchKind, TextMatch};
+use crate::store::{self, LogbookStore};
+pub mod entities;
+use entities::{prelude::*, climbers, grades, partners, route_styles, routes, send_partners, sends, settings, styles};
+
+const DATABASE_URL: &str = "sqlite:./src/routes_sql.db?mode=rwc";
+/// Environment variable with the url of the logbook to use instead of the local `SQLite` file,
+/// e.g. `postgres://climber@localhost/team_logbook` for a shared team logbook.
+pub const DATABASE_ENV: &str = "CLIMB_LOG_DATABASE";
+
+/// Handle on a logbook database. Cheap to clone, every clone shares the same connection pool.
+#[derive(Clone)]
+pub struct RoutesDb {
+    db: DatabaseConnection,
+}
+
+impl RoutesDb {
+    /// Opens the logbook named by `CLIMB_LOG_DATABASE`, or the app's own `SQLite` file when that isn't set.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the database can't be opened or migrated.
+    pub async fn new() -> Result<RoutesDb, DbErr> {
+        match std::env::var(DATABASE_ENV) {
+            Ok(url) if !url.trim().is_empty() => RoutesDb::open(url.trim()).await,
+            _ => RoutesDb::open(DATABASE_URL).await,
+        }
+    }
+
+    /// Opens the logbook at a `sqlite:` or `postgres:` url and runs any migrations it hasn't had yet.
+    ///
+    /// A Postgres database has to exist already, it's never created or dropped here.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the url isn't a `sqlite:` or `postgres:` one, can't be connected to, or a migration fails.
+    pub async fn open(url: &str) -> Result<RoutesDb, DbErr> { // Any database url, the command line can point at another logbook
+        // SeaORM panics on a url it can't parse, so anything else is turned away first
+        if !matches!(url.split_once(':'), Some(("sqlite" | "postgres" | "postgresql", _))) {
+            return Err(DbErr::Custom(format!("not a sqlite: or postgres: url: {url}")));
+        }
+        // Migrations carry existing data over to the new schema, so opening never loses anything
+        let db = Database::connect(url).await?;
+        // Bring the schema up to date before anything else touches it
+        Migrator::up(&db, None).await?;
+        Ok(RoutesDb { db })
+    }
+
+    async fn remove_send(self, id: i32, session: i32) -> Result<(), DbErr> {
+        SendPartners::delete_many().filter(send_partners::Column::Send.eq(id)).exec(&self.db).await?;
+
+        let delete_send = sends::ActiveModel {
+            id: ActiveValue::Set(id),
+            session: ActiveValue::Set(session),
+            ..Default::default()
+        };
+        delete_send.delete(&self.db).await?;
+        Ok(())
+    }
+}
+
+impl LogbookStore for RoutesDb {
+    // Grade Funcs
+    async fn add_grade(self, grade: grades::Model) -> Result<(), DbErr> {
+        // Takes every system's grade at once, the id is ignored and assigned by the database
+        let mut new_grade: grades::ActiveModel = grade.into();
+        new_grade.id = ActiveValue::NotSet;
+        Grades::insert(new_grade).exec(&self.db).await?;
+        Ok(())
+    }
+
+    async fn remove_grade(self, id: i32) -> Result<(), DbErr> {
+        let delete_grade = grades::ActiveModel {
+            id: ActiveValue::Set(id),
+            ..Default::default()
+        };
+        delete_grade.delete(&self.db).await?;
+        Ok(())
+    }
+
+    async fn get_grade_id(self, grd: &str) -> Result<i32, DbErr> {
+        let mut grade = Grades::find().filter(grades::Column::Yosemite.eq(grd)).one(&self.db).await?;
+        if grade.is_none() {
+            grade = Grades::find().filter(grades::Column::Hueco.eq(grd)).one(&self.db).await?;
+        }
+        // Return the id of the grade
+        grade.map(|grade| grade.id).ok_or_else(|| DbErr::RecordNotFound(format!("Grade {grd}")))
+    }
+
+    async fn get_grade(self, id: i32) -> Result<grades::Model, DbErr> {
+        let grade = Grades::find_by_id(id).one(&self.db).await?;
+        grade.ok_or_else(|| DbErr::RecordNotFound(format!("Grade {id}")))
+    }
+
+    async fn get_all_grades(self) -> Result<Vec<grades::Model>, DbErr> {
+        let all_grades = Grades::find().order_by_asc(grades::Column::Id).all(&self.db).await?;
+        Ok(all_grades)
+    }
+
+    // Route Funcs
+    async fn add_route(self, route: routes::Model, style_names: Vec<String>) -> Result<(), DbErr> {
+        // Takes the whole route, the id is ignored and assigned by the database. Styles go by name, unknown names are skipped
+        // The route and its styles go in together, or not at all
+        let mut new_route: routes::ActiveModel = route.into();
+        new_route.id = ActiveValue::NotSet;
+        let txn = self.db.begin().await?;
+        let route_id = Routes::insert(new_route).exec(&txn).await?.last_insert_id;
+        let found = Styles::find().filter(styles::Column::Name.is_in(style_names)).all(&txn).await?;
+        for style in found {
+            let new_route_style = route_styles::ActiveModel {
+                route: ActiveValue::Set(route_id),
+                style: ActiveValue::Set(style.id),
+                ..Default::default()
+            };
+            RouteStyles::insert(new_route_style).exec(&txn).await?;
+        }
+        txn.commit().await
+    }
+    
+    async fn remove_route(self, id: i32) -> Result<(), DbErr> {
+        // Routes with sends are refused before anything is deleted, the same as MemoryStore
+        if Sends::find().filter(sends::Column::Route.eq(id)).one(&self.db).await?.is_some() {
+            return Err(store::in_use("sends"));
+        }
+        let txn = self.db.begin().await?;
+        RouteStyles::delete_many().filter(route_styles::Column::Route.eq(id)).exec(&txn).await?;
+        let delete_route = routes::ActiveModel {
+            id: ActiveValue::Set(id),
+            ..Default::default()
+        };
+        delete_route.delete(&txn).await?;
+        txn.commit().await
+    }
+
+    async fn find_route_name(self, name: &str) -> Result<Option<routes::Model>, DbErr> {
+        let route = Routes::find().filter(routes::Column::Name.eq(name)).one(&self.db).await?;
+        if route.is_none() {
+            return Ok(None);
+        }
+        Ok(route)
+    }
+
+    async fn get_route_id(self, name: &str) -> Result<i32, DbErr> {
+        let route = Routes::find().filter(routes::Column::Name.eq(name)).one(&self.db).await?;
+        route.map(|route| route.id).ok_or_else(|| DbErr::RecordNotFound(format!("Route {name}")))
+    }
+
+    async fn find_routes_by_grade(self, grade: i32) -> Result<Vec<String>, DbErr> {
+        let grades: Vec<grades::Model> = Grades::find()
+            .filter(grades::Column::Id.eq(grade))
+            .all(&self.db)
+            .await?;
+
+        let find_routes: Vec<Vec<routes::Model>> = grades.load_many(Routes, &self.db).await?;
+        // No routes for a grade that isn't there, the same as MemoryStore
+        let mut routes_at_grade: Vec<String> = find_routes.into_iter().next().unwrap_or_default().iter().map(|route| route.name.clone()).collect();
+        routes_at_grade.sort_unstable();
+        Ok(routes_at_grade)
+    }
+
+    async fn find_all_routes(self) -> Result<Vec<routes::Model>, DbErr> {
+        let all_routes: Vec<routes::Model> = Routes::find().order_by_asc(routes::Column::Id).all(&self.db).await?;
+        Ok(all_routes)
+    }
+
+    async fn find_route_by_id(self, id: i32) -> Result<routes::Model, DbErr> {
+        let route = Routes::find_by_id(id).one(&self.db).await?;
+        route.ok_or_else(|| DbErr::RecordNotFound(format!("Route {id}")))
+    }
+
+    async fn find_route_and_grade(self, name: &str) -> Result<(routes::Model, grades::Model), DbErr> {
+        // One query, the grade comes back joined onto the route
+        let found = Routes::find().filter(routes::Column::Name.eq(name)).find_also_related(Grades).one(&self.db).await?;
+        match found {
+            Some((route, Some(grade))) => Ok((route, grade)),
+            Some((route, None)) => Err(DbErr::RecordNotFound(format!("Grade {}", route.grade_id))),
+            None => Err(DbErr::RecordNotFound("Route not found".to_string())),
+        }
+    }
+
+    async fn find_all_routes_and_grade(self) -> Result<Vec<(routes::Model, grades::Model)>, DbErr> {
+        // Joined in a single query rather than a grade lookup per route
+        let all_routes = Routes::find().order_by_asc(routes::Column::Id).find_also_related(Grades).all(&self.db).await?;
+        all_routes.into_iter()
+            .map(|(route, grade)| match grade {
+                Some(grade) => Ok((route, grade)),
+                None => Err(DbErr::RecordNotFound(format!("Grade {}", route.grade_id))),
+            })
+            .collect()
+    }
+
+    async fn get_all_route_styles(self) -> Result<Vec<(route_styles::Model, Option<styles::Model>)>, DbErr> {
+        let all_route_styles = RouteStyles::find().find_also_related(Styles).all(&self.db).await?;
+        Ok(all_route_styles)
+    }
+
+    // Send/Session Funcs
+    async fn add_send(self, climber: i32, session: i32, route: entities::routes::Model, date: String, partners: Vec<i32>, send_type: String, attempts: i32, notes: Option<String>, personal_grade: Option<String>) -> Result<(), DbErr> {
+        let new_send = sends::ActiveModel {
+            session: ActiveValue::Set(session),
+            date: ActiveValue::Set(date.clone()),
+            r#type: ActiveValue::Set(send_type.clone()),
+            attempts: ActiveValue::Set(attempts),
+            notes: ActiveValue::Set(notes.clone()),
+            route: ActiveValue::Set(self.clone().get_route_id(&route.name).await?),
+            personal_grade: ActiveValue::Set(personal_grade),
+            climber: ActiveValue::Set(climber),
+            ..Default::default()
+        };
+
+        // The send and its partners go in together, or not at all
+        let txn = self.db.begin().await?;
+        let send_id = Sends::insert(new_send).exec(&txn).await?.last_insert_id;
+        for partner in partners {
+            let new_send_partner = send_partners::ActiveModel {
+                send: ActiveValue::Set(send_id),
+                partner: ActiveValue::Set(partner),
+                ..Default::default()
+            };
+            SendPartners::insert(new_send_partner).exec(&txn).await?;
+        }
+        txn.commit().await
+    }
+
+    async fn get_session(self, id: i32) -> Result<Vec<sends::Model>, DbErr> {
+        let session = Sends::find().filter(sends::Column::Session.eq(id)).all(&self.db).await?;
+        Ok(session)
+    }
+
+    async fn get_session_by_date(self, date: String) -> Result<Vec<sends::Model>, DbErr> {
+        let session = Sends::find().filter(sends::Column::Date.eq(date)).all(&self.db).await?;
+        Ok(session)
+    }
+
+    async fn get_all_sends(self) -> Result<Vec<sends::Model>, DbErr> {
+        let all_sends: Vec<sends::Model> = Sends::find().order_by_asc(sends::Column::Id).all(&self.db).await?;
+        Ok(all_sends)
+    }
+
+    async fn remove_session(self, id: i32) -> Result<(), DbErr> {
+        let session = self.clone().get_session(id).await?;
+        for send in session {
+            self.clone().remove_send(send.id, send.session).await?;
+        }
+        Ok(())
+    }
+
+    async fn get_next_session_id(self) -> Result<i32, DbErr> {
+        // Get the highest session id
+        let id = Sends::find().order_by_desc(sends::Column::Session).one(&self.db).await?;
+        Ok(id.map_or(1, |send| send.session + 1))
+    }
+
+    // Climber Funcs
+    async fn add_climber(self, name: &str) -> Result<i32, DbErr> {
+        // Names are unique like partners, so adding an existing climber just returns their id
+        if let Some(climber) = Climbers::find().filter(climbers::Column::Name.eq(name)).one(&self.db).await? {
+            return Ok(climber.id);
+        }
+        let new_climber = climbers::ActiveModel {
+            name: ActiveValue::Set(name.to_string()),
+            ..Default::default()
+        };
+        Ok(Climbers::insert(new_climber).exec(&self.db).await?.last_insert_id)
+    }
+
+    async fn get_all_climbers(self) -> Result<Vec<climbers::Model>, DbErr> {
+        let all_climbers = Climbers::find().order_by_asc(climbers::Column::Id).all(&self.db).await?;
+        Ok(all_climbers)
+    }
+
+    // Partner Funcs
+    async fn add_partner(self, name: &str) -> Result<i32, DbErr> {
+        // Names are unique, so adding an existing partner just returns their id
+        if let Some(partner) = Partners::find().filter(partners::Column::Name.eq(name)).one(&self.db).await? {
+            return Ok(partner.id);
+        }
+        let new_partner = partners::ActiveModel {
+            name: ActiveValue::Set(name.to_string()),
+            ..Default::default()
+        };
+        Ok(Partners::insert(new_partner).exec(&self.db).await?.last_insert_id)
+    }
+
+    async fn remove_partner(self, id: i32) -> Result<(), DbErr> {
+        SendPartners::delete_many().filter(send_partners::Column::Partner.eq(id)).exec(&self.db).await?;
+        let delete_partner = partners::ActiveModel {
+            id: ActiveValue::Set(id),
+            ..Default::default()
+        };
+        delete_partner.delete(&self.db).await?;
+        Ok(())
+    }
+
+    async fn get_all_partners(self) -> Result<Vec<partners::Model>, DbErr> {
+        let all_partners = Partners::find().order_by_asc(partners::Column::Name).all(&self.db).await?;
+        Ok(all_partners)
+    }
+
+    async fn get_all_send_partners(self) -> Result<Vec<send_partners::Model>, DbErr> {
+        let all_send_partners = SendPartners::find().order_by_asc(send_partners::Column::Id).all(&self.db).await?;
+        Ok(all_send_partners)
+    }
+
+    // Search Funcs
+    async fn search_text(self, text: &str) -> Result<Vec<TextMatch>, DbErr> {
+        // Postgres logbooks have no FTS5 index, so they're scanned like MemoryStore
+        if self.db.get_database_backend() != DbBackend::Sqlite {
+            let routes = self.clone().find_all_routes().await?;
+            let sends = self.clone().get_all_sends().await?;
+            let partners = self.clone().get_all_partners().await?;
+            let send_partners = self.get_all_send_partners().await?;
+            return Ok(search::scan(text, &routes, &sends, &partners, &send_partners));
+        }
+        let Some(query) = search::fts_query(text) else {
+            return Ok(Vec::new());
+        };
+        let mut found = Vec::new();
+        // Index rows share their rowid with the route or send they came from
+        for (kind, table) in [(MatchKind::Route, "route_search"), (MatchKind::Send, "send_search")] {
+            let sql = format!("SELECT rowid AS id, snippet({table}, -1, ?, ?, ?, {}) AS snippet FROM {table} WHERE {table} MATCH ? ORDER BY rank", search::SNIPPET_WORDS);
+            let values = [search::MATCH_START.into(), search::MATCH_END.into(), search::ELLIPSIS.into(), query.clone().into()];
+            for row in self.db.query_all(Statement::from_sql_and_values(DbBackend::Sqlite, sql, values)).await? {
+                found.push(TextMatch { kind, id: row.try_get("", "id")?, snippet: row.try_get("", "snippet")? });
+            }
+        }
+        Ok(found)
+    }
+
+    // Filter Funcs
+    async fn filter_sends(self, filter: &Filter, climber: i32) -> Result<Vec<sends::Model>, DbErr> {
+        // Joined to routes and grades so the filter can reach their columns
+        let found = Sends::find()
+            .join(JoinType::InnerJoin, sends::Relation::Routes.def())
+            .join(JoinType::InnerJoin, routes::Relation::Grades.def())
+            .filter(sends::Column::Climber.eq(climber))
+            .filter(filter.send_condition())
+            .order_by_asc(sends::Column::Id)
+            .all(&self.db)
+            .await?;
+        Ok(found)
+    }
+
+    async fn filter_routes(self, filter: &Filter, climber: i32) -> Result<Vec<(routes::Model, grades::Model)>, DbErr> {
+        let found = Routes::find()
+            .find_also_related(Grades)
+            .filter(filter.route_condition(climber))
+            .order_by_asc(routes::Column::Id)
+            .all(&self.db)
+            .await?;
+        found.into_iter()
+            .map(|(route, grade)| match grade {
+                Some(grade) => Ok((route, grade)),
+                None => Err(DbErr::RecordNotFound(format!("Grade {}", route.grade_id))),
+            })
+            .collect()
+    }
+
+    // Settings Funcs
+    async fn get_setting(self, key: &str) -> Result<Option<String>, DbErr> {
+        let setting = Settings::find_by_id(key.to_string()).one(&self.db).await?;
+        Ok(setting.map(|setting| setting.value))
+    }
+
+    async fn get_all_settings(self) -> Result<Vec<settings::Model>, DbErr> {
+        let all_settings = Settings::find().order_by_asc(settings::Column::Key).all(&self.db).await?;
+        Ok(all_settings)
+    }
+
+    async fn set_setting(self, key: &str, value: String) -> Result<(), DbErr> {
+        let setting = settings::ActiveModel {
+            key: ActiveValue::Set(key.to_string()),
+            value: ActiveValue::Set(value),
+        };
+        // Insert or overwrite the existing value for this key
+        Settings::insert(setting)
+            .on_conflict(OnConflict::column(settings::Column::Key).update_column(settings::Column::Value).to_owned())
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.15
 
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Hash)]
+#[derive(Clone, Debug, Default, PartialEq, DeriveEntityModel, Eq, Hash, Serialize, Deserialize)]
 #[sea_orm(table_name = "grades")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -12,6 +13,11 @@ pub struct Model {
     pub font: Option<String>,
     pub french: Option<String>,
     pub uiaa: Option<String>,
+    pub british: Option<String>,
+    pub ewbank: Option<String>,
+    pub saxon: Option<String>,
+    pub brazilian: Option<String>,
+    pub kurtyka: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
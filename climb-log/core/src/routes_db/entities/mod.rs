@@ -2,6 +2,12 @@
 
 pub mod prelude;
 
+pub mod climbers;
 pub mod grades;
+pub mod partners;
+pub mod route_styles;
 pub mod routes;
+pub mod send_partners;
 pub mod sends;
+pub mod settings;
+pub mod styles;
@@ -1,17 +1,27 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.15
 
 use sea_orm::entity::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "routes")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub name: String,
-    pub length: i32,
+    pub length: i32, // Centimetres
     pub pitches: i32,
-    pub style: String,
     pub grade_id: i32,
+    pub location: Option<String>,
+    pub ice_grade: Option<String>,
+    pub mixed_grade: Option<String>,
+    pub aid_grade: Option<String>,
+    pub commitment_grade: Option<String>,
+    pub ifas_grade: Option<String>,
+    pub danger: Option<String>,
+    pub grade_high: Option<String>,
+    pub discipline: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -24,6 +34,8 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Grades,
+    #[sea_orm(has_many = "super::route_styles::Entity")]
+    RouteStyles,
     #[sea_orm(has_many = "super::sends::Entity")]
     Sends,
 }
@@ -34,6 +46,12 @@ impl Related<super::grades::Entity> for Entity {
     }
 }
 
+impl Related<super::route_styles::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RouteStyles.def()
+    }
+}
+
 impl Related<super::sends::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Sends.def()
@@ -1,19 +1,23 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.15
 
 use sea_orm::entity::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
 #[sea_orm(table_name = "sends")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub session: i32,
     pub date: String,
-    pub partner: Option<String>,
     pub r#type: String,
     pub attempts: i32,
     pub notes: Option<String>,
     pub route: i32,
+    pub personal_grade: Option<String>,
+    #[serde(default)]
+    pub climber: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -26,6 +30,8 @@ pub enum Relation {
         on_delete = "NoAction"
     )]
     Routes,
+    #[sea_orm(has_many = "super::send_partners::Entity")]
+    SendPartners,
 }
 
 impl Related<super::routes::Entity> for Entity {
@@ -34,4 +40,10 @@ impl Related<super::routes::Entity> for Entity {
     }
 }
 
+impl Related<super::send_partners::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SendPartners.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,274 @@
+use std::{collections::HashMap, fmt::Write, hash::BuildHasher};
+use chrono::NaiveDate;
+use serde::{Serialize, Serializer};
+use crate::climbing::{SendType, Style};
+use crate::entry::{self, EntryError};
+use crate::logbook::{Logbook, LogbookData};
+use crate::routes_db::entities::{grades::Model as GradeModel, partners, routes, send_partners, sends};
+use crate::settings::Settings;
+use crate::store::LogbookStore;
+
+// Full-text search over route names, crags, send notes and partners, narrowed down by filters
+// SQLite logbooks match through their FTS5 index, MemoryStore and Postgres are scanned here the same way:
+// every word searched for has to start a word somewhere in the route or send
+
+/// Put before a matched word in a snippet.
+pub const MATCH_START: &str = "\u{2}";
+/// Put after a matched word in a snippet.
+pub const MATCH_END: &str = "\u{3}";
+/// Stands in for text cut off either side of a snippet.
+pub const ELLIPSIS: &str = "…";
+/// Longest snippet, in words.
+pub const SNIPPET_WORDS: usize = 12;
+
+/// Whether a match is a route or a send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchKind {
+    /// A route, matched on its name or crag.
+    Route,
+    /// A send, matched on its notes or partners.
+    Send,
+}
+
+/// A route or send with every word searched for, and a snippet of where they turned up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextMatch {
+    /// Route or send.
+    pub kind: MatchKind,
+    /// Id of the route or send.
+    pub id: i32,
+    /// Matched words between `MATCH_START` and `MATCH_END`.
+    pub snippet: String,
+}
+
+fn words(text: &str) -> Vec<String> {
+    // Split the way FTS5's default tokenizer does, on anything that isn't a letter or digit
+    text.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty()).map(str::to_lowercase).collect()
+}
+
+/// The search text as an FTS5 query, every word as a prefix. None when there's nothing to search for.
+///
+/// Only letters and digits make it through, so nothing typed is read as query syntax.
+pub fn fts_query(text: &str) -> Option<String> {
+    let terms = words(text);
+    (!terms.is_empty()).then(|| terms.iter().map(|term| format!("\"{term}\"*")).collect::<Vec<_>>().join(" "))
+}
+
+fn matches_term(token: &str, terms: &[String]) -> bool {
+    words(token).iter().any(|word| terms.iter().any(|term| word.starts_with(term.as_str())))
+}
+
+fn mark(token: &str, terms: &[String]) -> String {
+    // Marks the matched words in a token, leaving punctuation around them out like FTS5 does
+    let mut marked = String::new();
+    let mut word = String::new();
+    for c in token.chars().chain(std::iter::once(' ')) {
+        if c.is_alphanumeric() {
+            word.push(c);
+            continue;
+        }
+        if matches_term(&word, terms) {
+            let _ = write!(marked, "{MATCH_START}{word}{MATCH_END}");
+        } else {
+            marked.push_str(&word);
+        }
+        word.clear();
+        marked.push(c);
+    }
+    marked.pop();
+    marked
+}
+
+fn snippet(field: &str, terms: &[String]) -> String {
+    // A few words either side of the first match, like FTS5's snippet()
+    let tokens: Vec<&str> = field.split_whitespace().collect();
+    let first = tokens.iter().position(|token| matches_term(token, terms)).unwrap_or(0);
+    let start = first.saturating_sub(SNIPPET_WORDS / 4);
+    let end = (start + SNIPPET_WORDS).min(tokens.len());
+    let marked: Vec<String> = tokens[start..end].iter().map(|token| mark(token, terms)).collect();
+    let mut snippet = marked.join(" ");
+    if start > 0 {
+        snippet.insert_str(0, ELLIPSIS);
+    }
+    if end < tokens.len() {
+        snippet.push_str(ELLIPSIS);
+    }
+    snippet
+}
+
+fn find(kind: MatchKind, id: i32, fields: &[&str], terms: &[String]) -> Option<TextMatch> {
+    let all_words: Vec<String> = fields.iter().flat_map(|field| words(field)).collect();
+    if !terms.iter().all(|term| all_words.iter().any(|word| word.starts_with(term.as_str()))) {
+        return None;
+    }
+    // The snippet comes from whichever field matched the most
+    let best = fields.iter().max_by_key(|field| (field.split_whitespace().filter(|token| matches_term(token, terms)).count(), std::cmp::Reverse(field.len())))?;
+    Some(TextMatch { kind, id, snippet: snippet(best, terms) })
+}
+
+/// Searches without an index, for stores that don't have one. Routes come first, then sends.
+pub fn scan(text: &str, routes: &[routes::Model], sends: &[sends::Model], partners: &[partners::Model], send_partners: &[send_partners::Model]) -> Vec<TextMatch> {
+    let terms = words(text);
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let route_matches = routes.iter().filter_map(|route| find(MatchKind::Route, route.id, &[&route.name, route.location.as_deref().unwrap_or("")], &terms));
+    let send_matches = sends.iter().filter_map(|send| {
+        let names: Vec<&str> = send_partners.iter().filter(|link| link.send == send.id)
+            .filter_map(|link| partners.iter().find(|partner| partner.id == link.partner))
+            .map(|partner| partner.name.as_str())
+            .collect();
+        find(MatchKind::Send, send.id, &[send.notes.as_deref().unwrap_or(""), &names.join(" ")], &terms)
+    });
+    route_matches.chain(send_matches).collect()
+}
+
+/// A snippet split into plain and matched parts, in order.
+pub fn highlights(snippet: &str) -> Vec<(&str, bool)> {
+    let mut parts = Vec::new();
+    let mut pieces = snippet.split(MATCH_START);
+    parts.extend(pieces.next().map(|plain| (plain, false)));
+    for piece in pieces {
+        match piece.split_once(MATCH_END) {
+            Some((matched, plain)) => parts.extend([(matched, true), (plain, false)]),
+            None => parts.push((piece, true)),
+        }
+    }
+    parts.retain(|(text, _)| !text.is_empty());
+    parts
+}
+
+/// Narrows search results down. Empty lists and unset bounds let everything through.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFilters {
+    /// In the preferred grading system or any other, both ends routes or both boulders.
+    pub grade_from: Option<String>,
+    /// Top of the grade range, in the same systems.
+    pub grade_to: Option<String>,
+    /// Routes with any of these.
+    pub styles: Vec<Style>,
+    /// Sends of any of these types.
+    pub send_types: Vec<SendType>,
+    /// Sends on or after the day.
+    pub from: Option<NaiveDate>,
+    /// Sends on or before the day.
+    pub to: Option<NaiveDate>,
+}
+
+impl SearchFilters {
+    fn filters_sends(&self) -> bool {
+        !self.send_types.is_empty() || self.from.is_some() || self.to.is_some()
+    }
+
+    fn send_passes(&self, send: &sends::Model) -> bool {
+        let send_type = entry::find_named(SendType::iter(), "send type", &send.r#type).ok();
+        let date = NaiveDate::parse_from_str(&send.date, "%Y-%m-%d").ok();
+        (self.send_types.is_empty() || send_type.is_some_and(|send_type| self.send_types.contains(&send_type)))
+            && self.from.is_none_or(|from| date.is_some_and(|date| date >= from))
+            && self.to.is_none_or(|to| date.is_some_and(|date| date <= to))
+    }
+}
+
+fn grade_bound(text: &str, logbook: &Logbook) -> Result<(bool, i32), EntryError> {
+    // Whether it's a boulder grade, and its stored index
+    logbook.settings.any_grade_index(text).ok_or_else(|| EntryError::Invalid(format!("unknown grade: {}", text.trim())))
+}
+
+fn snippet_parts<S: Serializer>(snippet: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Part<'a> {
+        text: &'a str,
+        matched: bool,
+    }
+    serializer.collect_seq(highlights(snippet).into_iter().map(|(text, matched)| Part { text, matched }))
+}
+
+/// A search result, linking to the route or the send and its session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchHit {
+    /// Route or send.
+    pub kind: MatchKind,
+    /// The route matched, or the one the send was on.
+    pub route: i32,
+    /// The send matched.
+    pub send: Option<i32>,
+    /// Session of the send matched.
+    pub session: Option<i32>,
+    /// Day of the send matched, YYYY-MM-DD.
+    pub date: Option<String>,
+    /// Route name, after the day for sends.
+    pub title: String,
+    /// Words around the match, as parts with whether each was matched.
+    #[serde(serialize_with = "snippet_parts")]
+    pub snippet: String,
+}
+
+/// Search matches that pass the filters, as results. Sends are only the logbook's, so one climber's when it's been narrowed to them.
+///
+/// # Errors
+///
+/// Fails with [`EntryError::Invalid`] when a grade bound isn't a grade, or the two bounds mix routes and boulders.
+pub fn hits<S: BuildHasher>(matches: &[TextMatch], logbook: &Logbook, route_styles: &HashMap<i32, Vec<Style>, S>, filters: &SearchFilters) -> Result<Vec<SearchHit>, EntryError> {
+    let from = filters.grade_from.as_deref().map(|text| grade_bound(text, logbook)).transpose()?;
+    let to = filters.grade_to.as_deref().map(|text| grade_bound(text, logbook)).transpose()?;
+    if let (Some((from_boulder, _)), Some((to_boulder, _))) = (from, to) {
+        if from_boulder != to_boulder {
+            return Err(EntryError::Invalid("a grade range can't mix boulder and route grades".to_string()));
+        }
+    }
+    let route_passes = |route: &routes::Model, grade: &GradeModel| {
+        let boulder = Logbook::is_boulder(route);
+        let index = if boulder { logbook.boulder_index(route, grade) } else { logbook.tall_index(route, grade) };
+        let in_range = [(from, true), (to, false)].into_iter().all(|(bound, lower)| match bound {
+            None => true,
+            Some((bound_boulder, bound)) => bound_boulder == boulder && index.is_some_and(|index| if lower { index >= bound } else { index <= bound }),
+        });
+        let styles = route_styles.get(&route.id).map_or(&[][..], Vec::as_slice);
+        in_range && (filters.styles.is_empty() || styles.iter().any(|style| filters.styles.contains(style)))
+    };
+
+    let mut found = Vec::new();
+    for text_match in matches {
+        let hit = match text_match.kind {
+            MatchKind::Route => {
+                let Some((route, grade)) = logbook.routes.iter().find(|(route, _)| route.id == text_match.id) else { continue };
+                // With send filters set, a route only counts if it was climbed that way
+                let climbed = !filters.filters_sends() || logbook.sends.iter().any(|send| send.route == route.id && filters.send_passes(send));
+                if !route_passes(route, grade) || !climbed {
+                    continue;
+                }
+                SearchHit { kind: MatchKind::Route, route: route.id, send: None, session: None, date: None, title: route.name.clone(), snippet: text_match.snippet.clone() }
+            }
+            MatchKind::Send => {
+                let Some(send) = logbook.sends.iter().find(|send| send.id == text_match.id) else { continue };
+                let Some((route, grade)) = logbook.routes.iter().find(|(route, _)| route.id == send.route) else { continue };
+                if !route_passes(route, grade) || !filters.send_passes(send) {
+                    continue;
+                }
+                SearchHit {
+                    kind: MatchKind::Send,
+                    route: route.id,
+                    send: Some(send.id),
+                    session: Some(send.session),
+                    date: Some(send.date.clone()),
+                    title: format!("{}: {}", send.date, route.name),
+                    snippet: text_match.snippet.clone(),
+                }
+            }
+        };
+        found.push(hit);
+    }
+    Ok(found)
+}
+
+/// Searches a climber's logbook the way the Search page does.
+///
+/// # Errors
+///
+/// Fails with [`EntryError::Invalid`] when a grade bound isn't a grade, and [`EntryError::Db`] when the store fails.
+pub async fn search(db: impl LogbookStore, text: &str, filters: &SearchFilters, climber: i32, settings: &Settings) -> Result<Vec<SearchHit>, EntryError> {
+    let matches = db.clone().search_text(text).await?;
+    let data = LogbookData::load(db).await?.for_climber(climber);
+    hits(&matches, &data.logbook(settings), &data.route_styles, filters)
+}
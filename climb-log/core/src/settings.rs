@@ -0,0 +1,305 @@
+use std::str::FromStr;
+use itertools::Itertools;
+use sea_orm::DbErr;
+use crate::climbing::{Grade, SendType};
+use crate::conversion::{self, GradeSystem, LengthUnit};
+use crate::store::LogbookStore;
+
+/// Confidence levels offered on the settings page, with their two-sided z scores.
+pub const CONFIDENCE_LEVELS: [(f32, f64); 3] = [(0.90, 1.645), (0.95, 1.960), (0.99, 2.576)];
+
+/// Definition of a grade metric like flash or redpoint grade.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeMetric {
+    /// Success rate needed at a grade for it to count, 0.0 to 1.0.
+    pub threshold: f64,
+    /// Minimum number of logged sends at a grade before it can count.
+    pub min_sample: i32,
+    /// Only use sends from the last n days, 0 for all time.
+    pub window_days: i32,
+    /// Send types that count as a success.
+    pub send_types: Vec<SendType>,
+    /// Compare the lower end of the confidence interval to the threshold instead of the raw rate.
+    pub conservative: bool,
+}
+impl GradeMetric {
+    /// The flash grade by default: flashes and onsights, 80% of at least 5 sends at the grade.
+    pub fn flash() -> GradeMetric {
+        GradeMetric {
+            threshold: 0.8,
+            min_sample: 5,
+            window_days: 0,
+            send_types: vec![SendType::Flash, SendType::Onsight],
+            conservative: false,
+        }
+    }
+
+    /// The redpoint grade by default: any clean send, 80% of at least 5 sends at the grade.
+    pub fn redpoint() -> GradeMetric {
+        GradeMetric {
+            threshold: 0.8,
+            min_sample: 5,
+            window_days: 0,
+            send_types: vec![SendType::Redpoint, SendType::Flash, SendType::Onsight, SendType::Pinkpoint],
+            conservative: false,
+        }
+    }
+
+    fn to_pairs(&self, prefix: &str) -> Vec<(String, String)> {
+        vec![
+            (format!("{prefix}.threshold"), self.threshold.to_string()),
+            (format!("{prefix}.min_sample"), self.min_sample.to_string()),
+            (format!("{prefix}.window_days"), self.window_days.to_string()),
+            (format!("{prefix}.send_types"), self.send_types.iter().map(std::string::ToString::to_string).join(", ")),
+            (format!("{prefix}.conservative"), self.conservative.to_string()),
+        ]
+    }
+
+    fn apply(&mut self, field: &str, value: &str) { // Unknown fields and bad values keep the current value
+        match field {
+            "threshold" => if let Ok(threshold) = value.parse::<f64>() { self.threshold = threshold.clamp(0.0, 1.0) },
+            "min_sample" => if let Ok(min_sample) = value.parse::<i32>() { self.min_sample = min_sample.max(1) },
+            "window_days" => if let Ok(window_days) = value.parse::<i32>() { self.window_days = window_days.max(0) },
+            "send_types" => self.send_types = value.split(',').filter_map(|send_type| SendType::from_str(send_type).ok()).collect(),
+            "conservative" => if let Ok(conservative) = value.parse::<bool>() { self.conservative = conservative },
+            _ => {}
+        }
+    }
+}
+
+/// Which grade of a slash grade or range ("5.10c/d") the stats count.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum RangeGrade {
+    /// Middle of the range, a middle between two grades rounds up to the harder one.
+    #[default]
+    Midpoint,
+    /// Bottom of the range.
+    Lower,
+}
+impl std::fmt::Display for RangeGrade {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            RangeGrade::Midpoint => write!(f, "Midpoint"),
+            RangeGrade::Lower => write!(f, "Lower Bound"),
+        }
+    }
+}
+impl FromStr for RangeGrade {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<RangeGrade, String> {
+        RangeGrade::iter().find(|found| found.to_string() == value.trim()).ok_or_else(|| format!("Unknown range grade: {value}"))
+    }
+}
+impl RangeGrade {
+    /// Every option, in the order the settings page lists them.
+    pub fn iter() -> impl Iterator<Item = RangeGrade> {
+        [RangeGrade::Midpoint, RangeGrade::Lower].iter().copied()
+    }
+
+    /// Grade index to use for a range between two grade indexes.
+    pub fn pick(self, low: i32, high: i32) -> i32 {
+        match self {
+            RangeGrade::Midpoint => {
+                let (low, high) = (low.min(high), low.max(high));
+                low + (high - low + 1).div_euclid(2) // Rounding down would make 5.10c/d the same as Lower
+            }
+            RangeGrade::Lower => low.min(high),
+        }
+    }
+}
+
+/// The climber's preferences, like grading systems and units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings { // User preferences, stored in the settings table as key/value pairs
+    /// How the flash grade is calculated.
+    pub flash: GradeMetric,
+    /// How the redpoint grade is calculated.
+    pub redpoint: GradeMetric,
+    /// Confidence level used for the intervals shown with grade metrics.
+    pub confidence: f32,
+    /// How slash grades and ranges count in the stats.
+    pub range_grade: RangeGrade,
+    /// Count sends at the grade the climber felt instead of the route's grade.
+    pub personal_grades: bool,
+    /// Grading system routes are shown and picked in.
+    pub rope_system: GradeSystem,
+    /// Grading system boulders are shown and picked in.
+    pub boulder_system: GradeSystem,
+    /// Unit lengths are shown in, and assumed when typed without one.
+    pub units: LengthUnit,
+    /// Climber whose sends are logged and shown, when several share the logbook.
+    pub climber: i32,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            flash: GradeMetric::flash(),
+            redpoint: GradeMetric::redpoint(),
+            confidence: 0.95,
+            range_grade: RangeGrade::default(),
+            personal_grades: false,
+            rope_system: GradeSystem::Yosemite,
+            boulder_system: GradeSystem::Hueco,
+            units: LengthUnit::default(),
+            climber: 1,
+        }
+    }
+}
+impl Settings {
+    /// Reads the settings, using defaults for anything not set yet.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the settings table can't be read.
+    pub async fn load(db: impl LogbookStore) -> Result<Settings, DbErr> {
+        let pairs = db.get_all_settings().await?;
+        Ok(Settings::from_pairs(pairs.into_iter().map(|setting| (setting.key, setting.value))))
+    }
+
+    /// Writes every setting back to the logbook.
+    ///
+    /// # Errors
+    ///
+    /// Fails when a setting can't be written, leaving the ones before it written.
+    pub async fn save(&self, db: impl LogbookStore) -> Result<(), DbErr> {
+        for (key, value) in self.to_pairs() {
+            db.clone().set_setting(&key, value).await?;
+        }
+        Ok(())
+    }
+
+    /// Settings from key/value pairs, defaults for keys that are missing or values that don't parse.
+    pub fn from_pairs(pairs: impl IntoIterator<Item = (String, String)>) -> Settings {
+        let mut settings = Settings::default();
+        for (key, value) in pairs {
+            match key.split_once('.') {
+                Some(("flash", field)) => settings.flash.apply(field, &value),
+                Some(("redpoint", field)) => settings.redpoint.apply(field, &value),
+                _ if key == "confidence" => {
+                    if let Some((level, _)) = CONFIDENCE_LEVELS.iter().find(|(level, _)| level.to_string() == value) {
+                        settings.confidence = *level;
+                    }
+                }
+                _ if key == "range_grade" => {
+                    if let Ok(range_grade) = RangeGrade::from_str(&value) {
+                        settings.range_grade = range_grade;
+                    }
+                }
+                _ if key == "rope_system" => {
+                    if let Ok(system) = GradeSystem::from_str(&value) {
+                        settings.rope_system = system;
+                    }
+                }
+                _ if key == "boulder_system" => {
+                    if let Ok(system) = GradeSystem::from_str(&value) {
+                        settings.boulder_system = system;
+                    }
+                }
+                _ if key == "units" => {
+                    if let Ok(units) = LengthUnit::from_str(&value) {
+                        settings.units = units;
+                    }
+                }
+                _ if key == "climber" => {
+                    if let Ok(climber) = value.parse::<i32>() {
+                        settings.climber = climber;
+                    }
+                }
+                _ if key == "personal_grades" => {
+                    if let Ok(personal_grades) = value.parse::<bool>() {
+                        settings.personal_grades = personal_grades;
+                    }
+                }
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    /// Every setting as the key/value pairs it's stored as.
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = self.flash.to_pairs("flash");
+        pairs.extend(self.redpoint.to_pairs("redpoint"));
+        pairs.push(("confidence".to_string(), self.confidence.to_string()));
+        pairs.push(("range_grade".to_string(), self.range_grade.to_string()));
+        pairs.push(("personal_grades".to_string(), self.personal_grades.to_string()));
+        pairs.push(("rope_system".to_string(), self.rope_system.to_string()));
+        pairs.push(("boulder_system".to_string(), self.boulder_system.to_string()));
+        pairs.push(("units".to_string(), self.units.to_string()));
+        pairs.push(("climber".to_string(), self.climber.to_string()));
+        pairs
+    }
+
+    /// The grading system a discipline is shown and typed in.
+    pub fn grade_system(&self, boulder: bool) -> GradeSystem {
+        if boulder { self.boulder_system } else { self.rope_system }
+    }
+
+    /// Stored grade index of a typed grade, in the preferred system or any other for the discipline.
+    pub fn grade_index(&self, text: &str, boulder: bool) -> Option<i32> {
+        let stored = if boulder { GradeSystem::Hueco } else { GradeSystem::Yosemite };
+        let typed = conversion::parse_grade(text, boulder, self.grade_system(boulder))?;
+        match conversion::convert(typed, stored)? {
+            Grade::Yosemite(grade) => Some(grade as i32),
+            Grade::Hueco(grade) => Some(grade as i32 - 1),
+            _ => None,
+        }
+    }
+
+    /// Whether a grade typed for either discipline is a boulder grade, and its stored index.
+    /// The preferred systems are tried first, routes before boulders, then routes and boulders in any system.
+    pub fn any_grade_index(&self, text: &str) -> Option<(bool, i32)> {
+        let text = text.trim();
+        let preferred = [false, true].into_iter().find(|&boulder| self.grade_system(boulder).parse_range(text).is_ok());
+        preferred.into_iter().chain([false, true]).find_map(|boulder| self.grade_index(text, boulder).map(|index| (boulder, index)))
+    }
+
+    /// Stored grade index in the preferred system, e.g. 5.10a shown as 6a.
+    pub fn grade_name(&self, index: i32, boulder: bool) -> String {
+        conversion::index_name(index, boulder, self.grade_system(boulder))
+    }
+
+    /// Stored length in the preferred unit.
+    pub fn length(&self, cm: i32) -> String {
+        self.units.format(cm)
+    }
+
+    /// Z score for the chosen confidence level, falls back to 95%.
+    pub fn z_score(&self) -> f64 {
+        CONFIDENCE_LEVELS.iter()
+            .find(|(level, _)| (level - self.confidence).abs() < f32::EPSILON)
+            .map_or(1.960, |(_, z)| *z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_grades_differ_on_adjacent_grades() {
+        // 5.10c/d, two grades apart by one step
+        assert_eq!(RangeGrade::Midpoint.pick(16, 17), 17);
+        assert_eq!(RangeGrade::Lower.pick(16, 17), 16);
+        // 5.10a-c has a whole grade in the middle
+        assert_eq!(RangeGrade::Midpoint.pick(14, 16), 15);
+        assert_eq!(RangeGrade::Lower.pick(14, 16), 14);
+        // Single grades and ends the wrong way round
+        assert_eq!(RangeGrade::Midpoint.pick(15, 15), 15);
+        assert_eq!(RangeGrade::Lower.pick(15, 15), 15);
+        assert_eq!(RangeGrade::Midpoint.pick(17, 16), 17);
+        assert_eq!(RangeGrade::Lower.pick(17, 16), 16);
+    }
+
+    #[test]
+    fn range_grade_setting_round_trips() {
+        for range_grade in RangeGrade::iter() {
+            assert_eq!(RangeGrade::from_str(&range_grade.to_string()), Ok(range_grade));
+        }
+        let settings = Settings { range_grade: RangeGrade::Lower, ..Settings::default() };
+        let pairs = settings.to_pairs();
+        assert_eq!(Settings::from_pairs(pairs).range_grade, RangeGrade::Lower);
+    }
+}
+
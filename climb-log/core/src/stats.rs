@@ -0,0 +1,524 @@
+use std::{collections::{BTreeMap, BTreeSet, HashMap}, str::FromStr};
+use chrono::{Datelike, NaiveDate};
+use crate::climbing::{Danger, SendType};
+use crate::routes_db::entities::{grades::Model as GradeModel, routes::Model as RouteModel, send_partners::Model as SendPartnerModel, sends::Model as SendModel};
+use crate::settings::GradeMetric;
+
+/// Result of a grade metric at a single grade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradeEstimate {
+    /// Grade as an index into the grade system, converted back by the caller.
+    pub grade: i32,
+    /// Sends that counted as a success.
+    pub successes: i32,
+    /// All sends counted at this grade.
+    pub total: i32,
+    /// Lower end of the confidence interval for the success rate.
+    pub low: f64,
+    /// Upper end of the confidence interval for the success rate.
+    pub high: f64,
+}
+impl GradeEstimate {
+    /// Share of the sends at the grade that were successes, 0 with no sends.
+    pub fn rate(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        f64::from(self.successes) / f64::from(self.total)
+    }
+}
+
+/// Wilson score interval for a binomial proportion, behaves well for small samples and rates near 0 or 1.
+pub fn wilson_interval(successes: i32, total: i32, z: f64) -> (f64, f64) {
+    if total <= 0 {
+        return (0.0, 1.0);
+    }
+    let n = f64::from(total);
+    let p = f64::from(successes) / n;
+    let z2 = z * z;
+    let centre = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let margin = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    ((centre - margin).max(0.0), (centre + margin).min(1.0))
+}
+
+/// Whether a send's date falls inside the last `window_days` days, 0 means no window.
+pub fn in_window(date: &str, window_days: i32, today: NaiveDate) -> bool {
+    if window_days <= 0 {
+        return true;
+    }
+    NaiveDate::from_str(date).is_ok_and(|date| (today - date).num_days() < i64::from(window_days))
+}
+
+/// Hardest grade whose success rate meets the metric's threshold with enough sends to go on.
+pub fn grade_metric<'a>(samples: impl IntoIterator<Item = (i32, &'a SendModel)>, metric: &GradeMetric, z: f64, today: NaiveDate) -> Option<GradeEstimate> {
+    // Repeats say nothing about how hard a grade is for you, so they are left out unless they count as a success
+    let mut counts: BTreeMap<i32, (i32, i32)> = BTreeMap::new(); // grade -> (successes, total)
+    for (grade, send) in samples {
+        if !in_window(&send.date, metric.window_days, today) {
+            continue;
+        }
+        let send_type = SendType::from_str(&send.r#type).unwrap_or_default();
+        let success = metric.send_types.contains(&send_type);
+        if send_type == SendType::Repeat && !success {
+            continue;
+        }
+        let count = counts.entry(grade).or_insert((0, 0));
+        count.1 += 1;
+        if success {
+            count.0 += 1;
+        }
+    }
+
+    counts.into_iter().rev()
+        .filter(|(_, (_, total))| *total >= metric.min_sample)
+        .map(|(grade, (successes, total))| {
+            let (low, high) = wilson_interval(successes, total, z);
+            GradeEstimate { grade, successes, total, low, high }
+        })
+        .find(|estimate| {
+            let rate = if metric.conservative { estimate.low } else { estimate.rate() };
+            rate >= metric.threshold
+        })
+}
+
+/// The grade metric as it stood on each day with logged sends, using only the sends up to that day.
+pub fn grade_metric_history(samples: &[(i32, &SendModel)], metric: &GradeMetric, z: f64) -> Vec<(NaiveDate, i32)> {
+    let dates: BTreeSet<NaiveDate> = samples.iter().filter_map(|(_, send)| NaiveDate::from_str(&send.date).ok()).collect();
+    dates.into_iter().filter_map(|date| {
+        let so_far = samples.iter()
+            .filter(|(_, send)| NaiveDate::from_str(&send.date).is_ok_and(|sent| sent <= date))
+            .copied();
+        grade_metric(so_far, metric, z, date).map(|estimate| (date, estimate.grade))
+    }).collect()
+}
+
+/// Rounds a continuous value (rating, chart position) back to a grade index or day number.
+#[allow(clippy::cast_possible_truncation)] // Clamped to the i32 range first
+pub fn round_to_i32(value: f64) -> i32 {
+    value.round().clamp(f64::from(i32::MIN), f64::from(i32::MAX)) as i32
+}
+
+/// Everything climbed with one partner.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartnerProfile {
+    /// Distinct days climbed together.
+    pub days: usize,
+    /// Sessions climbed together.
+    pub sessions: usize,
+    /// Date of the first session together.
+    pub first: Option<String>,
+    /// Date of the latest session together.
+    pub last: Option<String>,
+    /// Names of routes climbed together, alphabetical.
+    pub routes: Vec<String>,
+    /// Crags visited together and the days spent at each, most visited first.
+    pub crags: Vec<(String, usize)>,
+}
+
+/// Days, sessions, routes and crags shared with a partner, from the sends they were on.
+pub fn partner_profile(partner: i32, sends: &[SendModel], routes: &[(RouteModel, GradeModel)], send_partners: &[SendPartnerModel]) -> PartnerProfile {
+    let together: BTreeSet<i32> = send_partners.iter().filter(|link| link.partner == partner).map(|link| link.send).collect();
+    let shared: Vec<&SendModel> = sends.iter().filter(|send| together.contains(&send.id)).collect();
+
+    let dates: BTreeSet<&str> = shared.iter().map(|send| send.date.as_str()).collect();
+    let sessions: BTreeSet<i32> = shared.iter().map(|send| send.session).collect();
+    let mut route_names: BTreeSet<String> = BTreeSet::new();
+    let mut crag_days: BTreeSet<(String, &str)> = BTreeSet::new();
+    for send in &shared {
+        if let Some((route, _)) = routes.iter().find(|(route, _)| route.id == send.route) {
+            route_names.insert(route.name.clone());
+            if let Some(location) = route.location.clone().filter(|location| !location.is_empty()) {
+                crag_days.insert((location, send.date.as_str()));
+            }
+        }
+    }
+    let mut crags: HashMap<String, usize> = HashMap::new();
+    for (crag, _) in crag_days {
+        *crags.entry(crag).or_insert(0) += 1;
+    }
+    let mut crags: Vec<(String, usize)> = crags.into_iter().collect();
+    crags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    PartnerProfile {
+        days: dates.len(),
+        sessions: sessions.len(),
+        first: dates.first().map(ToString::to_string),
+        last: dates.last().map(ToString::to_string),
+        routes: route_names.into_iter().collect(),
+        crags,
+    }
+}
+
+/// The `count` hardest completed sends, ties go to the earliest one.
+pub fn hardest_sends<'a>(samples: &[(i32, &'a SendModel)], count: usize) -> Vec<(i32, &'a SendModel)> {
+    let mut sends: Vec<(i32, &SendModel)> = samples.iter()
+        .filter(|(_, send)| SendType::from_str(&send.r#type).is_ok_and(SendType::is_send))
+        .copied()
+        .collect();
+    sends.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.date.cmp(&b.1.date)));
+    sends.truncate(count);
+    sends
+}
+
+/// Hardest clean lead on a route rated at least this dangerous, ties go to the earliest one.
+pub fn hardest_lead_at_danger<'a>(samples: &[(i32, &'a SendModel)], routes: &[(RouteModel, GradeModel)], danger: Danger) -> Option<(i32, &'a SendModel)> {
+    samples.iter()
+        .filter(|(_, send)| SendType::from_str(&send.r#type).is_ok_and(SendType::is_lead))
+        .filter(|(_, send)| routes.iter()
+            .find(|(route, _)| route.id == send.route)
+            .and_then(|(route, _)| Danger::from_str(route.danger.as_deref()?).ok())
+            .is_some_and(|rating| rating >= danger))
+        .copied()
+        .min_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.date.cmp(&b.1.date)))
+}
+
+/// Completed sends per grade, hardest grade first.
+pub fn pyramid(samples: &[(i32, &SendModel)]) -> Vec<(i32, usize)> {
+    let mut counts: BTreeMap<i32, usize> = BTreeMap::new();
+    for (grade, send) in samples {
+        if SendType::from_str(&send.r#type).is_ok_and(SendType::is_send) {
+            *counts.entry(*grade).or_default() += 1;
+        }
+    }
+    counts.into_iter().rev().collect()
+}
+
+/// How far off the climber thinks a route's grade is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeDisagreement {
+    /// Id of the route.
+    pub route: i32,
+    /// Grade index the route is given.
+    pub consensus: i32,
+    /// Average grade index the climber gave it over their sends.
+    pub personal: f64,
+    /// Sends with an opinion on the grade.
+    pub votes: usize,
+}
+impl GradeDisagreement {
+    /// Personal grade less the route's, positive for sandbagged and negative for soft.
+    pub fn difference(&self) -> f64 {
+        self.personal - f64::from(self.consensus)
+    }
+}
+
+/// Routes where the climber's grades differ from the route's, most sandbagged first.
+///
+/// Takes (consensus, personal, send) for each send with an opinion.
+pub fn grade_disagreements(opinions: &[(i32, i32, &SendModel)]) -> Vec<GradeDisagreement> {
+    let mut routes: BTreeMap<i32, (i32, Vec<i32>)> = BTreeMap::new();
+    for (consensus, personal, send) in opinions {
+        routes.entry(send.route).or_insert_with(|| (*consensus, Vec::new())).1.push(*personal);
+    }
+    let mut disagreements: Vec<GradeDisagreement> = routes.into_iter()
+        .map(|(route, (consensus, personal))| GradeDisagreement {
+            route,
+            consensus,
+            personal: f64::from(personal.iter().sum::<i32>()) / f64::from(u32::try_from(personal.len()).unwrap_or(u32::MAX)),
+            votes: personal.len(),
+        })
+        .filter(|disagreement| disagreement.difference().abs() > f64::EPSILON)
+        .collect();
+    disagreements.sort_by(|a, b| b.difference().total_cmp(&a.difference()));
+    disagreements
+}
+
+/// Everything logged at one crag.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CragSummary {
+    /// Crag name, as written on its routes.
+    pub name: String,
+    /// Logged sends and attempts at the crag.
+    pub sends: usize,
+    /// Dates of each day at the crag, oldest first.
+    pub visits: Vec<String>,
+    /// (Yosemite index, route id) of the hardest tall wall send.
+    pub hardest_tall: Option<(i32, i32)>,
+    /// (Hueco index, route id) of the hardest boulder send.
+    pub hardest_boulder: Option<(i32, i32)>,
+    /// Routes in the database at the crag.
+    pub routes: usize,
+    /// Routes at the crag with at least one completed send.
+    pub ticked: usize,
+}
+impl CragSummary {
+    /// Share of the crag's routes with a completed send, 0 with no routes.
+    pub fn tick_rate(&self) -> f64 {
+        if self.routes == 0 {
+            return 0.0;
+        }
+        f64::from(u32::try_from(self.ticked).unwrap_or(u32::MAX)) / f64::from(u32::try_from(self.routes).unwrap_or(u32::MAX))
+    }
+}
+
+fn crag_of(route: i32, routes: &[(RouteModel, GradeModel)]) -> Option<String> {
+    routes.iter().find(|(found, _)| found.id == route)
+        .and_then(|(found, _)| found.location.clone())
+        .map(|location| location.trim().to_string())
+        .filter(|location| !location.is_empty())
+}
+
+/// One summary per crag from sends on or after `since`, most visited crag first.
+pub fn crag_summaries(tall: &[(i32, &SendModel)], boulder: &[(i32, &SendModel)], routes: &[(RouteModel, GradeModel)], since: Option<NaiveDate>) -> Vec<CragSummary> {
+    let mut crags: BTreeMap<String, CragSummary> = BTreeMap::new();
+    for (route, _) in routes {
+        if let Some(crag) = crag_of(route.id, routes) {
+            crags.entry(crag.clone()).or_insert_with(|| CragSummary { name: crag, ..CragSummary::default() }).routes += 1;
+        }
+    }
+
+    let mut visits: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut ticked: BTreeMap<String, BTreeSet<i32>> = BTreeMap::new();
+    for (boulders, samples) in [(false, tall), (true, boulder)] {
+        for (grade, send) in samples {
+            if since.is_some_and(|since| NaiveDate::from_str(&send.date).map_or(true, |date| date < since)) {
+                continue;
+            }
+            let Some(crag) = crag_of(send.route, routes) else { continue };
+            let Some(summary) = crags.get_mut(&crag) else { continue };
+            summary.sends += 1;
+            visits.entry(crag.clone()).or_default().insert(send.date.clone());
+            if !SendType::from_str(&send.r#type).is_ok_and(SendType::is_send) {
+                continue;
+            }
+            ticked.entry(crag).or_default().insert(send.route);
+            let hardest = if boulders { &mut summary.hardest_boulder } else { &mut summary.hardest_tall };
+            if hardest.is_none_or(|(best, _)| *grade > best) {
+                *hardest = Some((*grade, send.route));
+            }
+        }
+    }
+
+    let mut summaries: Vec<CragSummary> = crags.into_values().map(|mut summary| {
+        summary.visits = visits.remove(&summary.name).unwrap_or_default().into_iter().collect();
+        summary.ticked = ticked.get(&summary.name).map_or(0, BTreeSet::len);
+        summary
+    }).collect();
+    summaries.sort_by(|a, b| b.visits.len().cmp(&a.visits.len()).then_with(|| b.sends.cmp(&a.sends)).then_with(|| a.name.cmp(&b.name)));
+    summaries
+}
+
+/// The crag with the most days climbed for each year, ties go to the alphabetically first crag.
+pub fn favourite_crag_by_year(sends: &[SendModel], routes: &[(RouteModel, GradeModel)]) -> Vec<(i32, String, usize)> {
+    let mut days: BTreeMap<i32, BTreeMap<String, BTreeSet<&str>>> = BTreeMap::new(); // year -> crag -> dates
+    for send in sends {
+        let Ok(date) = NaiveDate::from_str(&send.date) else { continue };
+        let Some(crag) = crag_of(send.route, routes) else { continue };
+        days.entry(date.year()).or_default().entry(crag).or_default().insert(send.date.as_str());
+    }
+    days.into_iter().filter_map(|(year, crags)| {
+        crags.into_iter()
+            .map(|(crag, dates)| (crag, dates.len()))
+            .fold(None, |best: Option<(String, usize)>, (crag, count)| match best {
+                Some((_, most)) if most >= count => best,
+                _ => Some((crag, count)),
+            })
+            .map(|(crag, count)| (year, crag, count))
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{route, send};
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, 1).unwrap()
+    }
+
+    #[test]
+    fn wilson_interval_at_the_edges() {
+        let (low, high) = wilson_interval(0, 10, 1.96);
+        assert!(low.abs() < 1e-9, "no successes starts at 0, got {low}");
+        assert!(high > 0.0 && high < 0.5, "no successes still leaves room above 0, got {high}");
+
+        let (low, high) = wilson_interval(10, 10, 1.96);
+        assert!(low > 0.5 && low < 1.0, "all successes still leaves room below 1, got {low}");
+        assert!((high - 1.0).abs() < 1e-9, "all successes ends at 1, got {high}");
+
+        assert_eq!(wilson_interval(0, 0, 1.96), (0.0, 1.0));
+        assert_eq!(wilson_interval(3, -1, 1.96), (0.0, 1.0));
+    }
+
+    #[test]
+    fn wilson_interval_narrows_with_more_sends() {
+        let (small_low, small_high) = wilson_interval(4, 5, 1.96);
+        let (large_low, large_high) = wilson_interval(80, 100, 1.96);
+        assert!(small_low < 0.8 && 0.8 < small_high);
+        assert!(large_low < 0.8 && 0.8 < large_high);
+        assert!(large_high - large_low < small_high - small_low);
+    }
+
+    #[test]
+    fn grade_metric_finds_the_hardest_grade_meeting_the_threshold() {
+        // Grade 10: 5 flashes, grade 11: 4 flashes of 5, grade 12: 2 flashes of 5, grade 13: 1 flash
+        let mut sends = Vec::new();
+        for (grade, flashes, total) in [(10, 5, 5), (11, 4, 5), (12, 2, 5), (13, 1, 1)] {
+            for n in 0..total {
+                let send_type = if n < flashes { SendType::Flash } else { SendType::Redpoint };
+                sends.push((grade, send(grade * 10 + n, grade, "2024-05-01", send_type)));
+            }
+        }
+        let samples = || sends.iter().map(|(grade, send)| (*grade, send));
+
+        let estimate = grade_metric(samples(), &GradeMetric::flash(), 1.96, today()).unwrap();
+        assert_eq!((estimate.grade, estimate.successes, estimate.total), (11, 4, 5));
+        assert!((estimate.rate() - 0.8).abs() < 1e-9);
+
+        let estimate = grade_metric(samples(), &GradeMetric::redpoint(), 1.96, today()).unwrap();
+        assert_eq!(estimate.grade, 12, "grade 13 has too few sends to count");
+
+        let two_in_five = GradeMetric { threshold: 0.4, ..GradeMetric::flash() };
+        assert_eq!(grade_metric(samples(), &two_in_five, 1.96, today()).unwrap().grade, 12, "a rate exactly at the threshold counts");
+
+        let conservative = GradeMetric { conservative: true, ..GradeMetric::flash() };
+        assert_eq!(grade_metric(samples(), &conservative, 1.96, today()), None, "five sends never clear 80% at the low end");
+    }
+
+    #[test]
+    fn grade_metric_window_and_repeats() {
+        let old: Vec<(i32, SendModel)> = (0..5).map(|n| (12, send(n, 12, "2020-01-01", SendType::Flash))).collect();
+        let repeats: Vec<(i32, SendModel)> = (5..10).map(|n| (11, send(n, 11, "2024-05-01", SendType::Repeat))).collect();
+        let samples = || old.iter().chain(&repeats).map(|(grade, send)| (*grade, send));
+
+        assert_eq!(grade_metric(samples(), &GradeMetric::flash(), 1.96, today()).map(|estimate| estimate.grade), Some(12));
+        let recent = GradeMetric { window_days: 365, ..GradeMetric::flash() };
+        assert_eq!(grade_metric(samples(), &recent, 1.96, today()), None, "old sends are out of the window and repeats don't count");
+        let with_repeats = GradeMetric { window_days: 365, send_types: vec![SendType::Repeat], ..GradeMetric::flash() };
+        assert_eq!(grade_metric(samples(), &with_repeats, 1.96, today()).map(|estimate| estimate.grade), Some(11));
+    }
+
+    #[test]
+    fn partner_profile_counts_only_shared_sends() {
+        let routes = [route(1, "Arete", Some("Smith Rock")), route(2, "Crack", Some("Smith Rock")), route(3, "Slab", Some("The Gunks")), route(4, "Roof", None)];
+        let mut sends = vec![
+            send(1, 1, "2024-05-01", SendType::Flash),
+            send(2, 2, "2024-05-01", SendType::Redpoint),
+            send(3, 3, "2024-05-04", SendType::Onsight),
+            send(4, 1, "2024-05-08", SendType::Repeat),
+            send(5, 4, "2024-05-09", SendType::Flash),
+            send(6, 3, "2024-05-10", SendType::Attempt),
+        ];
+        sends[1].session = 1;
+        let link = |id: i32, send: i32, partner: i32| SendPartnerModel { id, send, partner };
+        let send_partners = [link(1, 1, 7), link(2, 2, 7), link(3, 3, 7), link(4, 3, 8), link(5, 4, 7), link(6, 5, 7), link(7, 6, 8)];
+
+        let profile = partner_profile(7, &sends, &routes, &send_partners);
+        assert_eq!(profile.days, 4);
+        assert_eq!(profile.sessions, 4, "sends 1 and 2 were one session");
+        assert_eq!((profile.first.as_deref(), profile.last.as_deref()), (Some("2024-05-01"), Some("2024-05-09")));
+        assert_eq!(profile.routes, ["Arete", "Crack", "Roof", "Slab"]);
+
+        assert_eq!(profile.crags, [("Smith Rock".to_string(), 2), ("The Gunks".to_string(), 1)], "days per crag, the route without one left out");
+
+        let profile = partner_profile(8, &sends, &routes, &send_partners);
+        assert_eq!((profile.days, profile.routes.len()), (2, 1));
+        assert_eq!(partner_profile(9, &sends, &routes, &send_partners), PartnerProfile::default());
+    }
+
+    #[test]
+    fn crag_summaries_per_crag() {
+        let routes = [
+            route(1, "Arete", Some("Smith Rock")),
+            route(2, "Crack", Some("Smith Rock ")),
+            route(3, "Slab", Some("The Gunks")),
+            route(4, "Roof", None),
+            route(5, "Problem", Some("Smith Rock")),
+        ];
+        let sends = [
+            send(1, 1, "2024-05-01", SendType::Flash),
+            send(2, 2, "2024-05-01", SendType::Attempt),
+            send(3, 3, "2024-05-04", SendType::Onsight),
+            send(4, 4, "2024-05-05", SendType::Flash),
+            send(5, 5, "2024-05-06", SendType::Flash),
+        ];
+        let tall = [(10, &sends[0]), (12, &sends[1]), (11, &sends[2]), (13, &sends[3])];
+        let boulder = [(4, &sends[4])];
+
+        let summaries = crag_summaries(&tall, &boulder, &routes, None);
+        let names: Vec<&str> = summaries.iter().map(|summary| summary.name.as_str()).collect();
+        assert_eq!(names, ["Smith Rock", "The Gunks"], "the route without a crag is left out, most visited first");
+        let smith = &summaries[0];
+        assert_eq!((smith.sends, smith.visits.clone()), (3, vec!["2024-05-01".to_string(), "2024-05-06".to_string()]));
+        assert_eq!((smith.hardest_tall, smith.hardest_boulder), (Some((10, 1)), Some((4, 5))), "the attempt at 12 is not a send");
+        assert_eq!((smith.routes, smith.ticked), (3, 2));
+        assert!((smith.tick_rate() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!((summaries[1].hardest_tall, summaries[1].ticked), (Some((11, 3)), 1));
+
+        let since = crag_summaries(&tall, &boulder, &routes, NaiveDate::from_ymd_opt(2024, 5, 2));
+        assert_eq!((since[0].name.as_str(), since[0].sends, since[0].hardest_tall), ("Smith Rock", 1, None));
+        assert_eq!(since[0].routes, 3, "routes at the crag count whatever the date");
+        assert_eq!(since[1].visits, ["2024-05-04"]);
+    }
+
+    #[test]
+    fn favourite_crag_each_year() {
+        let routes = [route(1, "Arete", Some("Smith Rock")), route(2, "Slab", Some("The Gunks"))];
+        let sends = [
+            send(1, 1, "2023-05-01", SendType::Flash),
+            send(2, 1, "2023-05-01", SendType::Repeat),
+            send(3, 2, "2023-06-01", SendType::Flash),
+            send(4, 2, "2024-06-01", SendType::Flash),
+            send(5, 2, "2024-06-02", SendType::Attempt),
+            send(6, 1, "2024-06-03", SendType::Flash),
+        ];
+        assert_eq!(favourite_crag_by_year(&sends, &routes), [(2023, "Smith Rock".to_string(), 1), (2024, "The Gunks".to_string(), 2)]);
+    }
+
+    #[test]
+    fn hardest_lead_at_or_above_a_danger_rating() {
+        let rated = |id: i32, danger: Option<&str>| {
+            let (mut route, grade) = route(id, &format!("Route {id}"), None);
+            route.danger = danger.map(str::to_string);
+            (route, grade)
+        };
+        let routes = [rated(1, Some("PG-13")), rated(2, Some("R")), rated(3, Some("X")), rated(4, None)];
+        let sends = [
+            send(1, 1, "2024-05-01", SendType::Flash),
+            send(2, 2, "2024-05-02", SendType::Redpoint),
+            send(3, 2, "2024-05-01", SendType::Onsight),
+            send(4, 3, "2024-05-03", SendType::Onsight),
+            send(5, 3, "2024-05-04", SendType::FreeSolo),
+            send(6, 2, "2024-05-05", SendType::Top),
+            send(7, 4, "2024-05-06", SendType::Flash),
+        ];
+        let samples = [(15, &sends[0]), (12, &sends[1]), (12, &sends[2]), (11, &sends[3]), (16, &sends[4]), (17, &sends[5]), (20, &sends[6])];
+        let hardest = |danger: Danger| hardest_lead_at_danger(&samples, &routes, danger).map(|(grade, send)| (grade, send.id));
+
+        assert_eq!(hardest(Danger::R), Some((12, 3)), "X counts as R or worse, ties go to the earliest, solos and topropes aren't leads");
+        assert_eq!(hardest(Danger::X), Some((11, 4)));
+        assert_eq!(hardest(Danger::Pg13), Some((15, 1)));
+        assert_eq!(hardest(Danger::G), Some((15, 1)), "unrated routes are left out");
+        assert_eq!(hardest_lead_at_danger(&samples[..1], &routes, Danger::R), None);
+    }
+
+    #[test]
+    fn pyramid_counts_clean_sends_hardest_first() {
+        let sends = [
+            send(1, 1, "2024-05-01", SendType::Redpoint),
+            send(2, 2, "2024-05-01", SendType::Repeat),
+            send(3, 3, "2024-05-02", SendType::Attempt),
+            send(4, 4, "2024-05-02", SendType::Top),
+            send(5, 5, "2024-05-03", SendType::Onsight),
+        ];
+        let samples = [(10, &sends[0]), (10, &sends[1]), (14, &sends[2]), (12, &sends[3]), (11, &sends[4])];
+        assert_eq!(pyramid(&samples), [(11, 1), (10, 2)], "attempts and tops aren't sends, repeats are");
+        assert!(pyramid(&[]).is_empty());
+    }
+
+    #[test]
+    fn grade_disagreements_average_each_route() {
+        let sends = [
+            send(1, 1, "2024-05-01", SendType::Redpoint),
+            send(2, 1, "2024-05-02", SendType::Repeat),
+            send(3, 2, "2024-05-02", SendType::Flash),
+            send(4, 3, "2024-05-03", SendType::Onsight),
+        ];
+        let opinions = [(10, 11, &sends[0]), (10, 12, &sends[1]), (14, 13, &sends[2]), (12, 12, &sends[3])];
+        let disagreements = grade_disagreements(&opinions);
+        assert_eq!(disagreements.iter().map(|found| (found.route, found.votes)).collect::<Vec<_>>(), [(1, 2), (2, 1)], "most sandbagged first, agreeing routes left out");
+        assert!((disagreements[0].personal - 11.5).abs() < 1e-9);
+        assert!((disagreements[0].difference() - 1.5).abs() < 1e-9);
+        assert!((disagreements[1].difference() + 1.0).abs() < 1e-9);
+    }
+}
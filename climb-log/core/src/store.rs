@@ -0,0 +1,816 @@
+use std::{collections::BTreeMap, future::Future, sync::{Arc, Mutex, MutexGuard}};
+use sea_orm::DbErr;
+use crate::climbing::Style;
+use crate::filter::Filter;
+use crate::logbook;
+use crate::search::{self, TextMatch};
+use crate::routes_db::entities::{climbers, grades, partners, route_styles, routes, send_partners, sends, settings, styles};
+
+// Everything the app, command line and server read and write goes through LogbookStore
+// RoutesDb keeps the logbook in SQLite, MemoryStore keeps it in plain vectors for tests and throwaway logbooks
+// Methods take the store by value like RoutesDb always has, stores are cheap handles onto shared data
+
+/// Storage for a logbook: grades, routes, sends and sessions, climbers, partners and settings.
+///
+/// Ids are assigned by the store, so the id on any model passed in is ignored.
+///
+/// # Errors
+///
+/// Every method fails with the store's [`DbErr`] when the database can't be read or written. Lookups of a single
+/// row by id or name fail with [`DbErr::RecordNotFound`] when there's no such row, and removals fail with
+/// [`DbErr::Custom`] while other rows still refer to the one being removed.
+pub trait LogbookStore: Clone + Send + Sync + 'static {
+    // Grades
+    /// Adds a grade with every system's name filled in that it has.
+    fn add_grade(self, grade: grades::Model) -> impl Future<Output = Result<(), DbErr>> + Send;
+    /// Removes a grade, fails while routes are still at it.
+    fn remove_grade(self, id: i32) -> impl Future<Output = Result<(), DbErr>> + Send;
+    /// Id of a grade by its Yosemite or Hueco name.
+    fn get_grade_id(self, grd: &str) -> impl Future<Output = Result<i32, DbErr>> + Send;
+    /// The grade with an id.
+    fn get_grade(self, id: i32) -> impl Future<Output = Result<grades::Model, DbErr>> + Send;
+    /// Every grade, by id.
+    fn get_all_grades(self) -> impl Future<Output = Result<Vec<grades::Model>, DbErr>> + Send;
+
+    // Routes
+    /// Adds a route with styles by name, unknown style names are skipped.
+    fn add_route(self, route: routes::Model, style_names: Vec<String>) -> impl Future<Output = Result<(), DbErr>> + Send;
+    /// Removes a route and its styles, fails while sends are still logged on it.
+    fn remove_route(self, id: i32) -> impl Future<Output = Result<(), DbErr>> + Send;
+    /// The route with a name, none if there isn't one.
+    fn find_route_name(self, name: &str) -> impl Future<Output = Result<Option<routes::Model>, DbErr>> + Send;
+    /// Id of the route with a name.
+    fn get_route_id(self, name: &str) -> impl Future<Output = Result<i32, DbErr>> + Send;
+    /// Names of the routes at a grade id, sorted.
+    fn find_routes_by_grade(self, grade: i32) -> impl Future<Output = Result<Vec<String>, DbErr>> + Send;
+    /// Every route, by id.
+    fn find_all_routes(self) -> impl Future<Output = Result<Vec<routes::Model>, DbErr>> + Send;
+    /// The route with an id.
+    fn find_route_by_id(self, id: i32) -> impl Future<Output = Result<routes::Model, DbErr>> + Send;
+    /// The route with a name, with its grade.
+    fn find_route_and_grade(self, name: &str) -> impl Future<Output = Result<(routes::Model, grades::Model), DbErr>> + Send;
+    /// Every route with its grade, by id.
+    fn find_all_routes_and_grade(self) -> impl Future<Output = Result<Vec<(routes::Model, grades::Model)>, DbErr>> + Send;
+    /// Every route to style link, with the style it points at.
+    fn get_all_route_styles(self) -> impl Future<Output = Result<Vec<(route_styles::Model, Option<styles::Model>)>, DbErr>> + Send;
+
+    // Sends and sessions
+    /// Logs a climber's send of a route in a session, with partners by id.
+    #[allow(clippy::too_many_arguments)]
+    fn add_send(self, climber: i32, session: i32, route: routes::Model, date: String, partners: Vec<i32>, send_type: String, attempts: i32, notes: Option<String>, personal_grade: Option<String>) -> impl Future<Output = Result<(), DbErr>> + Send;
+    /// The sends logged in a session, none for a session that isn't there.
+    fn get_session(self, id: i32) -> impl Future<Output = Result<Vec<sends::Model>, DbErr>> + Send;
+    /// Every send logged on a YYYY-MM-DD date, by any climber.
+    fn get_session_by_date(self, date: String) -> impl Future<Output = Result<Vec<sends::Model>, DbErr>> + Send;
+    /// Every send by every climber, by id.
+    fn get_all_sends(self) -> impl Future<Output = Result<Vec<sends::Model>, DbErr>> + Send;
+    /// Removes every send in a session along with their partners.
+    fn remove_session(self, id: i32) -> impl Future<Output = Result<(), DbErr>> + Send;
+    /// One past the highest session id logged, 1 for an empty logbook.
+    fn get_next_session_id(self) -> impl Future<Output = Result<i32, DbErr>> + Send;
+
+    // Climbers
+    /// Adds a climber by name, or returns the id of the climber already called that.
+    fn add_climber(self, name: &str) -> impl Future<Output = Result<i32, DbErr>> + Send;
+    /// Every climber sharing the logbook, in the order they were added.
+    fn get_all_climbers(self) -> impl Future<Output = Result<Vec<climbers::Model>, DbErr>> + Send;
+
+    // Partners
+    /// Adds a partner by name, or returns the id of the partner already called that.
+    fn add_partner(self, name: &str) -> impl Future<Output = Result<i32, DbErr>> + Send;
+    /// Removes a partner, fails while sends are still logged with them.
+    fn remove_partner(self, id: i32) -> impl Future<Output = Result<(), DbErr>> + Send;
+    /// Every partner, sorted by name.
+    fn get_all_partners(self) -> impl Future<Output = Result<Vec<partners::Model>, DbErr>> + Send;
+    /// Every send to partner link.
+    fn get_all_send_partners(self) -> impl Future<Output = Result<Vec<send_partners::Model>, DbErr>> + Send;
+
+    // Search
+    /// Routes whose name or crag, and sends whose notes or partners, have every word of the text as a prefix.
+    /// Best matches first, routes before sends, nothing for text without any words.
+    fn search_text(self, text: &str) -> impl Future<Output = Result<Vec<TextMatch>, DbErr>> + Send;
+
+    // Filters
+    /// A climber's sends matching every term of the filter, by id.
+    fn filter_sends(self, filter: &Filter, climber: i32) -> impl Future<Output = Result<Vec<sends::Model>, DbErr>> + Send;
+    /// Routes matching every term of the filter, with their grades, by id. Terms on sends need one of the climber's sends to match them all.
+    fn filter_routes(self, filter: &Filter, climber: i32) -> impl Future<Output = Result<Vec<(routes::Model, grades::Model)>, DbErr>> + Send;
+
+    // Settings
+    /// The value of a setting, none if it's never been set.
+    fn get_setting(self, key: &str) -> impl Future<Output = Result<Option<String>, DbErr>> + Send;
+    /// Every setting that's been set.
+    fn get_all_settings(self) -> impl Future<Output = Result<Vec<settings::Model>, DbErr>> + Send;
+    /// Sets a setting, overwriting any value already there.
+    fn set_setting(self, key: &str, value: String) -> impl Future<Output = Result<(), DbErr>> + Send;
+}
+
+#[derive(Debug, Default)]
+struct Tables {
+    grades: Vec<grades::Model>,
+    routes: Vec<routes::Model>,
+    styles: Vec<styles::Model>,
+    route_styles: Vec<route_styles::Model>,
+    sends: Vec<sends::Model>,
+    climbers: Vec<climbers::Model>,
+    partners: Vec<partners::Model>,
+    send_partners: Vec<send_partners::Model>,
+    settings: BTreeMap<String, String>,
+}
+
+impl Tables {
+    fn route_and_grade(&self, id: i32) -> Result<(&routes::Model, &grades::Model), DbErr> {
+        let route = self.routes.iter().find(|route| route.id == id).ok_or_else(|| DbErr::RecordNotFound(format!("Route {id}")))?;
+        let grade = self.grades.iter().find(|grade| grade.id == route.grade_id).ok_or_else(|| DbErr::RecordNotFound(format!("Grade {}", route.grade_id)))?;
+        Ok((route, grade))
+    }
+
+    fn partner_names(&self, send: i32) -> Vec<String> {
+        self.send_partners.iter().filter(|link| link.send == send)
+            .filter_map(|link| self.partners.iter().find(|partner| partner.id == link.partner))
+            .map(|partner| partner.name.clone())
+            .collect()
+    }
+}
+
+fn next_id(ids: impl Iterator<Item = i32>) -> i32 {
+    // Same as SQLite hands out row ids
+    ids.max().unwrap_or(0) + 1
+}
+
+pub(crate) fn in_use(table: &str) -> DbErr {
+    // What SQLite says when a foreign key would be left pointing at nothing
+    DbErr::Custom(format!("FOREIGN KEY constraint failed, {table} still refer to it"))
+}
+
+/// A logbook kept in memory, for tests and anything that shouldn't touch the database.
+///
+/// Styles and the first climber are filled in like a freshly migrated database, grades start out empty the same way.
+#[derive(Debug, Clone)]
+pub struct MemoryStore {
+    tables: Arc<Mutex<Tables>>,
+}
+
+impl Default for MemoryStore {
+    fn default() -> MemoryStore {
+        MemoryStore::new()
+    }
+}
+
+impl MemoryStore {
+    /// An empty logbook with every style and the default climber, like a freshly migrated database.
+    pub fn new() -> MemoryStore {
+        let styles = Style::iter().zip(1..).map(|(style, id)| styles::Model { id, name: style.to_string() }).collect();
+        let climbers = vec![climbers::Model { id: 1, name: "Me".to_string() }];
+        MemoryStore { tables: Arc::new(Mutex::new(Tables { styles, climbers, ..Tables::default() })) }
+    }
+
+    fn tables(&self) -> MutexGuard<'_, Tables> {
+        self.tables.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl LogbookStore for MemoryStore {
+    // Grade Funcs
+    async fn add_grade(self, grade: grades::Model) -> Result<(), DbErr> {
+        let mut tables = self.tables();
+        let id = next_id(tables.grades.iter().map(|grade| grade.id));
+        tables.grades.push(grades::Model { id, ..grade });
+        Ok(())
+    }
+
+    async fn remove_grade(self, id: i32) -> Result<(), DbErr> {
+        let mut tables = self.tables();
+        if tables.routes.iter().any(|route| route.grade_id == id) {
+            return Err(in_use("routes"));
+        }
+        tables.grades.retain(|grade| grade.id != id);
+        Ok(())
+    }
+
+    async fn get_grade_id(self, grd: &str) -> Result<i32, DbErr> {
+        let tables = self.tables();
+        tables.grades.iter().find(|grade| grade.yosemite.as_deref() == Some(grd))
+            .or_else(|| tables.grades.iter().find(|grade| grade.hueco.as_deref() == Some(grd)))
+            .map(|grade| grade.id)
+            .ok_or_else(|| DbErr::RecordNotFound(format!("Grade {grd}")))
+    }
+
+    async fn get_grade(self, id: i32) -> Result<grades::Model, DbErr> {
+        self.tables().grades.iter().find(|grade| grade.id == id).cloned().ok_or_else(|| DbErr::RecordNotFound(format!("Grade {id}")))
+    }
+
+    async fn get_all_grades(self) -> Result<Vec<grades::Model>, DbErr> {
+        Ok(self.tables().grades.clone())
+    }
+
+    // Route Funcs
+    async fn add_route(self, route: routes::Model, style_names: Vec<String>) -> Result<(), DbErr> {
+        let mut tables = self.tables();
+        if !tables.grades.iter().any(|grade| grade.id == route.grade_id) {
+            return Err(in_use("grades"));
+        }
+        let route_id = next_id(tables.routes.iter().map(|route| route.id));
+        tables.routes.push(routes::Model { id: route_id, ..route });
+        let found: Vec<i32> = tables.styles.iter().filter(|style| style_names.contains(&style.name)).map(|style| style.id).collect();
+        for style in found {
+            let id = next_id(tables.route_styles.iter().map(|link| link.id));
+            tables.route_styles.push(route_styles::Model { id, route: route_id, style });
+        }
+        Ok(())
+    }
+
+    async fn remove_route(self, id: i32) -> Result<(), DbErr> {
+        let mut tables = self.tables();
+        if tables.sends.iter().any(|send| send.route == id) {
+            return Err(in_use("sends"));
+        }
+        tables.route_styles.retain(|link| link.route != id);
+        tables.routes.retain(|route| route.id != id);
+        Ok(())
+    }
+
+    async fn find_route_name(self, name: &str) -> Result<Option<routes::Model>, DbErr> {
+        Ok(self.tables().routes.iter().find(|route| route.name == name).cloned())
+    }
+
+    async fn get_route_id(self, name: &str) -> Result<i32, DbErr> {
+        self.tables().routes.iter().find(|route| route.name == name).map(|route| route.id).ok_or_else(|| DbErr::RecordNotFound(format!("Route {name}")))
+    }
+
+    async fn find_routes_by_grade(self, grade: i32) -> Result<Vec<String>, DbErr> {
+        let mut routes_at_grade: Vec<String> = self.tables().routes.iter().filter(|route| route.grade_id == grade).map(|route| route.name.clone()).collect();
+        routes_at_grade.sort_unstable();
+        Ok(routes_at_grade)
+    }
+
+    async fn find_all_routes(self) -> Result<Vec<routes::Model>, DbErr> {
+        Ok(self.tables().routes.clone())
+    }
+
+    async fn find_route_by_id(self, id: i32) -> Result<routes::Model, DbErr> {
+        self.tables().routes.iter().find(|route| route.id == id).cloned().ok_or_else(|| DbErr::RecordNotFound(format!("Route {id}")))
+    }
+
+    async fn find_route_and_grade(self, name: &str) -> Result<(routes::Model, grades::Model), DbErr> {
+        let route = self.clone().find_route_name(name).await?.ok_or_else(|| DbErr::RecordNotFound("Route not found".to_string()))?;
+        let grade = self.get_grade(route.grade_id).await?;
+        Ok((route, grade))
+    }
+
+    async fn find_all_routes_and_grade(self) -> Result<Vec<(routes::Model, grades::Model)>, DbErr> {
+        let tables = self.tables();
+        tables.routes.iter().map(|route| {
+            let grade = tables.grades.iter().find(|grade| grade.id == route.grade_id).ok_or_else(|| DbErr::RecordNotFound(format!("Grade {}", route.grade_id)))?;
+            Ok((route.clone(), grade.clone()))
+        }).collect()
+    }
+
+    async fn get_all_route_styles(self) -> Result<Vec<(route_styles::Model, Option<styles::Model>)>, DbErr> {
+        let tables = self.tables();
+        Ok(tables.route_styles.iter().map(|link| (link.clone(), tables.styles.iter().find(|style| style.id == link.style).cloned())).collect())
+    }
+
+    // Send/Session Funcs
+    async fn add_send(self, climber: i32, session: i32, route: routes::Model, date: String, partners: Vec<i32>, send_type: String, attempts: i32, notes: Option<String>, personal_grade: Option<String>) -> Result<(), DbErr> {
+        let route = self.clone().get_route_id(&route.name).await?;
+        let mut tables = self.tables();
+        if partners.iter().any(|partner| !tables.partners.iter().any(|found| found.id == *partner)) {
+            return Err(in_use("partners"));
+        }
+        let send_id = next_id(tables.sends.iter().map(|send| send.id));
+        tables.sends.push(sends::Model { id: send_id, session, date, r#type: send_type, attempts, notes, route, personal_grade, climber });
+        for partner in partners {
+            let id = next_id(tables.send_partners.iter().map(|link| link.id));
+            tables.send_partners.push(send_partners::Model { id, send: send_id, partner });
+        }
+        Ok(())
+    }
+
+    async fn get_session(self, id: i32) -> Result<Vec<sends::Model>, DbErr> {
+        Ok(self.tables().sends.iter().filter(|send| send.session == id).cloned().collect())
+    }
+
+    async fn get_session_by_date(self, date: String) -> Result<Vec<sends::Model>, DbErr> {
+        Ok(self.tables().sends.iter().filter(|send| send.date == date).cloned().collect())
+    }
+
+    async fn get_all_sends(self) -> Result<Vec<sends::Model>, DbErr> {
+        Ok(self.tables().sends.clone())
+    }
+
+    async fn remove_session(self, id: i32) -> Result<(), DbErr> {
+        let mut tables = self.tables();
+        let removed: Vec<i32> = tables.sends.iter().filter(|send| send.session == id).map(|send| send.id).collect();
+        tables.send_partners.retain(|link| !removed.contains(&link.send));
+        tables.sends.retain(|send| send.session != id);
+        Ok(())
+    }
+
+    async fn get_next_session_id(self) -> Result<i32, DbErr> {
+        Ok(next_id(self.tables().sends.iter().map(|send| send.session)))
+    }
+
+    // Climber Funcs
+    async fn add_climber(self, name: &str) -> Result<i32, DbErr> {
+        let mut tables = self.tables();
+        if let Some(climber) = tables.climbers.iter().find(|climber| climber.name == name) {
+            return Ok(climber.id);
+        }
+        let id = next_id(tables.climbers.iter().map(|climber| climber.id));
+        tables.climbers.push(climbers::Model { id, name: name.to_string() });
+        Ok(id)
+    }
+
+    async fn get_all_climbers(self) -> Result<Vec<climbers::Model>, DbErr> {
+        Ok(self.tables().climbers.clone())
+    }
+
+    // Partner Funcs
+    async fn add_partner(self, name: &str) -> Result<i32, DbErr> {
+        let mut tables = self.tables();
+        if let Some(partner) = tables.partners.iter().find(|partner| partner.name == name) {
+            return Ok(partner.id);
+        }
+        let id = next_id(tables.partners.iter().map(|partner| partner.id));
+        tables.partners.push(partners::Model { id, name: name.to_string() });
+        Ok(id)
+    }
+
+    async fn remove_partner(self, id: i32) -> Result<(), DbErr> {
+        let mut tables = self.tables();
+        tables.send_partners.retain(|link| link.partner != id);
+        tables.partners.retain(|partner| partner.id != id);
+        Ok(())
+    }
+
+    async fn get_all_partners(self) -> Result<Vec<partners::Model>, DbErr> {
+        let mut all_partners = self.tables().partners.clone();
+        all_partners.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(all_partners)
+    }
+
+    async fn get_all_send_partners(self) -> Result<Vec<send_partners::Model>, DbErr> {
+        Ok(self.tables().send_partners.clone())
+    }
+
+    // Search Funcs
+    async fn search_text(self, text: &str) -> Result<Vec<TextMatch>, DbErr> {
+        let tables = self.tables();
+        Ok(search::scan(text, &tables.routes, &tables.sends, &tables.partners, &tables.send_partners))
+    }
+
+    // Filter Funcs
+    async fn filter_sends(self, filter: &Filter, climber: i32) -> Result<Vec<sends::Model>, DbErr> {
+        let route_styles = logbook::styles_by_route(self.clone().get_all_route_styles().await?);
+        let tables = self.tables();
+        let mut found = Vec::new();
+        for send in tables.sends.iter().filter(|send| send.climber == climber) {
+            let (route, grade) = tables.route_and_grade(send.route)?;
+            let styles = route_styles.get(&route.id).map_or(&[][..], Vec::as_slice);
+            if filter.matches_send(route, grade, styles, send, &tables.partner_names(send.id)) {
+                found.push(send.clone());
+            }
+        }
+        Ok(found)
+    }
+
+    async fn filter_routes(self, filter: &Filter, climber: i32) -> Result<Vec<(routes::Model, grades::Model)>, DbErr> {
+        let route_styles = logbook::styles_by_route(self.clone().get_all_route_styles().await?);
+        let tables = self.tables();
+        let mut found = Vec::new();
+        for route in &tables.routes {
+            let (_, grade) = tables.route_and_grade(route.id)?;
+            let styles = route_styles.get(&route.id).map_or(&[][..], Vec::as_slice);
+            let sends: Vec<_> = tables.sends.iter().filter(|send| send.route == route.id && send.climber == climber)
+                .map(|send| (send, tables.partner_names(send.id)))
+                .collect();
+            if filter.matches_route(route, grade, styles, &sends) {
+                found.push((route.clone(), grade.clone()));
+            }
+        }
+        Ok(found)
+    }
+
+    // Settings Funcs
+    async fn get_setting(self, key: &str) -> Result<Option<String>, DbErr> {
+        Ok(self.tables().settings.get(key).cloned())
+    }
+
+    async fn get_all_settings(self) -> Result<Vec<settings::Model>, DbErr> {
+        Ok(self.tables().settings.iter().map(|(key, value)| settings::Model { key: key.clone(), value: value.clone() }).collect())
+    }
+
+    async fn set_setting(self, key: &str, value: String) -> Result<(), DbErr> {
+        self.tables().settings.insert(key.to_string(), value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::{ConnectionTrait, Database};
+    use migration::{Migrator, MigratorTrait};
+    use crate::climbing::{Discipline, Grade, Hueco, SendType, Yosemite};
+    use crate::conversion::{self, GradeSystem, LengthUnit};
+    use crate::export::LogbookExport;
+    use crate::filter::SavedFilters;
+    use crate::logbook::LogbookData;
+    use crate::routes_db::RoutesDb;
+    use crate::search::{MatchKind, SearchFilters};
+    use crate::settings::Settings;
+    use crate::entry::EntryError;
+    use chrono::NaiveDate;
+    use itertools::Itertools;
+
+    // Every check runs against both stores, so they can't drift apart
+
+    const POSTGRES_ENV: &str = "CLIMB_LOG_TEST_POSTGRES";
+
+    async fn postgres(test: &str) -> Option<RoutesDb> {
+        // Set CLIMB_LOG_TEST_POSTGRES to a server url without a database, like postgres://postgres@localhost:5432, to test Postgres too
+        // Each test gets a scratch database of its own, made fresh every run
+        let url = std::env::var(POSTGRES_ENV).ok()?;
+        let server = Database::connect(&url).await.unwrap();
+        let name = format!("climb_log_test_{test}");
+        server.execute_unprepared(&format!("DROP DATABASE IF EXISTS {name}")).await.unwrap();
+        server.execute_unprepared(&format!("CREATE DATABASE {name}")).await.unwrap();
+        server.close().await.unwrap();
+        Some(RoutesDb::open(&format!("{}/{name}", url.trim_end_matches('/'))).await.unwrap())
+    }
+
+    async fn stores(test: &str) -> (MemoryStore, Vec<RoutesDb>) {
+        let mut databases = vec![RoutesDb::open("sqlite::memory:").await.unwrap()];
+        databases.extend(postgres(test).await);
+        (MemoryStore::new(), databases)
+    }
+
+    macro_rules! for_each_store {
+        // A test per check, run on the memory store and every database, each Postgres test in a database of its own
+        ($($test:ident: $check:ident in $name:literal),* $(,)?) => {$(
+            #[tokio::test]
+            async fn $test() {
+                let (memory, databases) = stores($name).await;
+                $check(memory).await;
+                for db in databases {
+                    $check(db).await;
+                }
+            }
+        )*};
+    }
+
+    for_each_store! {
+        routes_add_find_and_remove: check_routes in "routes",
+        sessions_get_the_next_free_id: check_sessions in "sessions",
+        stats_over_fixture_logbook: check_stats in "stats",
+        climbers_keep_their_own_sends: check_climbers in "climbers",
+        search_finds_routes_notes_and_partners: check_search in "search",
+        filters_match_on_every_store: check_filters in "filters",
+        settings_round_trip: check_settings in "settings",
+    }
+
+    async fn add_grades(store: impl LogbookStore) {
+        let tall = [Yosemite::FiveNine, Yosemite::FiveTenA, Yosemite::FiveElevenA].map(Grade::Yosemite);
+        for grade in tall.into_iter().chain([Hueco::V3, Hueco::V5].map(Grade::Hueco)) {
+            store.clone().add_grade(conversion::grade_row(grade)).await.unwrap();
+        }
+    }
+
+    async fn add_route(store: impl LogbookStore, name: &str, grade: &str, location: &str, style: Style) {
+        let boulder = style == Style::Boulder;
+        let route = routes::Model {
+            id: 0,
+            name: name.to_string(),
+            length: if boulder { 450 } else { 3000 },
+            pitches: i32::from(!boulder),
+            grade_id: store.clone().get_grade_id(grade).await.unwrap(),
+            location: Some(location.to_string()),
+            ice_grade: None,
+            mixed_grade: None,
+            aid_grade: None,
+            commitment_grade: None,
+            ifas_grade: None,
+            danger: None,
+            grade_high: None,
+            discipline: if boulder { Discipline::Boulder } else { Discipline::TallWall }.to_string(),
+        };
+        store.add_route(route, vec![style.to_string(), "Not A Style".to_string()]).await.unwrap();
+    }
+
+    async fn add_send(store: impl LogbookStore, session: i32, date: &str, route: &str, send_type: SendType, attempts: i32, partners: &[&str]) {
+        add_climber_send(store, 1, session, date, route, send_type, attempts, partners).await;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn add_climber_send(store: impl LogbookStore, climber: i32, session: i32, date: &str, route: &str, send_type: SendType, attempts: i32, partners: &[&str]) {
+        let mut ids = Vec::new();
+        for partner in partners {
+            ids.push(store.clone().add_partner(partner).await.unwrap());
+        }
+        let route = store.clone().find_route_name(route).await.unwrap().unwrap();
+        store.add_send(climber, session, route, date.to_string(), ids, send_type.to_string(), attempts, None, None).await.unwrap();
+    }
+
+    async fn fixture(store: impl LogbookStore) {
+        // Three sessions over two crags, Arete is the favourite and Sam the usual partner
+        add_grades(store.clone()).await;
+        add_route(store.clone(), "Arete", "5.10a", "Smith Rock", Style::Sport).await;
+        add_route(store.clone(), "Crack", "5.11a", "Smith Rock", Style::Trad).await;
+        add_route(store.clone(), "Blob", "V5", "Bishop", Style::Boulder).await;
+        add_send(store.clone(), 1, "2024-05-01", "Arete", SendType::Onsight, 1, &["Sam"]).await;
+        add_send(store.clone(), 1, "2024-05-01", "Blob", SendType::Flash, 1, &[]).await;
+        add_send(store.clone(), 2, "2024-05-08", "Arete", SendType::Redpoint, 1, &["Sam"]).await;
+        add_send(store.clone(), 2, "2024-05-08", "Crack", SendType::Redpoint, 3, &["Sam", "Alex"]).await;
+        add_send(store.clone(), 3, "2024-06-01", "Blob", SendType::Redpoint, 2, &[]).await;
+        add_send(store.clone(), 3, "2024-06-01", "Arete", SendType::Redpoint, 1, &["Sam"]).await;
+    }
+
+    async fn check_routes(store: impl LogbookStore) {
+        add_grades(store.clone()).await;
+        add_route(store.clone(), "Arete", "5.10a", "Smith Rock", Style::Sport).await;
+        add_route(store.clone(), "Blob", "V5", "Bishop", Style::Boulder).await;
+
+        let (arete, grade) = store.clone().find_route_and_grade("Arete").await.unwrap();
+        assert_eq!(grade.yosemite.as_deref(), Some("5.10a"));
+        assert_eq!(store.clone().find_route_by_id(arete.id).await.unwrap(), arete);
+        assert_eq!(store.clone().get_route_id("Blob").await.unwrap(), store.clone().find_route_name("Blob").await.unwrap().unwrap().id);
+        assert_eq!(store.clone().find_routes_by_grade(arete.grade_id).await.unwrap(), vec!["Arete".to_string()]);
+        let v3 = store.clone().get_grade_id("V3").await.unwrap();
+        assert!(store.clone().find_routes_by_grade(v3).await.unwrap().is_empty());
+        assert!(store.clone().find_routes_by_grade(9999).await.unwrap().is_empty(), "unknown grades have no routes");
+        assert!(store.clone().find_route_name("Nowhere").await.unwrap().is_none());
+        assert!(matches!(store.clone().get_route_id("Nowhere").await, Err(DbErr::RecordNotFound(_))));
+        assert_eq!(store.clone().find_all_routes_and_grade().await.unwrap().len(), 2);
+
+        // Unknown style names are skipped
+        let styles = store.clone().get_all_route_styles().await.unwrap();
+        let arete_styles: Vec<String> = styles.iter().filter(|(link, _)| link.route == arete.id).filter_map(|(_, style)| style.as_ref().map(|style| style.name.clone())).collect();
+        assert_eq!(arete_styles, vec!["Sport".to_string()]);
+
+        // Routes with sends and grades with routes can't be removed
+        add_send(store.clone(), 1, "2024-05-01", "Arete", SendType::Onsight, 1, &[]).await;
+        // Refused before anything changes, so the route keeps its styles
+        assert!(matches!(store.clone().remove_route(arete.id).await, Err(DbErr::Custom(_))));
+        assert!(store.clone().find_route_name("Arete").await.unwrap().is_some());
+        assert_eq!(store.clone().get_all_route_styles().await.unwrap().iter().filter(|(link, _)| link.route == arete.id).count(), 1);
+        assert!(store.clone().remove_grade(arete.grade_id).await.is_err());
+        store.clone().remove_session(1).await.unwrap();
+        store.clone().remove_route(arete.id).await.unwrap();
+        assert!(store.clone().find_route_name("Arete").await.unwrap().is_none());
+        assert!(store.clone().get_all_route_styles().await.unwrap().iter().all(|(link, _)| link.route != arete.id));
+        store.clone().remove_grade(arete.grade_id).await.unwrap();
+        assert!(matches!(store.get_grade(arete.grade_id).await, Err(DbErr::RecordNotFound(_))));
+    }
+
+    async fn check_sessions(store: impl LogbookStore) {
+        assert_eq!(store.clone().get_next_session_id().await.unwrap(), 1);
+        fixture(store.clone()).await;
+        assert_eq!(store.clone().get_next_session_id().await.unwrap(), 4);
+        assert_eq!(store.clone().get_session(2).await.unwrap().len(), 2);
+        assert_eq!(store.clone().get_session_by_date("2024-06-01".to_string()).await.unwrap().len(), 2);
+
+        // Partners are unique by name
+        let sam = store.clone().add_partner("Sam").await.unwrap();
+        assert_eq!(store.clone().get_all_partners().await.unwrap().iter().map(|partner| partner.name.as_str()).collect::<Vec<_>>(), vec!["Alex", "Sam"]);
+        assert_eq!(store.clone().get_all_send_partners().await.unwrap().iter().filter(|link| link.partner == sam).count(), 4);
+
+        // Removing a session takes its partners along, and a later session doesn't reuse the last id
+        store.clone().remove_session(2).await.unwrap();
+        assert!(store.clone().get_session(2).await.unwrap().is_empty());
+        assert_eq!(store.clone().get_all_sends().await.unwrap().len(), 4);
+        assert_eq!(store.clone().get_all_send_partners().await.unwrap().len(), 2);
+        assert_eq!(store.clone().get_next_session_id().await.unwrap(), 4);
+        store.clone().remove_session(3).await.unwrap();
+        assert_eq!(store.clone().get_next_session_id().await.unwrap(), 2);
+        store.clone().remove_partner(sam).await.unwrap();
+        assert!(store.get_all_send_partners().await.unwrap().is_empty());
+    }
+
+    async fn check_stats(store: impl LogbookStore) {
+        fixture(store.clone()).await;
+        let summary = LogbookData::load(store).await.unwrap().summary(&Settings::default());
+        let stat = |label: &str| summary.iter().find(|(found, _)| *found == label).map(|(_, value)| value.clone()).unwrap();
+        assert_eq!(stat("Total Sends"), "6");
+        assert_eq!(stat("Total Sessions"), "3");
+        assert_eq!(stat("Average Sends per Session"), "2");
+        assert_eq!(stat("Average Attempts per Send"), "1.5");
+        assert_eq!(stat("Favorite Style"), "Sport");
+        assert_eq!(stat("Favorite Route"), "Arete");
+        assert_eq!(stat("Favorite Partner"), "Sam");
+        assert_eq!(stat("Favorite Crag"), "Smith Rock");
+        assert_eq!(stat("Top Tall Wall Grade"), "5.11a (Crack)");
+        assert_eq!(stat("Top Boulder Grade"), "V5 (Blob)");
+    }
+
+    async fn check_climbers(store: impl LogbookStore) {
+        // Sam shares the routes but keeps their own sends, the first climber's stats don't change
+        fixture(store.clone()).await;
+        let sam = store.clone().add_climber("Sam").await.unwrap();
+        assert_eq!(store.clone().add_climber("Sam").await.unwrap(), sam);
+        assert_eq!(store.clone().get_all_climbers().await.unwrap().iter().map(|climber| climber.name.as_str()).collect::<Vec<_>>(), vec!["Me", "Sam"]);
+        add_climber_send(store.clone(), sam, 4, "2024-06-02", "Crack", SendType::Flash, 1, &[]).await;
+
+        let data = LogbookData::load(store.clone()).await.unwrap();
+        assert_eq!(data.climber_id("sam"), Some(sam));
+        let team = data.team(&Settings::default());
+        let stat = |climber: usize, label: &str| team[climber].1.iter().find(|(found, _)| *found == label).map(|(_, value)| value.clone()).unwrap();
+        assert_eq!((team[0].0.as_str(), stat(0, "Total Sends")), ("Me", "6".to_string()));
+        assert_eq!((team[1].0.as_str(), stat(1, "Total Sends")), ("Sam", "1".to_string()));
+        assert_eq!(stat(1, "Top Tall Wall Grade"), "5.11a (Crack)");
+        let mine = data.for_climber(1);
+        assert_eq!(mine.sends.len(), 6);
+        assert_eq!(mine.send_partners.len(), 5);
+
+        // A climber's export carries their name, and importing it elsewhere adds them
+        let export = LogbookExport::build(store.clone(), Some(sam)).await.unwrap();
+        assert_eq!(export.sends.iter().map(|send| send.climber_name.as_str()).collect::<Vec<_>>(), vec!["Sam"]);
+        assert_eq!(export.routes.len(), 3);
+        let other = MemoryStore::new();
+        add_grades(other.clone()).await;
+        export.import(other.clone(), 1).await.unwrap();
+        let imported = LogbookData::load(other).await.unwrap();
+        assert_eq!(imported.climber_name(imported.sends[0].climber), "Sam");
+    }
+
+    async fn check_settings(store: impl LogbookStore) {
+        assert_eq!(Settings::load(store.clone()).await.unwrap(), Settings::default());
+        let settings = Settings { units: LengthUnit::Metres, ..Settings::default() };
+        settings.save(store.clone()).await.unwrap();
+        assert_eq!(Settings::load(store.clone()).await.unwrap(), settings);
+        store.clone().set_setting("units", "Feet".to_string()).await.unwrap();
+        assert_eq!(store.get_setting("units").await.unwrap().as_deref(), Some("Feet"));
+    }
+
+    async fn check_search(store: impl LogbookStore) {
+        fixture(store.clone()).await;
+        let crack = store.clone().find_route_name("Crack").await.unwrap().unwrap();
+        let notes = Some("Finally stuck the crux, Sam gave a great catch".to_string());
+        store.clone().add_send(1, 4, crack.clone(), "2024-07-01".to_string(), Vec::new(), SendType::Redpoint.to_string(), 1, notes, None).await.unwrap();
+        let found = |text: &'static str| {
+            let store = store.clone();
+            async move {
+                let mut found: Vec<(MatchKind, i32)> = store.search_text(text).await.unwrap().iter().map(|found| (found.kind, found.id)).collect();
+                found.sort_unstable_by_key(|(kind, id)| (*kind == MatchKind::Send, *id));
+                found
+            }
+        };
+
+        // Words match as prefixes in any case, on routes' names and crags and on sends' notes and partners
+        assert_eq!(found("smith").await, vec![(MatchKind::Route, 1), (MatchKind::Route, crack.id)]);
+        assert_eq!(found("Bish").await, vec![(MatchKind::Route, 3)]);
+        assert_eq!(found("SAM").await.len(), 5);
+        assert_eq!(found("sam alex").await.len(), 1);
+        assert_eq!(found("sam crux").await, vec![(MatchKind::Send, 7)]);
+        // Anything that isn't a word is ignored, rather than read as query syntax
+        assert_eq!(found("\"crux)( OR*").await, Vec::new());
+        assert_eq!(found("crux)(*").await, vec![(MatchKind::Send, 7)]);
+        assert!(found("  ").await.is_empty());
+        let notes = store.clone().search_text("crux").await.unwrap();
+        assert!(search::highlights(&notes[0].snippet).contains(&("crux", true)));
+
+        // Filters narrow results down, sends by type and day and both by the route's grade and style
+        let settings = &Settings::default();
+        let hits = |text: &'static str, filters: SearchFilters| {
+            let store = store.clone();
+            async move { search::search(store, text, &filters, 1, settings).await.map(|hits| hits.iter().map(|hit| hit.title.clone()).sorted().collect::<Vec<_>>()) }
+        };
+        assert_eq!(hits("sam", SearchFilters { send_types: vec![SendType::Onsight], ..SearchFilters::default() }).await.unwrap(), vec!["2024-05-01: Arete"]);
+        assert_eq!(hits("smith", SearchFilters { grade_from: Some("5.11a".to_string()), ..SearchFilters::default() }).await.unwrap(), vec!["Crack"]);
+        assert_eq!(hits("smith", SearchFilters { styles: vec![Style::Sport], ..SearchFilters::default() }).await.unwrap(), vec!["Arete"]);
+        let june = SearchFilters { from: NaiveDate::from_ymd_opt(2024, 6, 1), to: NaiveDate::from_ymd_opt(2024, 6, 30), ..SearchFilters::default() };
+        assert_eq!(hits("smith", june).await.unwrap(), vec!["Arete"]);
+        let mixed = SearchFilters { grade_from: Some("V3".to_string()), grade_to: Some("5.11a".to_string()), ..SearchFilters::default() };
+        assert!(matches!(hits("smith", mixed).await, Err(EntryError::Invalid(_))));
+
+        // Bounds are read in the preferred systems first, then in any other
+        let french = Settings { rope_system: GradeSystem::French, ..Settings::default() };
+        let font = Settings { boulder_system: GradeSystem::Font, ..Settings::default() };
+        let graded = |text: &'static str, from: &str, settings: &Settings| {
+            let (store, filters, settings) = (store.clone(), SearchFilters { grade_from: Some(from.to_string()), ..SearchFilters::default() }, settings.clone());
+            async move { search::search(store, text, &filters, 1, &settings).await.map(|hits| hits.iter().map(|hit| hit.title.clone()).sorted().collect::<Vec<_>>()) }
+        };
+        let crack = conversion::convert(Grade::Yosemite(Yosemite::FiveElevenA), GradeSystem::French).unwrap().to_string();
+        assert_eq!(graded("smith", &crack, &french).await.unwrap(), vec!["Crack"]);
+        assert_eq!(graded("smith", "5.11a", &french).await.unwrap(), vec!["Crack"], "Yosemite still works");
+        assert_eq!(graded("bishop", "7A", &font).await.unwrap(), Vec::<String>::new(), "Font 7A is harder than V5");
+        assert_eq!(graded("bishop", "6A", &font).await.unwrap(), vec!["Blob"]);
+        assert_eq!(graded("bishop", "7A", &french).await.unwrap(), Vec::<String>::new(), "7A is a French route grade, so boulders are left out");
+        assert!(matches!(graded("smith", "5.99", &french).await, Err(EntryError::Invalid(_))));
+
+        // Removing things takes them out of the search too
+        store.clone().remove_session(4).await.unwrap();
+        assert!(found("crux").await.is_empty());
+        store.clone().remove_partner(store.clone().add_partner("Alex").await.unwrap()).await.unwrap();
+        assert!(found("alex").await.is_empty());
+        add_route(store.clone(), "Slab", "5.9", "Index", Style::Trad).await;
+        assert_eq!(found("index").await.len(), 1);
+        store.clone().remove_route(store.clone().get_route_id("Slab").await.unwrap()).await.unwrap();
+        assert!(found("index").await.is_empty());
+    }
+
+    async fn check_filters(store: impl LogbookStore) {
+        fixture(store.clone()).await;
+        let sam = store.clone().add_climber("Sam").await.unwrap();
+        let crack = store.clone().find_route_name("Crack").await.unwrap().unwrap();
+        store.clone().add_send(sam, 4, crack, "2024-07-01".to_string(), Vec::new(), SendType::Redpoint.to_string(), 1, Some("Fell at 50% height".to_string()), None).await.unwrap();
+        let sends = |text: &'static str, climber: i32| {
+            let store = store.clone();
+            async move { store.filter_sends(&Filter::parse(text, &Settings::default()).unwrap(), climber).await.unwrap().iter().map(|send| send.id).collect::<Vec<_>>() }
+        };
+        let routes = |text: &'static str, climber: i32| {
+            let store = store.clone();
+            async move { store.filter_routes(&Filter::parse(text, &Settings::default()).unwrap(), climber).await.unwrap().iter().map(|(route, _)| route.name.clone()).collect::<Vec<_>>() }
+        };
+
+        // Sends, only the climber's, with grades compared within their own system
+        assert_eq!(sends("", 1).await, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(sends("grade>=5.10a", 1).await, vec![1, 3, 4, 6]);
+        assert_eq!(sends("grade>5.10a style:trad", 1).await, vec![4]);
+        assert_eq!(sends("grade>=V3 type:flash", 1).await, vec![2]);
+        assert_eq!(sends("type!=redpoint", 1).await, vec![1, 2]);
+        assert_eq!(sends("style!=trad", 1).await, vec![1, 2, 3, 5, 6]);
+        assert_eq!(sends("crag:\"smith rock\" after:2024-05-08", 1).await, vec![3, 4, 6]);
+        assert_eq!(sends("before:2024-05-08 -crag:smith", 1).await, vec![2]);
+        assert_eq!(sends("with:alex", 1).await, vec![4]);
+        assert_eq!(sends("partner:SAM -route:arete attempts>=2", 1).await, vec![4]);
+        assert_eq!(sends("bish", 1).await, vec![2, 5]);
+        // LIKE wildcards in the text are taken literally
+        assert_eq!(sends("notes:50%", sam).await, vec![7]);
+        assert_eq!(sends("notes:5%", sam).await, Vec::<i32>::new());
+
+        // Routes, with terms on sends needing one of the climber's sends to match them all
+        assert_eq!(routes("crag:smith", 1).await, vec!["Arete", "Crack"]);
+        assert_eq!(routes("pitches>=1 -style:sport", 1).await, vec!["Crack"]);
+        assert_eq!(routes("-type:redpoint", 1).await, vec!["Arete", "Blob"]);
+        assert_eq!(routes("after:2024-06-01", 1).await, vec!["Arete", "Blob"]);
+        assert_eq!(routes("type:onsight with:sam", 1).await, vec!["Arete"]);
+        assert_eq!(routes("with:alex", sam).await, Vec::<String>::new());
+        assert_eq!(routes("notes:50", sam).await, vec!["Crack"]);
+
+        // Saved filters are kept in the settings and picked with @name
+        let mut saved = SavedFilters::load(store.clone()).await.unwrap();
+        assert!(saved.0.is_empty());
+        saved.insert("hard trad", "  grade>=5.11a style:trad ", &Settings::default()).unwrap();
+        assert!(saved.insert("", "grade>=5.11a", &Settings::default()).is_err());
+        assert!(saved.insert("broken", "grade>=5.99", &Settings::default()).is_err());
+        saved.save(store.clone()).await.unwrap();
+        let saved = SavedFilters::load(store.clone()).await.unwrap();
+        assert_eq!(saved.0.get("hard trad").map(String::as_str), Some("grade>=5.11a style:trad"));
+        assert_eq!(store.clone().filter_sends(&saved.resolve("@hard trad", &Settings::default()).unwrap(), 1).await.unwrap().len(), 1);
+        assert!(saved.resolve("@nothing", &Settings::default()).is_err());
+        assert_eq!(Settings::load(store).await.unwrap(), Settings::default());
+    }
+
+    #[tokio::test]
+    async fn reopening_keeps_the_logbook() {
+        // Opening runs the migrations again, which must leave everything logged in place
+        let path = std::env::temp_dir().join(format!("climb_log_reopen_{}.db", std::process::id()));
+        let sqlite = format!("sqlite:{}?mode=rwc", path.display());
+        fixture(RoutesDb::open(&sqlite).await.unwrap()).await;
+        let mut urls = vec![sqlite];
+        if let Some(db) = postgres("reopen").await {
+            fixture(db).await;
+            urls.push(format!("{}/climb_log_test_reopen", std::env::var(POSTGRES_ENV).unwrap().trim_end_matches('/')));
+        }
+        for url in urls {
+            let db = RoutesDb::open(&url).await.unwrap();
+            assert_eq!(db.clone().get_all_sends().await.unwrap().len(), 6, "{url}");
+            assert_eq!(db.find_all_routes_and_grade().await.unwrap().len(), 3, "{url}");
+        }
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn opening_a_bad_url_is_an_error() {
+        // Callers show the error, so a url that isn't a logbook's must come back as one rather than panic
+        assert!(matches!(RoutesDb::open("routes.db").await, Err(DbErr::Custom(_))));
+        assert!(matches!(RoutesDb::open("mysql://localhost/logbook").await, Err(DbErr::Custom(_))));
+        assert!(RoutesDb::open("sqlite:/nonexistent/dir/logbook.db").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn migration_backfills_grade_systems() {
+        // Grades from before the newer systems get them filled in, without touching what was already picked
+        let path = std::env::temp_dir().join(format!("climb_log_backfill_{}.db", std::process::id()));
+        let url = format!("sqlite:{}?mode=rwc", path.display());
+        let db = RoutesDb::open(&url).await.unwrap();
+        add_grades(db.clone()).await;
+        let picked = grades::Model { french: Some("6a+".to_string()), ..grades::Model::default() };
+        db.clone().add_grade(grades::Model { yosemite: Some("5.10b".to_string()), ..picked }).await.unwrap();
+        let connection = Database::connect(&url).await.unwrap();
+        Migrator::down(&connection, Some(1)).await.unwrap();
+        connection.close().await.unwrap();
+
+        let grades = RoutesDb::open(&url).await.unwrap().get_all_grades().await.unwrap();
+        let find = |system: fn(&grades::Model) -> &Option<String>, grade: &str| grades.iter().find(|found| system(found).as_deref() == Some(grade)).unwrap().clone();
+        let ten_a = find(|grade| &grade.yosemite, "5.10a");
+        assert_eq!((ten_a.french.as_deref(), ten_a.ewbank.as_deref(), ten_a.kurtyka.as_deref()), (Some("6a"), Some("17"), Some("VI+")));
+        assert_eq!(ten_a.hueco, None);
+        assert_eq!(find(|grade| &grade.hueco, "V5").font.as_deref(), Some("6C"));
+        let ten_b = find(|grade| &grade.yosemite, "5.10b");
+        assert_eq!((ten_b.french.as_deref(), ten_b.saxon.as_deref()), (Some("6a+"), Some("VIIc")), "the picked French grade stays");
+        let _ = std::fs::remove_file(path);
+    }
+}
+
@@ -3,6 +3,22 @@ pub use sea_orm_migration::prelude::*;
 mod m20220101_000001_create_routes_table;
 mod m20220101_000001_create_grades_table;
 mod m20220101_000001_create_sends_table;
+mod m20240801_000001_create_settings_table;
+mod m20240801_000002_add_route_location;
+mod m20240801_000003_create_partners_table;
+mod m20240801_000004_add_grade_systems;
+mod m20240801_000005_add_route_style_grades;
+mod m20240801_000006_add_route_danger;
+mod m20240801_000007_add_route_grade_high;
+mod m20240801_000008_add_send_personal_grade;
+mod m20240801_000009_create_route_styles_table;
+mod m20240801_000010_route_length_in_centimetres;
+mod m20240801_000011_create_climbers_table;
+mod m20240801_000012_add_lookup_indexes;
+mod m20240801_000013_create_search_index;
+mod m20240801_000014_backfill_grade_systems;
+
+pub use m20240801_000014_backfill_grade_systems::{BOULDER_EQUIVALENTS, TALL_COLUMNS, TALL_EQUIVALENTS};
 
 pub struct Migrator;
 
@@ -13,6 +29,20 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000001_create_grades_table::Migration),
             Box::new(m20220101_000001_create_routes_table::Migration),
             Box::new(m20220101_000001_create_sends_table::Migration),
+            Box::new(m20240801_000001_create_settings_table::Migration),
+            Box::new(m20240801_000002_add_route_location::Migration),
+            Box::new(m20240801_000003_create_partners_table::Migration),
+            Box::new(m20240801_000004_add_grade_systems::Migration),
+            Box::new(m20240801_000005_add_route_style_grades::Migration),
+            Box::new(m20240801_000006_add_route_danger::Migration),
+            Box::new(m20240801_000007_add_route_grade_high::Migration),
+            Box::new(m20240801_000008_add_send_personal_grade::Migration),
+            Box::new(m20240801_000009_create_route_styles_table::Migration),
+            Box::new(m20240801_000010_route_length_in_centimetres::Migration),
+            Box::new(m20240801_000011_create_climbers_table::Migration),
+            Box::new(m20240801_000012_add_lookup_indexes::Migration),
+            Box::new(m20240801_000013_create_search_index::Migration),
+            Box::new(m20240801_000014_backfill_grade_systems::Migration),
             
             ]
     }
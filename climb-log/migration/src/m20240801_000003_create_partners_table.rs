@@ -0,0 +1,122 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_sends_table::Sends;
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000003_create_partners_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // Partners move from free text on each send to their own table, with a join table so a send can have several
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Partners::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Partners::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Partners::Name).string().not_null().unique_key())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SendPartners::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SendPartners::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SendPartners::Send).integer().not_null())
+                    .col(ColumnDef::new(SendPartners::Partner).integer().not_null())
+                    .foreign_key(ForeignKey::create()
+                        .name("fk-sends-send_partners_id")
+                        .from(SendPartners::Table, SendPartners::Send)
+                        .to(Sends::Table, Sends::Id)
+                    )
+                    .foreign_key(ForeignKey::create()
+                        .name("fk-partners-send_partners_id")
+                        .from(SendPartners::Table, SendPartners::Partner)
+                        .to(Partners::Table, Partners::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Carry the existing free text partners over before dropping the old column
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "INSERT INTO partners (name) SELECT DISTINCT partner FROM sends WHERE partner IS NOT NULL AND partner <> ''",
+        )
+        .await?;
+        db.execute_unprepared(
+            "INSERT INTO send_partners (send, partner) SELECT sends.id, partners.id FROM sends JOIN partners ON partners.name = sends.partner",
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sends::Table)
+                    .drop_column(Sends::Partner)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sends::Table)
+                    .add_column(ColumnDef::new(Sends::Partner).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Only one partner fits in the old column, keep the first one
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE sends SET partner = (SELECT partners.name FROM send_partners JOIN partners ON partners.id = send_partners.partner WHERE send_partners.send = sends.id ORDER BY send_partners.id LIMIT 1)",
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SendPartners::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Partners::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Partners {
+    Table,
+    Id,
+    Name,
+}
+
+#[derive(DeriveIden)]
+pub enum SendPartners {
+    Table,
+    Id,
+    Send,
+    Partner,
+}
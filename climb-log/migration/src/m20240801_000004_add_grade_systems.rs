@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_grades_table::Grades;
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000004_add_grade_systems"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // One nullable column per new grading system, SQLite only takes one column per alter statement
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in GradeSystems::all() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Grades::Table)
+                        .add_column(ColumnDef::new(column).string())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in GradeSystems::all() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Grades::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+pub enum GradeSystems {
+    British,
+    Ewbank,
+    Saxon,
+    Brazilian,
+    Kurtyka,
+}
+impl GradeSystems {
+    fn all() -> [GradeSystems; 5] {
+        [GradeSystems::British, GradeSystems::Ewbank, GradeSystems::Saxon, GradeSystems::Brazilian, GradeSystems::Kurtyka]
+    }
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_routes_table::Routes;
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000005_add_route_style_grades"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // Ice, mixed, aid and alpine grades sit next to the free grade, all optional
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in StyleGrades::all() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Routes::Table)
+                        .add_column(ColumnDef::new(column).string())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in StyleGrades::all() {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Routes::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+pub enum StyleGrades {
+    #[sea_orm(iden = "ice_grade")]
+    Ice,
+    #[sea_orm(iden = "mixed_grade")]
+    Mixed,
+    #[sea_orm(iden = "aid_grade")]
+    Aid,
+    #[sea_orm(iden = "commitment_grade")]
+    Commitment,
+    #[sea_orm(iden = "ifas_grade")]
+    Ifas,
+}
+impl StyleGrades {
+    fn all() -> [StyleGrades; 5] {
+        [StyleGrades::Ice, StyleGrades::Mixed, StyleGrades::Aid, StyleGrades::Commitment, StyleGrades::Ifas]
+    }
+}
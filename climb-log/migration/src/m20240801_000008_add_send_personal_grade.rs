@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_sends_table::Sends;
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000008_add_send_personal_grade"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // What the climber thought of the grade on a send: "Soft", "Hard" or their own grade. Existing sends agree with the route
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sends::Table)
+                    .add_column(ColumnDef::new(SendPersonalGrade::PersonalGrade).string())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sends::Table)
+                    .drop_column(SendPersonalGrade::PersonalGrade)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum SendPersonalGrade {
+    PersonalGrade,
+}
@@ -0,0 +1,157 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::DbBackend;
+
+use super::m20220101_000001_create_routes_table::Routes;
+
+// Style names as shown in the app, one row each in the styles table
+const STYLES: [&str; 10] = ["Boulder", "Top Rope", "Sport", "Trad", "Ice", "Alpine", "Aid", "Speed", "Free Solo", "Deep Water"];
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000009_create_route_styles_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // Routes get a discipline column instead of treating 0 pitches as a boulder,
+    // and the comma joined style column moves to a styles table with a join table so a route can have several
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Routes::Table)
+                    .add_column(ColumnDef::new(RouteDiscipline::Discipline).string().not_null().default("Tall Wall"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Styles::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Styles::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Styles::Name).string().not_null().unique_key())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RouteStyles::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RouteStyles::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(RouteStyles::Route).integer().not_null())
+                    .col(ColumnDef::new(RouteStyles::Style).integer().not_null())
+                    .foreign_key(ForeignKey::create()
+                        .name("fk-routes-route_styles_id")
+                        .from(RouteStyles::Table, RouteStyles::Route)
+                        .to(Routes::Table, Routes::Id)
+                    )
+                    .foreign_key(ForeignKey::create()
+                        .name("fk-styles-route_styles_id")
+                        .from(RouteStyles::Table, RouteStyles::Style)
+                        .to(Styles::Table, Styles::Id)
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        let mut insert = Query::insert();
+        insert.into_table(Styles::Table).columns([Styles::Name]);
+        for style in STYLES {
+            insert.values_panic([style.into()]);
+        }
+        manager.exec_stmt(insert).await?;
+
+        // Carry the existing data over: old routes with no pitches were boulders, and each name in the style list becomes a row
+        let db = manager.get_connection();
+        db.execute_unprepared("UPDATE routes SET discipline = 'Boulder' WHERE pitches = 0").await?;
+        db.execute_unprepared(
+            "INSERT INTO route_styles (route, style) SELECT routes.id, styles.id FROM routes JOIN styles ON ', ' || routes.style || ', ' LIKE '%, ' || styles.name || ', %' ORDER BY routes.id, styles.id",
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Routes::Table)
+                    .drop_column(Routes::Style)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Routes::Table)
+                    .add_column(ColumnDef::new(Routes::Style).string().not_null().default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        // SQLite and Postgres each have their own way of joining the names back into one string
+        let names = match manager.get_database_backend() {
+            DbBackend::Postgres => "STRING_AGG(styles.name, ', ' ORDER BY styles.id)",
+            _ => "GROUP_CONCAT(styles.name, ', ')",
+        };
+        manager
+            .get_connection()
+            .execute_unprepared(&format!(
+                "UPDATE routes SET style = COALESCE((SELECT {names} FROM route_styles JOIN styles ON styles.id = route_styles.style WHERE route_styles.route = routes.id), '')",
+            ))
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(RouteStyles::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Styles::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Routes::Table)
+                    .drop_column(RouteDiscipline::Discipline)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum RouteDiscipline {
+    Discipline,
+}
+
+#[derive(DeriveIden)]
+pub enum Styles {
+    Table,
+    Id,
+    Name,
+}
+
+#[derive(DeriveIden)]
+pub enum RouteStyles {
+    Table,
+    Id,
+    Route,
+    Style,
+}
@@ -0,0 +1,28 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000010_route_length_in_centimetres"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // Route lengths were whole feet, store them in centimetres so metric and imperial lengths both fit
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE routes SET length = (length * 3048 + 50) / 100")
+            .await
+            .map(|_| ())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE routes SET length = (length * 100 + 1524) / 3048")
+            .await
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_sends_table::Sends;
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000011_create_climbers_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // Several climbers can share one logbook. Routes and grades stay shared, each send belongs to a climber.
+    // Everything logged so far belongs to the first climber, "Me"
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Climbers::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Climbers::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Climbers::Name).string().not_null().unique_key())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Climbers::Table)
+                    .columns([Climbers::Name])
+                    .values_panic(["Me".into()])
+                    .to_owned(),
+            )
+            .await?;
+
+        // SQLite can't add a foreign key to an existing table, so the column is checked by the app instead
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sends::Table)
+                    .add_column(ColumnDef::new(SendClimber::Climber).integer().not_null().default(1))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Going back to one climber keeps everyone's sends in the one logbook
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Sends::Table)
+                    .drop_column(SendClimber::Climber)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Climbers::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum Climbers {
+    Table,
+    Id,
+    Name,
+}
+
+#[derive(DeriveIden)]
+pub enum SendClimber {
+    Climber,
+}
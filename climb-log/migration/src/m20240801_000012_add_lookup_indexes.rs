@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_routes_table::Routes;
+use super::m20220101_000001_create_sends_table::Sends;
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000012_add_lookup_indexes"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // Only indexes, no data is touched, so this is safe to run on a big shared logbook
+    // Routes are joined to their grade and looked up by name, sends are looked up by route, session and date
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(Index::create().if_not_exists().name("idx_routes_grade_id").table(Routes::Table).col(Routes::GradeId).to_owned())
+            .await?;
+        manager
+            .create_index(Index::create().if_not_exists().name("idx_routes_name").table(Routes::Table).col(Routes::Name).to_owned())
+            .await?;
+        manager
+            .create_index(Index::create().if_not_exists().name("idx_sends_route").table(Sends::Table).col(Sends::Route).to_owned())
+            .await?;
+        manager
+            .create_index(Index::create().if_not_exists().name("idx_sends_session").table(Sends::Table).col(Sends::Session).to_owned())
+            .await?;
+        manager
+            .create_index(Index::create().if_not_exists().name("idx_sends_date").table(Sends::Table).col(Sends::Date).to_owned())
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for name in ["idx_sends_date", "idx_sends_session", "idx_sends_route"] {
+            manager.drop_index(Index::drop().name(name).table(Sends::Table).to_owned()).await?;
+        }
+        for name in ["idx_routes_name", "idx_routes_grade_id"] {
+            manager.drop_index(Index::drop().name(name).table(Routes::Table).to_owned()).await?;
+        }
+        Ok(())
+    }
+}
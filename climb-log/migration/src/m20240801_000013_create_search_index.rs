@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::DbBackend;
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000013_create_search_index"
+    }
+}
+
+// Full-text search over route names and crags, and over send notes and partners, with SQLite FTS5
+// Each index row shares its rowid with the route or send it came from, triggers keep them in step
+// Postgres logbooks are searched without an index, so there's nothing to make there
+
+// Partner names on a send, space separated, for the send_search partners column
+const SEND_PARTNERS: &str = "COALESCE((SELECT GROUP_CONCAT(partners.name, ' ') FROM send_partners \
+    JOIN partners ON partners.id = send_partners.partner WHERE send_partners.send = {send}), '')";
+
+fn send_partners(send: &str) -> String {
+    SEND_PARTNERS.replace("{send}", send)
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DbBackend::Sqlite {
+            return Ok(());
+        }
+        let db = manager.get_connection();
+        db.execute_unprepared("CREATE VIRTUAL TABLE IF NOT EXISTS route_search USING fts5(name, crag)").await?;
+        db.execute_unprepared("CREATE VIRTUAL TABLE IF NOT EXISTS send_search USING fts5(notes, partners)").await?;
+
+        // Everything logged so far
+        db.execute_unprepared("INSERT INTO route_search (rowid, name, crag) SELECT id, name, COALESCE(location, '') FROM routes").await?;
+        db.execute_unprepared(&format!(
+            "INSERT INTO send_search (rowid, notes, partners) SELECT sends.id, COALESCE(sends.notes, ''), {} FROM sends",
+            send_partners("sends.id"),
+        )).await?;
+
+        // And everything from here on
+        db.execute_unprepared("CREATE TRIGGER IF NOT EXISTS route_search_insert AFTER INSERT ON routes BEGIN \
+            INSERT INTO route_search (rowid, name, crag) VALUES (new.id, new.name, COALESCE(new.location, '')); END").await?;
+        db.execute_unprepared("CREATE TRIGGER IF NOT EXISTS route_search_update AFTER UPDATE ON routes BEGIN \
+            DELETE FROM route_search WHERE rowid = old.id; \
+            INSERT INTO route_search (rowid, name, crag) VALUES (new.id, new.name, COALESCE(new.location, '')); END").await?;
+        db.execute_unprepared("CREATE TRIGGER IF NOT EXISTS route_search_delete AFTER DELETE ON routes BEGIN \
+            DELETE FROM route_search WHERE rowid = old.id; END").await?;
+        db.execute_unprepared(&format!("CREATE TRIGGER IF NOT EXISTS send_search_insert AFTER INSERT ON sends BEGIN \
+            INSERT INTO send_search (rowid, notes, partners) VALUES (new.id, COALESCE(new.notes, ''), {}); END", send_partners("new.id"))).await?;
+        db.execute_unprepared(&format!("CREATE TRIGGER IF NOT EXISTS send_search_update AFTER UPDATE ON sends BEGIN \
+            DELETE FROM send_search WHERE rowid = old.id; \
+            INSERT INTO send_search (rowid, notes, partners) VALUES (new.id, COALESCE(new.notes, ''), {}); END", send_partners("new.id"))).await?;
+        db.execute_unprepared("CREATE TRIGGER IF NOT EXISTS send_search_delete AFTER DELETE ON sends BEGIN \
+            DELETE FROM send_search WHERE rowid = old.id; END").await?;
+        // Partners are linked to a send after it's added, and can be renamed or removed later
+        db.execute_unprepared(&format!("CREATE TRIGGER IF NOT EXISTS send_partner_search_insert AFTER INSERT ON send_partners BEGIN \
+            UPDATE send_search SET partners = {} WHERE rowid = new.send; END", send_partners("new.send"))).await?;
+        db.execute_unprepared(&format!("CREATE TRIGGER IF NOT EXISTS send_partner_search_delete AFTER DELETE ON send_partners BEGIN \
+            UPDATE send_search SET partners = {} WHERE rowid = old.send; END", send_partners("old.send"))).await?;
+        db.execute_unprepared(&format!("CREATE TRIGGER IF NOT EXISTS partner_search_update AFTER UPDATE OF name ON partners BEGIN \
+            UPDATE send_search SET partners = {} WHERE rowid IN (SELECT send FROM send_partners WHERE partner = new.id); END", send_partners("send_search.rowid"))).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DbBackend::Sqlite {
+            return Ok(());
+        }
+        let db = manager.get_connection();
+        for trigger in [
+            "route_search_insert", "route_search_update", "route_search_delete",
+            "send_search_insert", "send_search_update", "send_search_delete",
+            "send_partner_search_insert", "send_partner_search_delete", "partner_search_update",
+        ] {
+            db.execute_unprepared(&format!("DROP TRIGGER IF EXISTS {trigger}")).await?;
+        }
+        db.execute_unprepared("DROP TABLE IF EXISTS send_search").await?;
+        db.execute_unprepared("DROP TABLE IF EXISTS route_search").await?;
+        Ok(())
+    }
+}
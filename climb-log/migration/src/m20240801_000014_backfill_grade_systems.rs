@@ -0,0 +1,147 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20220101_000001_create_grades_table::Grades;
+
+pub struct Migration;
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20240801_000014_backfill_grade_systems"
+    }
+}
+
+/// Columns filled in from the Yosemite grade, in the order of `TALL_EQUIVALENTS`.
+pub const TALL_COLUMNS: [&str; 7] = ["french", "uiaa", "british", "ewbank", "saxon", "brazilian", "kurtyka"];
+
+/// Each Yosemite grade and its equivalents in `TALL_COLUMNS`, from the app's conversion table when this was written.
+/// Kept as data so the migration does the same thing however the conversions change later.
+pub const TALL_EQUIVALENTS: [(&str, [&str; 7]); 38] = [
+    ("1", ["1", "I", "M", "1", "I", "I", "I"]),
+    ("2", ["1", "I", "M", "2", "I", "I", "I"]),
+    ("3", ["1", "I", "M", "3", "I", "I", "I"]),
+    ("4", ["1", "I", "M", "4", "I", "I", "I"]),
+    ("5.0", ["1", "I", "M", "5", "I", "I", "I"]),
+    ("5.1", ["1", "I", "M", "7", "II", "I", "I"]),
+    ("5.2", ["1", "II", "D", "9", "II", "Isup", "II"]),
+    ("5.3", ["2", "III", "D", "10", "III", "II", "II"]),
+    ("5.4", ["3", "IV-", "VD", "11", "III", "IIsup", "III"]),
+    ("5.5", ["4b", "IV+", "HVD", "12", "IV", "III", "IV"]),
+    ("5.6", ["5a", "V-", "S 4a", "13", "V", "IIIsup", "IV+"]),
+    ("5.7", ["5b", "V", "HS 4b", "14", "V", "IV", "V"]),
+    ("5.8", ["5c", "V+", "VS 4c", "15", "VI", "IVsup", "V+"]),
+    ("5.9", ["6a-", "VI-", "VS 5a", "16", "VIIa", "Vsup", "VI"]),
+    ("5.10a", ["6a", "VI", "HVS 5a", "17", "VIIb", "VI", "VI+"]),
+    ("5.10b", ["6a+", "VI+", "E1 5a", "18", "VIIc", "VIsup", "VI.1"]),
+    ("5.10c", ["6b-", "VII-", "E1 5c", "18", "VIIIa", "7a", "VI.1"]),
+    ("5.10d", ["6b+", "VII", "E2 5b", "19", "VIIIb", "7b", "VI.1+"]),
+    ("5.11a", ["6c-", "VII+", "E2 6a", "20", "VIIIc", "7c", "VI.2"]),
+    ("5.11b", ["6c+", "VII+", "E3 5c", "21", "IXa", "8a", "VI.2+"]),
+    ("5.11c", ["7a", "VIII-", "E3 6a", "21", "IXb", "8b", "VI.2+"]),
+    ("5.11d", ["7a+", "VIII", "E4 6a", "22", "IXc", "8c", "VI.3"]),
+    ("5.12a", ["7b", "VIII+", "E4 6b", "23", "Xa", "9a", "VI.3+"]),
+    ("5.12b", ["7b+", "VIII+", "E5 6a", "24", "Xb", "9b", "VI.4"]),
+    ("5.12c", ["7c-", "IX-", "E5 6c", "25", "Xb", "9c", "VI.4+"]),
+    ("5.12d", ["7c+", "IX", "E6 6b", "25", "Xc", "10a", "VI.5"]),
+    ("5.13a", ["8a-", "IX", "E6 6c", "26", "XIa", "10b", "VI.5+"]),
+    ("5.13b", ["8a", "IX+", "E7 6c", "27", "XIa", "10c", "VI.6"]),
+    ("5.13c", ["8a+", "X-", "E7 7a", "28", "XIb", "11a", "VI.6+"]),
+    ("5.13d", ["8b-", "X", "E8 6c", "29", "XIc", "11b", "VI.7"]),
+    ("5.14a", ["8b+", "X+", "E8 7a", "30", "XIc", "11c", "VI.7"]),
+    ("5.14b", ["8c", "XI-", "E9 7a", "31", "XIIa", "12a", "VI.7+"]),
+    ("5.14c", ["8c+", "XI-", "E9 7b", "32", "XIIb", "12b", "VI.8"]),
+    ("5.14d", ["9a-", "XI", "E10 7b", "33", "XIIb", "12c", "VI.8+"]),
+    ("5.15a", ["9a+", "XI+", "E10 7b", "34", "XIIc", "12c", "VI.8+"]),
+    ("5.15b", ["9b", "XII-", "E11 7b", "35", "XIIc", "12c", "VI.9"]),
+    ("5.15c", ["9b+", "XII", "E11 7b", "36", "XIIc", "12c", "VI.9"]),
+    ("5.15d", ["9c", "XII+", "E11 7b", "38", "XIIc", "12c", "VI.9"]),
+];
+
+/// Each Hueco grade and its Font equivalent.
+pub const BOULDER_EQUIVALENTS: [(&str, &str); 55] = [
+    ("VB", "2+"),
+    ("V0-", "4-"),
+    ("V0", "4"),
+    ("V0+", "4"),
+    ("V1-", "5-"),
+    ("V1", "5"),
+    ("V1+", "5"),
+    ("V2-", "5+"),
+    ("V2", "5+"),
+    ("V2+", "5+"),
+    ("V3-", "6A"),
+    ("V3", "6A"),
+    ("V3+", "6A+"),
+    ("V4-", "6B-"),
+    ("V4", "6B"),
+    ("V4+", "6B+"),
+    ("V5-", "6C-"),
+    ("V5", "6C"),
+    ("V5+", "6C+"),
+    ("V6-", "7A-"),
+    ("V6", "7A"),
+    ("V6+", "7A"),
+    ("V7-", "7A+"),
+    ("V7", "7A+"),
+    ("V7+", "7A+"),
+    ("V8-", "7B"),
+    ("V8", "7B"),
+    ("V8+", "7B+"),
+    ("V9-", "7C-"),
+    ("V9", "7C"),
+    ("V9+", "7C"),
+    ("V10-", "7C+"),
+    ("V10", "7C+"),
+    ("V10+", "8A-"),
+    ("V11-", "8A-"),
+    ("V11", "8A"),
+    ("V11+", "8A"),
+    ("V12-", "8A+"),
+    ("V12", "8A+"),
+    ("V12+", "8B-"),
+    ("V13-", "8B-"),
+    ("V13", "8B"),
+    ("V13+", "8B"),
+    ("V14-", "8B+"),
+    ("V14", "8B+"),
+    ("V14+", "8C-"),
+    ("V15-", "8C-"),
+    ("V15", "8C"),
+    ("V15+", "8C"),
+    ("V16-", "8C+"),
+    ("V16", "8C+"),
+    ("V16+", "9A-"),
+    ("V17-", "9A-"),
+    ("V17", "9A"),
+    ("V17+", "9A+"),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // Grades added before the newer systems existed only have the systems picked when they were added
+    // Fill the empty ones in from the Yosemite or Hueco grade, anything already set is left alone
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        for (yosemite, equivalents) in TALL_EQUIVALENTS {
+            for (column, grade) in TALL_COLUMNS.into_iter().zip(equivalents) {
+                db.execute(db.get_database_backend().build(&fill(Grades::Yosemite, yosemite, column, grade))).await?;
+            }
+        }
+        for (hueco, font) in BOULDER_EQUIVALENTS {
+            db.execute(db.get_database_backend().build(&fill(Grades::Hueco, hueco, "font", font))).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // Backfilled grades can't be told apart from picked ones, and are right either way, so they stay
+        Ok(())
+    }
+}
+
+fn fill(from: Grades, grade: &str, column: &str, equivalent: &str) -> UpdateStatement {
+    Query::update()
+        .table(Grades::Table)
+        .value(Alias::new(column), equivalent)
+        .and_where(Expr::col(from).eq(grade))
+        .and_where(Expr::col(Alias::new(column)).is_null())
+        .to_owned()
+}
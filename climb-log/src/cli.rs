@@ -0,0 +1,512 @@
+use std::{collections::HashSet, fmt::Write, path::PathBuf};
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand};
+use itertools::Itertools;
+use sea_orm::DbErr;
+use serde::Serialize;
+use crate::server;
+use climb_log_core::climbing::{SendType, Style};
+use climb_log_core::entry::{self, NewRoute, NewSend, NewSession};
+use climb_log_core::export::{ExportedRoute, ExportedSend, LogbookExport};
+use climb_log_core::filter::{Filter, SavedFilters};
+use climb_log_core::logbook::LogbookData;
+use climb_log_core::recap::Recap;
+use climb_log_core::search::{self, SearchFilters};
+use climb_log_core::routes_db::{entities::climbers::Model as ClimberModel, RoutesDb};
+use climb_log_core::settings::Settings;
+use climb_log_core::store::LogbookStore;
+
+// Headless access to the logbook, for scripts and use over SSH
+// Commands share the app's database and settings, so grades and lengths are typed and shown the same way
+
+#[derive(Parser)]
+#[command(name = "climb-log", version, about = "Ascent climbing log, opens the app when run without a command")]
+pub struct Cli {
+    #[command(flatten)]
+    pub options: Options,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Args)]
+pub struct Options {
+    /// Print results as JSON instead of text
+    #[arg(long, global = true)]
+    pub json: bool,
+    /// Database url to use instead of the app's own, e.g. `sqlite:./other.db?mode=rwc` or `postgres://localhost/team_logbook`.
+    /// Defaults to the `CLIMB_LOG_DATABASE` environment variable when that's set
+    #[arg(long, global = true)]
+    pub database: Option<String>,
+    /// Climber to log and show sends for, defaults to the one picked in the app
+    #[arg(long, global = true)]
+    pub climber: Option<String>,
+    /// Only show routes, history and stats matching a filter like `grade>=5.11a style:trad after:2024-01-01`, or @name for a saved one
+    #[arg(long, global = true)]
+    pub filter: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Add a route or boulder problem
+    AddRoute(AddRouteArgs),
+    /// List every route
+    Routes,
+    /// Log one or more sends as a session
+    Log(LogArgs),
+    /// Show everything logged on a date (YYYY-MM-DD), or every send matching --filter
+    History { date: Option<NaiveDate> },
+    /// Print the stats page
+    Stats {
+        /// Compare every climber side by side instead
+        #[arg(long)]
+        team: bool,
+    },
+    /// List the climbers sharing the logbook
+    Climbers,
+    /// Add a climber to the logbook
+    AddClimber { name: String },
+    /// Write the routes and the climber's sends to a JSON file
+    Export {
+        /// Include every climber's sends
+        #[arg(long)]
+        team: bool,
+        path: PathBuf,
+    },
+    /// Add everything in a JSON export to the logbook, routes that already exist are kept.
+    /// Sends from exports without climbers go to the climber
+    Import { path: PathBuf },
+    /// Write a recap of a date range as an HTML file
+    Recap { from: NaiveDate, to: NaiveDate, path: PathBuf },
+    /// Find routes by name or crag, and sends by notes or partners
+    Search(SearchArgs),
+    /// List the saved filters
+    Filters,
+    /// Save a filter to use as --filter @name, replacing one already called that
+    SaveFilter { name: String, filter: String },
+    /// Remove a saved filter
+    RemoveFilter { name: String },
+    /// Serve the logbook as a JSON API on localhost
+    Serve {
+        #[arg(long, default_value_t = server::DEFAULT_PORT)]
+        port: u16,
+    },
+}
+
+#[derive(Args)]
+pub struct AddRouteArgs {
+    name: String,
+    /// Grade in the preferred grading system or Yosemite/Hueco, slash grades and ranges like 5.10c/d work too
+    #[arg(long)]
+    grade: String,
+    /// Length with a unit like 30m or 100ft, bare numbers use the units setting
+    #[arg(long)]
+    length: String,
+    #[arg(long, default_value_t = 1)]
+    pitches: i32,
+    #[arg(long)]
+    location: Option<String>,
+    /// Style of the route, repeat for more than one
+    #[arg(long = "style", required = true)]
+    styles: Vec<String>,
+    /// Add a boulder problem instead of a roped route
+    #[arg(long)]
+    boulder: bool,
+    /// Protection rating: G, PG, PG-13, R or X
+    #[arg(long)]
+    danger: Option<String>,
+}
+
+#[derive(Args)]
+pub struct LogArgs {
+    /// Names of the routes climbed, each one is logged as a send
+    #[arg(required = true)]
+    routes: Vec<String>,
+    /// Day of the session, today if left out
+    #[arg(long)]
+    date: Option<NaiveDate>,
+    /// Send type, like onsight, flash, redpoint or attempt
+    #[arg(long = "type", default_value = "Onsight")]
+    send_type: String,
+    #[arg(long, default_value_t = 1)]
+    attempts: i32,
+    #[arg(long)]
+    notes: Option<String>,
+    /// Partner along for the sends, repeat for more than one. New names are added as partners
+    #[arg(long = "partner")]
+    partners: Vec<String>,
+    /// How the grade felt: soft, hard, or your own grade
+    #[arg(long)]
+    felt: Option<String>,
+    /// Add to an existing session instead of starting a new one
+    #[arg(long)]
+    session: Option<i32>,
+}
+
+#[derive(Args)]
+pub struct SearchArgs {
+    /// Words to look for, each one as the start of a word
+    #[arg(required = true)]
+    words: Vec<String>,
+    /// Easiest grade, in the preferred grading system or any other
+    #[arg(long)]
+    grade_from: Option<String>,
+    /// Hardest grade, in the preferred grading system or any other
+    #[arg(long)]
+    grade_to: Option<String>,
+    /// Only routes with this style, repeat for any of several
+    #[arg(long = "style")]
+    styles: Vec<String>,
+    /// Only sends of this type, repeat for any of several
+    #[arg(long = "type")]
+    send_types: Vec<String>,
+    /// First day to search sends from
+    #[arg(long)]
+    from: Option<NaiveDate>,
+    /// Last day to search sends to
+    #[arg(long)]
+    to: Option<NaiveDate>,
+}
+
+#[derive(Serialize)]
+struct Saved {
+    path: PathBuf,
+}
+
+#[derive(Serialize)]
+struct NamedFilter {
+    name: String,
+    filter: String,
+}
+
+#[allow(clippy::needless_pass_by_value)] // Handed to map_err
+pub(crate) fn db_error(error: DbErr) -> String {
+    format!("database error: {error}")
+}
+
+impl Options {
+    fn output<T: Serialize>(&self, value: &T, text: impl FnOnce() -> String) -> Result<String, String> {
+        // What a command prints, as JSON or as text
+        if self.json {
+            serde_json::to_string_pretty(value).map_err(|error| error.to_string())
+        } else {
+            Ok(text())
+        }
+    }
+}
+
+impl Command {
+    /// Runs the command on the app's database or the one given, returning what to print.
+    pub async fn run(self, options: &Options) -> Result<String, String> {
+        let db = match &options.database {
+            Some(url) => RoutesDb::open(url).await,
+            None => RoutesDb::new().await,
+        }.map_err(db_error)?;
+        self.run_on(db, options).await
+    }
+
+    async fn run_on(self, db: RoutesDb, options: &Options) -> Result<String, String> {
+        let mut settings = Settings::load(db.clone()).await.unwrap_or_default();
+        if let Some(name) = &options.climber {
+            let climbers = db.clone().get_all_climbers().await.map_err(db_error)?;
+            settings.climber = climbers.iter().find(|climber| climber.name.eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| format!("no climber named {name}, add them with add-climber"))?.id;
+        }
+        let saved_filters = SavedFilters::load(db.clone()).await.map_err(db_error)?;
+        let filter = options.filter.as_deref().map(|text| saved_filters.resolve(text, &settings)).transpose().map_err(|error| error.to_string())?;
+
+        match self {
+            Command::AddRoute(args) => add_route(db, &settings, options, args).await,
+            Command::Routes => {
+                let mut data = LogbookData::load(db.clone()).await.map_err(db_error)?;
+                if let Some(filter) = &filter {
+                    let ids: HashSet<i32> = db.filter_routes(filter, settings.climber).await.map_err(db_error)?.iter().map(|(route, _)| route.id).collect();
+                    data.routes.retain(|(route, _)| ids.contains(&route.id));
+                }
+                let logbook = data.logbook(&settings);
+                let exported: Vec<ExportedRoute> = data.routes.iter().map(|(route, grade)| ExportedRoute::new(route, grade, &data.style_names(route.id))).collect();
+                options.output(&exported, || data.routes.iter().map(|(route, grade)| format!(
+                    "{}: {}, {}, {}, {} pitches, at {}, styles: {}",
+                    route.name,
+                    logbook.grade_label(route, grade),
+                    route.discipline,
+                    settings.length(route.length),
+                    route.pitches,
+                    route.location.as_deref().unwrap_or("Unknown"),
+                    data.style_names(route.id).join(", "),
+                )).join("\n"))
+            }
+            Command::Log(args) => log_sends(db, settings.climber, options, args).await,
+            Command::History { date } => show_history(db, &settings, options, date, filter.as_ref()).await,
+            Command::Stats { team: false } => {
+                let mut data = LogbookData::load(db.clone()).await.map_err(db_error)?.for_climber(settings.climber);
+                keep_filtered_sends(db, &mut data, filter.as_ref(), settings.climber).await?;
+                let summary = data.summary(&settings);
+                options.output(&data.stats(&settings), || summary.iter().map(|(label, value)| format!("{label}: {value}")).join("\n"))
+            }
+            Command::Stats { team: true } if filter.is_some() => Err("--filter only works on one climber's stats, not with --team".to_string()),
+            Command::Stats { team: true } => team_stats(db, &settings, options).await,
+            Command::Climbers => list_climbers(db, &settings, options).await,
+            Command::AddClimber { name } => {
+                let name = name.trim().to_string();
+                if name.is_empty() {
+                    return Err("climbers need a name".to_string());
+                }
+                let id = db.add_climber(&name).await.map_err(db_error)?;
+                options.output(&ClimberModel { id, name: name.clone() }, || format!("Added {name}"))
+            }
+            Command::Export { team, path } => {
+                let export = LogbookExport::build(db, (!team).then_some(settings.climber)).await.map_err(db_error)?;
+                let json = export.to_json().map_err(|error| error.to_string())?;
+                std::fs::write(&path, json).map_err(|error| format!("could not write {}: {error}", path.display()))?;
+                options.output(&Saved { path: path.clone() }, || format!("Exported {} routes and {} sends to {}", export.routes.len(), export.sends.len(), path.display()))
+            }
+            Command::Import { path } => {
+                let json = std::fs::read_to_string(&path).map_err(|error| format!("could not read {}: {error}", path.display()))?;
+                let export = LogbookExport::from_json(&json).map_err(|error| format!("{} is not a logbook export: {error}", path.display()))?;
+                let summary = export.import(db, settings.climber).await.map_err(db_error)?;
+                options.output(&summary, || format!(
+                    "Added {} routes ({} already there), {} sessions and {} sends",
+                    summary.routes_added, summary.routes_kept, summary.sessions_added, summary.sends_added,
+                ))
+            }
+            Command::Recap { from, to, path } => {
+                if from > to {
+                    return Err("the start date must be before the end date".to_string());
+                }
+                let data = LogbookData::load(db).await.map_err(db_error)?.for_climber(settings.climber);
+                let logbook = data.logbook(&settings);
+                let recap = Recap::build(from, to, &data.sends, &logbook.tall_samples(), &logbook.boulder_samples(), &data.routes,
+                    &data.partners, &data.send_partners, (settings.rope_system, settings.boulder_system), settings.units);
+                std::fs::write(&path, recap.to_html()).map_err(|error| format!("could not write {}: {error}", path.display()))?;
+                options.output(&Saved { path: path.clone() }, || format!("Saved {} to {}", recap.title(), path.display()))
+            }
+            Command::Search(args) => search_logbook(db, &settings, options, args).await,
+            Command::Filters => options.output(&saved_filters.0, || {
+                if saved_filters.0.is_empty() {
+                    return "No saved filters, add one with save-filter".to_string();
+                }
+                saved_filters.0.iter().map(|(name, filter)| format!("@{name}: {filter}")).join("\n")
+            }),
+            Command::SaveFilter { name, filter } => save_filter(db, &settings, options, saved_filters, &name, &filter).await,
+            Command::RemoveFilter { name } => remove_filter(db, options, saved_filters, &name).await,
+            Command::Serve { port } => server::serve(db, settings, port).await.map(|()| String::new()).map_err(|error| format!("server stopped: {error}")),
+        }
+    }
+}
+
+async fn add_route(db: RoutesDb, settings: &Settings, options: &Options, args: AddRouteArgs) -> Result<String, String> {
+    let new_route = NewRoute {
+        name: args.name,
+        grade: args.grade,
+        length: args.length,
+        pitches: args.pitches,
+        location: args.location,
+        styles: args.styles,
+        boulder: args.boulder,
+        danger: args.danger,
+    };
+    let added = new_route.add(db, settings).await.map_err(|error| error.to_string())?;
+    let grade = match &added.route.grade_high {
+        Some(high) => format!("{} to {high}", added.grade),
+        None => added.grade.clone(),
+    };
+    options.output(&added, || format!("Added {} ({grade})", added.route.name))
+}
+
+async fn show_history(db: RoutesDb, settings: &Settings, options: &Options, day: Option<NaiveDate>, filter: Option<&Filter>) -> Result<String, String> {
+    // Sends on the day, matching the filter if there is one, or every matching send without a day
+    if day.is_none() && filter.is_none() {
+        return Err("give a date, or a --filter to show every send matching it".to_string());
+    }
+    let mut logbook = LogbookData::load(db.clone()).await.map_err(db_error)?.for_climber(settings.climber);
+    keep_filtered_sends(db, &mut logbook, filter, settings.climber).await?;
+    let date = day.map(|day| day.to_string());
+    let history: Vec<ExportedSend> = logbook.sends.iter()
+        .filter(|send| date.as_ref().is_none_or(|date| send.date == *date))
+        .map(|send| ExportedSend::from_data(send, &logbook))
+        .collect();
+    options.output(&history, || {
+        if history.is_empty() {
+            return match day {
+                Some(day) => format!("Nothing logged on {day}"),
+                None => "No sends match the filter".to_string(),
+            };
+        }
+        history.iter().map(|exported| {
+            let send = &exported.send;
+            let mut line = format!("Session {}: {}, {}, {} attempts", send.session, exported.route_name, send.r#type, send.attempts);
+            if day.is_none() {
+                line.insert_str(0, &format!("{}, ", send.date));
+            }
+            if !exported.partners.is_empty() {
+                let _ = write!(line, ", with {}", exported.partners.join(", "));
+            }
+            if let Some(felt) = &send.personal_grade {
+                let _ = write!(line, ", felt {felt}");
+            }
+            if let Some(notes) = &send.notes {
+                let _ = write!(line, ", notes: {notes}");
+            }
+            line
+        }).join("\n")
+    })
+}
+
+async fn save_filter(db: RoutesDb, settings: &Settings, options: &Options, mut saved: SavedFilters, name: &str, filter: &str) -> Result<String, String> {
+    saved.insert(name, filter, settings).map_err(|error| error.to_string())?;
+    saved.save(db).await.map_err(db_error)?;
+    let name = name.trim().to_string();
+    let filter = saved.0[&name].clone();
+    options.output(&NamedFilter { name: name.clone(), filter }, || format!("Saved @{name}"))
+}
+
+async fn remove_filter(db: RoutesDb, options: &Options, mut saved: SavedFilters, name: &str) -> Result<String, String> {
+    let name = name.trim().to_string();
+    let filter = saved.0.remove(&name).ok_or_else(|| format!("no saved filter named {name}"))?;
+    saved.save(db).await.map_err(db_error)?;
+    options.output(&NamedFilter { name: name.clone(), filter }, || format!("Removed @{name}"))
+}
+
+async fn keep_filtered_sends(db: RoutesDb, data: &mut LogbookData, filter: Option<&Filter>, climber: i32) -> Result<(), String> {
+    // Narrows the logbook down to the climber's sends matching the filter, routes stay for looking up names and grades
+    if let Some(filter) = filter {
+        let ids: HashSet<i32> = db.filter_sends(filter, climber).await.map_err(db_error)?.iter().map(|send| send.id).collect();
+        data.sends.retain(|send| ids.contains(&send.id));
+    }
+    Ok(())
+}
+
+async fn team_stats(db: RoutesDb, settings: &Settings, options: &Options) -> Result<String, String> {
+    // Every climber's stats, as an object keyed by name in JSON
+    let data = LogbookData::load(db).await.map_err(db_error)?;
+    let team = data.team(settings);
+    options.output(&data.team_stats(settings), || team.iter().map(|(name, summary)| {
+        let lines = summary.iter().map(|(label, value)| format!("  {label}: {value}")).join("\n");
+        format!("{name}\n{lines}")
+    }).join("\n\n"))
+}
+
+async fn list_climbers(db: RoutesDb, settings: &Settings, options: &Options) -> Result<String, String> {
+    let data = LogbookData::load(db).await.map_err(db_error)?;
+    options.output(&data.climbers, || data.climbers.iter().map(|climber| {
+        let sends = data.sends.iter().filter(|send| send.climber == climber.id).count();
+        let picked = if climber.id == settings.climber { " (current)" } else { "" };
+        format!("{}: {sends} sends{picked}", climber.name)
+    }).join("\n"))
+}
+
+async fn search_logbook(db: RoutesDb, settings: &Settings, options: &Options, args: SearchArgs) -> Result<String, String> {
+    let filters = SearchFilters {
+        grade_from: args.grade_from,
+        grade_to: args.grade_to,
+        styles: args.styles.iter().map(|style| entry::find_named(Style::iter(), "style", style)).collect::<Result<_, _>>().map_err(|error| error.to_string())?,
+        send_types: args.send_types.iter().map(|send_type| entry::find_named(SendType::iter(), "send type", send_type)).collect::<Result<_, _>>().map_err(|error| error.to_string())?,
+        from: args.from,
+        to: args.to,
+    };
+    let hits = search::search(db, &args.words.join(" "), &filters, settings.climber, settings).await.map_err(|error| error.to_string())?;
+    options.output(&hits, || {
+        if hits.is_empty() {
+            return "Nothing found".to_string();
+        }
+        // Matched words are starred, sends show their session to look up with history
+        hits.iter().map(|hit| {
+            let snippet: String = search::highlights(&hit.snippet).iter().map(|(text, matched)| if *matched { format!("*{text}*") } else { (*text).to_string() }).collect();
+            match hit.session {
+                Some(session) => format!("{} (session {session}): {snippet}", hit.title),
+                None => format!("{}: {snippet}", hit.title),
+            }
+        }).join("\n")
+    })
+}
+
+async fn log_sends(db: RoutesDb, climber: i32, options: &Options, args: LogArgs) -> Result<String, String> {
+    // Every route named gets a send with the same details
+    let session = NewSession {
+        date: args.date.map(|date| date.to_string()),
+        session: args.session,
+        climber: None,
+        sends: args.routes.iter().map(|route| NewSend {
+            route: route.clone(),
+            send_type: Some(args.send_type.clone()),
+            attempts: Some(args.attempts),
+            notes: args.notes.clone(),
+            partners: args.partners.clone(),
+            felt: args.felt.clone(),
+        }).collect(),
+    };
+    let logged = session.log(db, climber).await.map_err(|error| error.to_string())?;
+    options.output(&logged, || format!("Logged {} send{} in session {} on {}", logged.sends, if logged.sends == 1 { "" } else { "s" }, logged.session, logged.date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use climb_log_core::climbing::{Grade, Hueco, Yosemite};
+    use climb_log_core::conversion;
+    use serde_json::{json, Value};
+
+    async fn logbook() -> RoutesDb {
+        // Fresh in-memory logbook with a route and a boulder problem, commands share it through run_on
+        let db = RoutesDb::open("sqlite::memory:").await.unwrap();
+        for grade in [Grade::Yosemite(Yosemite::FiveTenA), Grade::Hueco(Hueco::V4)] {
+            db.clone().add_grade(conversion::grade_row(grade)).await.unwrap();
+        }
+        run(&db, &["add-route", "Arete", "--grade", "5.10a", "--length", "30m", "--style", "Sport", "--location", "Smith Rock"]).await.unwrap();
+        run(&db, &["add-route", "Blob", "--grade", "V4", "--length", "4m", "--style", "Boulder", "--boulder"]).await.unwrap();
+        db
+    }
+
+    async fn run(db: &RoutesDb, args: &[&str]) -> Result<String, String> {
+        let cli = Cli::try_parse_from(["climb-log"].iter().chain(args)).unwrap();
+        cli.command.unwrap().run_on(db.clone(), &cli.options).await
+    }
+
+    async fn json(db: &RoutesDb, args: &[&str]) -> Value {
+        serde_json::from_str(&run(db, &[args, &["--json"]].concat()).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn routes_as_json() {
+        let db = logbook().await;
+        let routes = json(&db, &["routes"]).await;
+        assert_eq!(routes.as_array().unwrap().len(), 2);
+        assert_eq!((routes[0]["name"].clone(), routes[0]["grade"].clone(), routes[0]["length"].clone()), (json!("Arete"), json!("5.10a"), json!(3000)));
+        assert_eq!(routes[0]["styles"], json!(["Sport"]));
+        assert_eq!(routes[1]["grade"], "V4");
+
+        let boulders = json(&db, &["routes", "--filter", "discipline:boulder"]).await;
+        assert_eq!(boulders.as_array().unwrap().iter().map(|route| route["name"].clone()).collect::<Vec<_>>(), [json!("Blob")]);
+        assert!(run(&db, &["routes", "--filter", "grade>>5.10a"]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn history_of_a_day() {
+        let db = logbook().await;
+        run(&db, &["log", "Arete", "--date", "2024-08-01", "--type", "redpoint", "--attempts", "3", "--partner", "Sam"]).await.unwrap();
+        run(&db, &["log", "Blob", "--date", "2024-08-02", "--type", "flash"]).await.unwrap();
+
+        let history = json(&db, &["history", "2024-08-01"]).await;
+        assert_eq!(history.as_array().unwrap().len(), 1);
+        assert_eq!((history[0]["route_name"].clone(), history[0]["partners"].clone()), (json!("Arete"), json!(["Sam"])));
+        assert_eq!(run(&db, &["history", "2024-08-01"]).await.unwrap(), "Session 1: Arete, Redpoint, 3 attempts, with Sam");
+        assert_eq!(run(&db, &["history", "2024-08-03"]).await.unwrap(), "Nothing logged on 2024-08-03");
+        assert_eq!(json(&db, &["history", "--filter", "type:flash"]).await[0]["route_name"], "Blob");
+        assert!(run(&db, &["history"]).await.is_err(), "needs a date or a filter");
+    }
+
+    #[tokio::test]
+    async fn stats_as_json() {
+        let db = logbook().await;
+        run(&db, &["log", "Arete", "Blob", "--date", "2024-08-01"]).await.unwrap();
+        run(&db, &["log", "Blob", "--date", "2024-08-02", "--type", "attempt"]).await.unwrap();
+
+        let stats = json(&db, &["stats"]).await;
+        assert_eq!((stats["total_sends"].clone(), stats["total_sessions"].clone()), (json!(3), json!(2)));
+        let boulders = json(&db, &["stats", "--filter", "discipline:boulder"]).await;
+        assert_eq!(boulders["total_sends"], 2);
+        let team = json(&db, &["stats", "--team"]).await;
+        assert!(team.as_object().unwrap().values().any(|stats| stats["total_sends"] == 3));
+        assert!(run(&db, &["stats", "--team", "--filter", "type:flash"]).await.is_err());
+    }
+}
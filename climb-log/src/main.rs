@@ -1,14 +1,29 @@
-#![allow(dead_code, unreachable_patterns)]
 #![warn(clippy::pedantic)]
 #![allow(clippy::match_same_arms)]
 
 mod ui;
+mod cli;
+mod server;
+use clap::Parser;
+use cli::Cli;
 use ui::MyApp;
 
 #[tokio::main]
 async fn main() {
 
-    MyApp::run().await;
+    // Run a command when given one, otherwise open the app
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Some(command) => command.run(&cli.options).await,
+        None => MyApp::run().await.map(|()| String::new()).map_err(cli::db_error),
+    };
+    match result {
+        Ok(output) if output.is_empty() => {}
+        Ok(output) => println!("{output}"),
+        Err(error) => {
+            eprintln!("Error: {error}");
+            std::process::exit(1);
+        }
+    }
 
 }
-
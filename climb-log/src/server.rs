@@ -0,0 +1,594 @@
+use std::{collections::{BTreeMap, HashSet}, net::Ipv4Addr, sync::Arc};
+use axum::{extract::{Path, Query, State}, http::StatusCode, response::{IntoResponse, Response}, routing::{get, post}, Json, Router};
+use sea_orm::DbErr;
+use schemars::{schema_for, JsonSchema};
+use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use climb_log_core::climbing::{Discipline, SendType, Style};
+use climb_log_core::entry::{self, EntryError, LoggedSession, NewRoute, NewSession};
+use climb_log_core::export::{ExportedRoute, ExportedSend};
+use climb_log_core::filter::{Filter, SavedFilters};
+use climb_log_core::logbook::{LogbookData, StatsSummary};
+use climb_log_core::search::{self, SearchFilters, SearchHit};
+use climb_log_core::routes_db::{entities::{climbers::Model as ClimberModel, grades::Model as GradeModel}, RoutesDb};
+use climb_log_core::settings::Settings;
+use climb_log_core::store::LogbookStore;
+
+// The logbook as a JSON API on localhost, for dashboards and phone shortcuts
+// Reads and writes through RoutesDb and works stats out with Logbook, the same as the app
+//
+// GET    /routes          ?name= &location= &discipline= &style= &filter= &offset= &limit=
+// POST   /routes          NewRoute, returns the added route
+// GET    /routes/:id
+// DELETE /routes/:id      Only routes with nothing logged on them
+// GET    /grades          ?offset= &limit=
+// GET    /climbers        ?offset= &limit=
+// POST   /climbers        {"name": ...}, returns the climber
+// GET    /sends           ?climber= &route= &session= &date= &from= &to= &type= &partner= &filter= &offset= &limit=
+// POST   /sessions        NewSession, returns the session id. Sends go to the app's climber unless it names one
+// GET    /sessions/:id
+// DELETE /sessions/:id
+// GET    /stats           ?climber= &filter=, the app's climber if left out
+// GET    /stats/team      Every climber's stats side by side
+// GET    /search          ?q= &climber= &grade_from= &grade_to= &style= &type= &from= &to= &offset= &limit=
+// GET    /schema          JSON schemas for the bodies, queries and results above, by type name
+//
+// filter= takes a filter expression like grade>=5.11a style:trad, or @name for a saved one
+// Lists come back as a Page, errors as {"error": "..."} with a 4xx or 5xx status
+
+pub const DEFAULT_PORT: u16 = 7878;
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+#[derive(Clone)]
+struct ApiState {
+    db: RoutesDb,
+    settings: Arc<Settings>, // Settings as they were when the server started
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: usize, // Matches before paging
+    pub offset: usize,
+    pub limit: usize,
+}
+
+fn page<T>(items: Vec<T>, offset: Option<usize>, limit: Option<usize>) -> Page<T> {
+    let total = items.len();
+    let offset = offset.unwrap_or(0);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    Page { items: items.into_iter().skip(offset).take(limit).collect(), total, offset, limit }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SessionView {
+    pub session: i32,
+    pub date: String,
+    pub sends: Vec<ExportedSend>,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+struct RouteQuery {
+    name: Option<String>, // Part of the name, any case
+    location: Option<String>, // Part of the location, any case
+    discipline: Option<String>, // Boulder or Tall Wall
+    style: Option<String>,
+    filter: Option<String>, // Terms on sends go by the app's climber
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+struct SendQuery {
+    climber: Option<String>, // Climber name, any case
+    route: Option<String>, // Route name, any case
+    session: Option<i32>,
+    date: Option<String>, // Exact day, YYYY-MM-DD
+    from: Option<String>, // First day, YYYY-MM-DD
+    to: Option<String>, // Last day, YYYY-MM-DD
+    #[serde(rename = "type")]
+    send_type: Option<String>,
+    partner: Option<String>, // Partner name, any case
+    filter: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+struct StatsQuery {
+    climber: Option<String>, // Climber name, any case
+    filter: Option<String>, // Only count sends matching it
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+struct SearchQuery {
+    q: String, // Words to find in route names, crags, notes and partners
+    climber: Option<String>, // Climber name, any case
+    grade_from: Option<String>, // Preferred grading system or any other
+    grade_to: Option<String>,
+    style: Option<String>,
+    #[serde(rename = "type")]
+    send_type: Option<String>,
+    from: Option<String>, // First day, YYYY-MM-DD
+    to: Option<String>, // Last day, YYYY-MM-DD
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct NewClimber {
+    pub name: String,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+struct PageQuery {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+struct ApiError(StatusCode, String);
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+impl From<EntryError> for ApiError {
+    fn from(error: EntryError) -> ApiError {
+        let status = match error {
+            EntryError::Invalid(_) => StatusCode::BAD_REQUEST,
+            EntryError::NotFound(_) => StatusCode::NOT_FOUND,
+            EntryError::Exists(_) => StatusCode::CONFLICT,
+            EntryError::Db(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError(status, error.to_string())
+    }
+}
+impl From<DbErr> for ApiError {
+    fn from(error: DbErr) -> ApiError {
+        ApiError::from(EntryError::Db(error))
+    }
+}
+
+pub fn router(db: RoutesDb, settings: Settings) -> Router {
+    Router::new()
+        .route("/routes", get(list_routes).post(add_route))
+        .route("/routes/:id", get(get_route).delete(remove_route))
+        .route("/grades", get(list_grades))
+        .route("/climbers", get(list_climbers).post(add_climber))
+        .route("/sends", get(list_sends))
+        .route("/sessions", post(log_session))
+        .route("/sessions/:id", get(get_session).delete(remove_session))
+        .route("/stats", get(stats))
+        .route("/stats/team", get(team_stats))
+        .route("/search", get(search_logbook))
+        .route("/schema", get(schema))
+        .with_state(ApiState { db, settings: Arc::new(settings) })
+}
+
+pub async fn serve(db: RoutesDb, settings: Settings, port: u16) -> std::io::Result<()> {
+    // Only listens on localhost, there's no authentication
+    let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await?;
+    println!("Serving the logbook on http://{}", listener.local_addr()?);
+    axum::serve(listener, router(db, settings)).await
+}
+
+async fn parse_filter(db: RoutesDb, text: Option<&str>, settings: &Settings) -> Result<Option<Filter>, ApiError> {
+    let Some(text) = text else { return Ok(None) };
+    let saved = SavedFilters::load(db).await?;
+    Ok(Some(saved.resolve(text, settings).map_err(EntryError::from)?))
+}
+
+fn contains(text: Option<&str>, part: &str) -> bool {
+    text.is_some_and(|text| text.to_lowercase().contains(&part.trim().to_lowercase()))
+}
+
+async fn list_routes(State(state): State<ApiState>, Query(query): Query<RouteQuery>) -> Result<Json<Page<ExportedRoute>>, ApiError> {
+    let discipline = query.discipline.as_deref().map(|discipline| entry::find_named([Discipline::Boulder, Discipline::TallWall].into_iter(), "discipline", discipline)).transpose()?;
+    let style = query.style.as_deref().map(|style| entry::find_named(Style::iter(), "style", style)).transpose()?;
+    let filtered: Option<HashSet<i32>> = match parse_filter(state.db.clone(), query.filter.as_deref(), &state.settings).await? {
+        Some(filter) => Some(state.db.clone().filter_routes(&filter, state.settings.climber).await?.iter().map(|(route, _)| route.id).collect()),
+        None => None,
+    };
+    let data = LogbookData::load(state.db).await?;
+    let routes = data.routes.iter()
+        .filter(|(route, _)| filtered.as_ref().is_none_or(|ids| ids.contains(&route.id)))
+        .filter(|(route, _)| query.name.as_deref().is_none_or(|name| contains(Some(&route.name), name)))
+        .filter(|(route, _)| query.location.as_deref().is_none_or(|location| contains(route.location.as_deref(), location)))
+        .filter(|(route, _)| discipline.is_none_or(|discipline| route.discipline == discipline.to_string()))
+        .filter(|(route, _)| style.is_none_or(|style| data.route_styles.get(&route.id).is_some_and(|styles| styles.contains(&style))))
+        .map(|(route, grade)| ExportedRoute::new(route, grade, &data.style_names(route.id)))
+        .collect();
+    Ok(Json(page(routes, query.offset, query.limit)))
+}
+
+async fn add_route(State(state): State<ApiState>, Json(new_route): Json<NewRoute>) -> Result<(StatusCode, Json<ExportedRoute>), ApiError> {
+    let added = new_route.add(state.db, &state.settings).await?;
+    Ok((StatusCode::CREATED, Json(added)))
+}
+
+async fn get_route(State(state): State<ApiState>, Path(id): Path<i32>) -> Result<Json<ExportedRoute>, ApiError> {
+    let data = LogbookData::load(state.db).await?;
+    let (route, grade) = data.routes.iter().find(|(route, _)| route.id == id).ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("no route with id {id}")))?;
+    Ok(Json(ExportedRoute::new(route, grade, &data.style_names(route.id))))
+}
+
+async fn remove_route(State(state): State<ApiState>, Path(id): Path<i32>) -> Result<StatusCode, ApiError> {
+    let data = LogbookData::load(state.db.clone()).await?;
+    if !data.routes.iter().any(|(route, _)| route.id == id) {
+        return Err(ApiError(StatusCode::NOT_FOUND, format!("no route with id {id}")));
+    }
+    if data.sends.iter().any(|send| send.route == id) {
+        return Err(ApiError(StatusCode::CONFLICT, "the route has sends logged on it, remove those sessions first".to_string()));
+    }
+    state.db.remove_route(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_grades(State(state): State<ApiState>, Query(query): Query<PageQuery>) -> Result<Json<Page<GradeModel>>, ApiError> {
+    let grades = state.db.get_all_grades().await?;
+    Ok(Json(page(grades, query.offset, query.limit)))
+}
+
+async fn list_climbers(State(state): State<ApiState>, Query(query): Query<PageQuery>) -> Result<Json<Page<ClimberModel>>, ApiError> {
+    let climbers = state.db.get_all_climbers().await?;
+    Ok(Json(page(climbers, query.offset, query.limit)))
+}
+
+async fn add_climber(State(state): State<ApiState>, Json(new_climber): Json<NewClimber>) -> Result<(StatusCode, Json<ClimberModel>), ApiError> {
+    let name = new_climber.name.trim().to_string();
+    if name.is_empty() {
+        return Err(ApiError(StatusCode::BAD_REQUEST, "climbers need a name".to_string()));
+    }
+    if state.db.clone().get_all_climbers().await?.iter().any(|climber| climber.name.eq_ignore_ascii_case(&name)) {
+        return Err(ApiError(StatusCode::CONFLICT, format!("there's already a climber named {name}")));
+    }
+    let id = state.db.add_climber(&name).await?;
+    Ok((StatusCode::CREATED, Json(ClimberModel { id, name })))
+}
+
+fn day(text: Option<&str>) -> Result<Option<NaiveDate>, ApiError> {
+    text.map(|text| NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+        .map_err(|_| ApiError(StatusCode::BAD_REQUEST, format!("{text} isn't a YYYY-MM-DD date")))).transpose()
+}
+
+async fn list_sends(State(state): State<ApiState>, Query(query): Query<SendQuery>) -> Result<Json<Page<ExportedSend>>, ApiError> {
+    let (on, from, to) = (day(query.date.as_deref())?, day(query.from.as_deref())?, day(query.to.as_deref())?);
+    let data = LogbookData::load(state.db.clone()).await?;
+    // A filter matches each climber's sends, and narrows down everyone's the same way
+    let mut filtered: Option<HashSet<i32>> = None;
+    if let Some(filter) = parse_filter(state.db.clone(), query.filter.as_deref(), &state.settings).await? {
+        let mut ids = HashSet::new();
+        for climber in &data.climbers {
+            ids.extend(state.db.clone().filter_sends(&filter, climber.id).await?.iter().map(|send| send.id));
+        }
+        filtered = Some(ids);
+    }
+    let same = |text: &str, wanted: &str| text.trim().eq_ignore_ascii_case(wanted.trim());
+    let sends = data.sends.iter()
+        .filter(|send| filtered.as_ref().is_none_or(|ids| ids.contains(&send.id)))
+        .map(|send| ExportedSend::from_data(send, &data))
+        .filter(|exported| query.climber.as_deref().is_none_or(|climber| same(&exported.climber_name, climber)))
+        .filter(|exported| query.route.as_deref().is_none_or(|route| same(&exported.route_name, route)))
+        .filter(|exported| query.session.is_none_or(|session| exported.send.session == session))
+        .filter(|exported| {
+            let sent = exported.send.date.parse::<NaiveDate>().ok();
+            on.is_none_or(|on| sent == Some(on)) && from.is_none_or(|from| sent >= Some(from)) && to.is_none_or(|to| sent.is_some_and(|sent| sent <= to))
+        })
+        .filter(|exported| query.send_type.as_deref().is_none_or(|send_type| same(&exported.send.r#type, send_type)))
+        .filter(|exported| query.partner.as_deref().is_none_or(|partner| exported.partners.iter().any(|name| same(name, partner))))
+        .collect();
+    Ok(Json(page(sends, query.offset, query.limit)))
+}
+
+async fn log_session(State(state): State<ApiState>, Json(session): Json<NewSession>) -> Result<(StatusCode, Json<LoggedSession>), ApiError> {
+    let logged = session.log(state.db, state.settings.climber).await?;
+    Ok((StatusCode::CREATED, Json(logged)))
+}
+
+async fn get_session(State(state): State<ApiState>, Path(id): Path<i32>) -> Result<Json<SessionView>, ApiError> {
+    let data = LogbookData::load(state.db).await?;
+    let sends: Vec<ExportedSend> = data.sends.iter().filter(|send| send.session == id).map(|send| ExportedSend::from_data(send, &data)).collect();
+    let day = sends.first().map(|exported| exported.send.date.clone()).ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("no session with id {id}")))?;
+    Ok(Json(SessionView { session: id, date: day, sends }))
+}
+
+async fn remove_session(State(state): State<ApiState>, Path(id): Path<i32>) -> Result<StatusCode, ApiError> {
+    if state.db.clone().get_session(id).await?.is_empty() {
+        return Err(ApiError(StatusCode::NOT_FOUND, format!("no session with id {id}")));
+    }
+    state.db.remove_session(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn stats(State(state): State<ApiState>, Query(query): Query<StatsQuery>) -> Result<Json<StatsSummary>, ApiError> {
+    let data = LogbookData::load(state.db.clone()).await?;
+    let climber = match query.climber.as_deref() {
+        Some(name) => data.climber_id(name).ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("no climber named {name}")))?,
+        None => state.settings.climber,
+    };
+    let mut data = data.for_climber(climber);
+    if let Some(filter) = parse_filter(state.db.clone(), query.filter.as_deref(), &state.settings).await? {
+        let ids: HashSet<i32> = state.db.filter_sends(&filter, climber).await?.iter().map(|send| send.id).collect();
+        data.sends.retain(|send| ids.contains(&send.id));
+    }
+    Ok(Json(data.stats(&state.settings)))
+}
+
+async fn team_stats(State(state): State<ApiState>) -> Result<Json<BTreeMap<String, StatsSummary>>, ApiError> {
+    // Keyed by climber name
+    let data = LogbookData::load(state.db).await?;
+    Ok(Json(data.team_stats(&state.settings)))
+}
+
+async fn search_logbook(State(state): State<ApiState>, Query(query): Query<SearchQuery>) -> Result<Json<Page<SearchHit>>, ApiError> {
+    let filters = SearchFilters {
+        grade_from: query.grade_from,
+        grade_to: query.grade_to,
+        styles: query.style.as_deref().map(|style| entry::find_named(Style::iter(), "style", style)).transpose()?.into_iter().collect(),
+        send_types: query.send_type.as_deref().map(|send_type| entry::find_named(SendType::iter(), "send type", send_type)).transpose()?.into_iter().collect(),
+        from: day(query.from.as_deref())?,
+        to: day(query.to.as_deref())?,
+    };
+    let climber = match query.climber.as_deref() {
+        Some(name) => state.db.clone().get_all_climbers().await?.iter().find(|climber| climber.name.eq_ignore_ascii_case(name.trim())).map(|climber| climber.id)
+            .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("no climber named {name}")))?,
+        None => state.settings.climber,
+    };
+    let hits = search::search(state.db, &query.q, &filters, climber, &state.settings).await?;
+    Ok(Json(page(hits, query.offset, query.limit)))
+}
+
+async fn schema() -> Json<serde_json::Map<String, serde_json::Value>> {
+    // Schemas are worked out per request, it's only asked for when writing a client
+    let schemas = [
+        ("NewRoute", schema_for!(NewRoute)),
+        ("NewSession", schema_for!(NewSession)),
+        ("LoggedSession", schema_for!(LoggedSession)),
+        ("NewClimber", schema_for!(NewClimber)),
+        ("ExportedRoute", schema_for!(ExportedRoute)),
+        ("ExportedSend", schema_for!(ExportedSend)),
+        ("SessionView", schema_for!(SessionView)),
+        ("StatsSummary", schema_for!(StatsSummary)),
+        ("RoutePage", schema_for!(Page<ExportedRoute>)),
+        ("SendPage", schema_for!(Page<ExportedSend>)),
+        ("ClimberPage", schema_for!(Page<ClimberModel>)),
+        ("RouteQuery", schema_for!(RouteQuery)),
+        ("SendQuery", schema_for!(SendQuery)),
+        ("StatsQuery", schema_for!(StatsQuery)),
+        ("SearchQuery", schema_for!(SearchQuery)),
+        ("PageQuery", schema_for!(PageQuery)),
+    ];
+    Json(schemas.into_iter().map(|(name, schema)| (name.to_string(), serde_json::to_value(schema).unwrap_or_default())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use climb_log_core::climbing::{Grade, Hueco, Yosemite};
+    use climb_log_core::conversion;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn app() -> Router {
+        // Fresh in-memory logbook with a few grades to add routes at
+        let db = RoutesDb::open("sqlite::memory:").await.unwrap();
+        for grade in [Grade::Yosemite(Yosemite::FiveTenA), Grade::Yosemite(Yosemite::FiveTenB), Grade::Hueco(Hueco::V4)] {
+            db.clone().add_grade(conversion::grade_row(grade)).await.unwrap();
+        }
+        router(db, Settings::default())
+    }
+
+    async fn call(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let request = Request::builder().method(method).uri(uri).header("content-type", "application/json");
+        let request = request.body(body.map_or_else(Body::empty, |body| Body::from(body.to_string()))).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    fn route(name: &str, grade: &str, boulder: bool) -> Value {
+        json!({ "name": name, "grade": grade, "length": "30m", "styles": [if boulder { "Boulder" } else { "Sport" }], "boulder": boulder, "location": "Smith Rock" })
+    }
+
+    #[tokio::test]
+    async fn routes_add_list_filter_and_remove() {
+        let app = app().await;
+        let (status, added) = call(&app, "POST", "/routes", Some(route("Arete", "5.10a/b", false))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(added["grade"], "5.10a");
+        assert_eq!(added["grade_high"], "5.10b");
+        assert_eq!(added["length"], 3000);
+        call(&app, "POST", "/routes", Some(route("Blob", "V4", true))).await;
+
+        let (status, listed) = call(&app, "GET", "/routes", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed["total"], 2);
+        let (_, boulders) = call(&app, "GET", "/routes?discipline=boulder", None).await;
+        assert_eq!(boulders["total"], 1);
+        assert_eq!(boulders["items"][0]["name"], "Blob");
+        let (_, paged) = call(&app, "GET", "/routes?limit=1&offset=1", None).await;
+        assert_eq!((paged["total"].clone(), paged["items"].as_array().unwrap().len()), (json!(2), 1));
+
+        let id = added["id"].as_i64().unwrap();
+        let (status, found) = call(&app, "GET", &format!("/routes/{id}"), None).await;
+        assert_eq!((status, found["name"].clone()), (StatusCode::OK, json!("Arete")));
+        let (status, _) = call(&app, "DELETE", &format!("/routes/{id}"), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, error) = call(&app, "GET", &format!("/routes/{id}"), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(error["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn bad_routes_are_rejected() {
+        let app = app().await;
+        let (status, _) = call(&app, "POST", "/routes", Some(route("Nowhere", "5.15d", false))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = call(&app, "POST", "/routes", Some(json!({ "name": "Odd", "grade": "5.10a", "length": "10m", "styles": ["Parkour"] }))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        call(&app, "POST", "/routes", Some(route("Arete", "5.10a", false))).await;
+        let (status, _) = call(&app, "POST", "/routes", Some(route("Arete", "5.10a", false))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn sessions_log_filter_and_count_in_stats() {
+        let app = app().await;
+        call(&app, "POST", "/routes", Some(route("Arete", "5.10a", false))).await;
+        call(&app, "POST", "/routes", Some(route("Blob", "V4", true))).await;
+        let session = json!({ "date": "2024-08-01", "sends": [
+            { "route": "Arete", "type": "redpoint", "attempts": 3, "partners": ["Sam"] },
+            { "route": "Blob", "type": "Flash" },
+        ] });
+        let (status, logged) = call(&app, "POST", "/sessions", Some(session)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!((logged["session"].clone(), logged["sends"].clone()), (json!(1), json!(2)));
+        let (_, second) = call(&app, "POST", "/sessions", Some(json!({ "date": "2024-09-01", "sends": [{ "route": "Arete" }] }))).await;
+        assert_eq!(second["session"], 2);
+
+        let (_, flashes) = call(&app, "GET", "/sends?type=flash", None).await;
+        assert_eq!(flashes["total"], 1);
+        let (_, with_sam) = call(&app, "GET", "/sends?partner=sam", None).await;
+        assert_eq!(with_sam["items"][0]["route_name"], "Arete");
+        let (_, august) = call(&app, "GET", "/sends?from=2024-08-01&to=2024-08-31", None).await;
+        assert_eq!(august["total"], 2);
+        for query in ["from=August", "to=2024-13-01", "date=yesterday"] {
+            let (status, _) = call(&app, "GET", &format!("/sends?{query}"), None).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{query} read as a date");
+        }
+        let (_, viewed) = call(&app, "GET", "/sessions/1", None).await;
+        assert_eq!(viewed["sends"].as_array().unwrap().len(), 2);
+
+        let (_, summary) = call(&app, "GET", "/stats", None).await;
+        assert_eq!((summary["total_sends"].clone(), summary["total_sessions"].clone()), (json!(3), json!(2)));
+        assert_eq!(summary["top_tall_wall_grade"], json!({ "grade": "5.10a", "route": "Arete" }));
+        assert!(summary["hardest_x_rated_lead"].is_null());
+
+        let (status, _) = call(&app, "DELETE", "/routes/1", None).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = call(&app, "DELETE", "/sessions/1", None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = call(&app, "GET", "/sessions/1", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = call(&app, "POST", "/sessions", Some(json!({ "sends": [{ "route": "Missing" }] }))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // Grades felt have to be grades for the route, and nothing is logged when one isn't
+        let (status, _) = call(&app, "POST", "/sessions", Some(json!({ "sends": [{ "route": "Arete", "felt": "5.10b" }] }))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        for felt in ["sandbag", "V4"] {
+            let (status, _) = call(&app, "POST", "/sessions", Some(json!({ "sends": [{ "route": "Blob" }, { "route": "Arete", "felt": felt }] }))).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{felt} logged on a tall wall");
+        }
+        let (_, summary) = call(&app, "GET", "/stats", None).await;
+        assert_eq!(summary["total_sends"], 2);
+    }
+
+    #[tokio::test]
+    async fn climbers_keep_their_own_sends_and_stats() {
+        let app = app().await;
+        call(&app, "POST", "/routes", Some(route("Arete", "5.10a", false))).await;
+        let (status, sam) = call(&app, "POST", "/climbers", Some(json!({ "name": "Sam" }))).await;
+        assert_eq!((status, sam["id"].clone()), (StatusCode::CREATED, json!(2)));
+        let (status, _) = call(&app, "POST", "/climbers", Some(json!({ "name": "sam" }))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (_, climbers) = call(&app, "GET", "/climbers", None).await;
+        assert_eq!((climbers["items"][0]["name"].clone(), climbers["total"].clone()), (json!("Me"), json!(2)));
+
+        call(&app, "POST", "/sessions", Some(json!({ "date": "2024-08-01", "sends": [{ "route": "Arete" }] }))).await;
+        call(&app, "POST", "/sessions", Some(json!({ "date": "2024-08-01", "climber": "Sam", "sends": [{ "route": "Arete" }, { "route": "Arete", "type": "Repeat" }] }))).await;
+        let (status, _) = call(&app, "POST", "/sessions", Some(json!({ "climber": "Nobody", "sends": [{ "route": "Arete" }] }))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (_, sams) = call(&app, "GET", "/sends?climber=sam", None).await;
+        assert_eq!((sams["total"].clone(), sams["items"][0]["climber_name"].clone()), (json!(2), json!("Sam")));
+        let (_, mine) = call(&app, "GET", "/stats", None).await;
+        assert_eq!(mine["total_sends"], 1);
+        let (_, theirs) = call(&app, "GET", "/stats?climber=Sam", None).await;
+        assert_eq!(theirs["total_sends"], 2);
+        let (_, team) = call(&app, "GET", "/stats/team", None).await;
+        assert_eq!((team["Me"]["total_sends"].clone(), team["Sam"]["total_sends"].clone()), (json!(1), json!(2)));
+    }
+
+    #[tokio::test]
+    async fn search_links_to_routes_and_sessions() {
+        let app = app().await;
+        call(&app, "POST", "/routes", Some(route("Arete", "5.10a", false))).await;
+        call(&app, "POST", "/routes", Some(route("Blob", "V4", true))).await;
+        let session = json!({ "date": "2024-08-01", "sends": [
+            { "route": "Arete", "type": "Onsight", "partners": ["Sam"], "notes": "Sam clipped the chains" },
+            { "route": "Blob", "type": "Flash", "notes": "Sloper start" },
+        ] });
+        call(&app, "POST", "/sessions", Some(session)).await;
+
+        let (status, found) = call(&app, "GET", "/search?q=smith", None).await;
+        assert_eq!((status, found["total"].clone()), (StatusCode::OK, json!(2)));
+        assert_eq!(found["items"][0]["kind"], "route");
+        let (_, sam) = call(&app, "GET", "/search?q=sam", None).await;
+        assert_eq!((sam["total"].clone(), sam["items"][0]["session"].clone()), (json!(1), json!(1)));
+        assert!(sam["items"][0]["snippet"].as_array().unwrap().contains(&json!({ "text": "Sam", "matched": true })));
+        let (_, flashes) = call(&app, "GET", "/search?q=smith&type=flash&grade_from=V1", None).await;
+        assert_eq!(flashes["items"][0]["title"], "Blob");
+        let (_, sport) = call(&app, "GET", "/search?q=smith&style=sport", None).await;
+        assert_eq!(sport["total"], 1);
+
+        let (status, _) = call(&app, "GET", "/search?q=smith&grade_from=9z", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = call(&app, "GET", "/search?q=smith&from=August", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn filters_narrow_routes_sends_and_stats() {
+        let app = app().await;
+        call(&app, "POST", "/routes", Some(route("Arete", "5.10a", false))).await;
+        call(&app, "POST", "/routes", Some(route("Crack", "5.10b", false))).await;
+        call(&app, "POST", "/routes", Some(route("Blob", "V4", true))).await;
+        let session = json!({ "date": "2024-08-01", "sends": [
+            { "route": "Arete", "type": "Onsight", "partners": ["Sam"] },
+            { "route": "Crack", "type": "Redpoint", "attempts": 4 },
+            { "route": "Blob", "type": "Flash" },
+        ] });
+        call(&app, "POST", "/sessions", Some(session)).await;
+        call(&app, "POST", "/sessions", Some(json!({ "date": "2024-09-01", "sends": [{ "route": "Crack", "type": "Repeat" }] }))).await;
+
+        let (status, hard) = call(&app, "GET", "/sends?filter=grade%3E%3D5.10b", None).await;
+        assert_eq!((status, hard["total"].clone()), (StatusCode::OK, json!(2)));
+        let (_, with_sam) = call(&app, "GET", "/sends?filter=with:sam%20type:onsight", None).await;
+        assert_eq!(with_sam["items"][0]["route_name"], "Arete");
+        let (_, routes) = call(&app, "GET", "/routes?filter=after:2024-09-01", None).await;
+        assert_eq!((routes["total"].clone(), routes["items"][0]["name"].clone()), (json!(1), json!("Crack")));
+        let (_, summary) = call(&app, "GET", "/stats?filter=-style:boulder", None).await;
+        assert_eq!(summary["total_sends"], 3);
+
+        let (status, error) = call(&app, "GET", "/sends?filter=colour:red", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(error["error"].as_str().unwrap().contains("colour"));
+        let (status, _) = call(&app, "GET", "/stats?filter=@nothing", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn schema_describes_the_bodies_and_results() {
+        let app = app().await;
+        let (status, schemas) = call(&app, "GET", "/schema", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let mut required = schemas["NewRoute"]["required"].as_array().unwrap().clone();
+        required.sort_by_key(ToString::to_string);
+        assert_eq!(required, [json!("grade"), json!("length"), json!("name"), json!("styles")]);
+        assert!(schemas["SendQuery"]["properties"]["type"].is_object(), "renamed fields go by their JSON name");
+        assert_eq!(schemas["RoutePage"]["properties"]["items"]["items"]["$ref"], "#/definitions/ExportedRoute");
+        assert_eq!(schemas["StatsSummary"]["properties"]["total_sends"]["type"], "integer");
+
+        // Every field of a listed route is in the schema, including the flattened route
+        call(&app, "POST", "/routes", Some(route("Arete", "5.10a", false))).await;
+        let (_, listed) = call(&app, "GET", "/routes", None).await;
+        let properties = &schemas["RoutePage"]["definitions"]["ExportedRoute"]["properties"];
+        for field in listed["items"][0].as_object().unwrap().keys() {
+            assert!(properties[field].is_object(), "{field} missing from the ExportedRoute schema");
+        }
+    }
+}
@@ -1,12 +1,26 @@
-use std::{collections::HashMap, sync::{Arc, Mutex, MutexGuard}};
+use std::{str::FromStr, sync::Arc};
 use tokio::runtime::Runtime;
+use sea_orm::DbErr;
 use itertools::Itertools;
 use eframe::{egui::{self, CentralPanel, ScrollArea}, App, run_native, NativeOptions};
-mod routes_db;
-use routes_db::{entities::{grades::Model as GradeModel, routes::Model as RouteModel, sends::Model as SendModel}, RoutesDb};
-mod climbing;
-use climbing::{Font, French, FullGrade, Hueco, SendType, Style, Uiaa, Yosemite};
-use chrono;
+use climb_log_core::{climbing, conversion, filter, logbook, rating, recap, routes_db, search, settings, stats, store::LogbookStore};
+use routes_db::{entities::{grades::Model as GradeModel, partners::Model as PartnerModel, routes::Model as RouteModel, send_partners::Model as SendPartnerModel, sends::Model as SendModel}, RoutesDb};
+use climbing::{Aid, Commitment, Danger, Discipline, FullGrade, Grade, GradeOpinion, Hueco, Ice, Ifas, Mixed, SendType, Style, Yosemite};
+use settings::{GradeMetric, RangeGrade, Settings, CONFIDENCE_LEVELS};
+use stats::GradeDisagreement;
+use rating::RatingPoint;
+use recap::Recap;
+use search::{MatchKind, SearchFilters};
+use filter::{Filter, SavedFilters};
+use logbook::{Logbook, TEAM_STATS};
+use conversion::{GradeSystem, LengthUnit};
+mod charts;
+use charts::{Band, Series};
+mod cache;
+use cache::{Cache, Data};
+
+// What the logbook pages need loaded before they can draw
+const LOGBOOK_DATA: [Data; 4] = [Data::Sends, Data::Routes, Data::RouteStyles, Data::Partners];
 
 
 
@@ -19,6 +33,7 @@ enum Page { // All the possible pages for the app to display
     AddRoute,
     RemoveRoute,
     SearchHome,
+    SearchLogbook,
     FindRoute,
     ViewRoute,
     ViewAllRoutes,
@@ -26,7 +41,14 @@ enum Page { // All the possible pages for the app to display
     RemoveSession,
     ViewSession,
     History,
+    Partners,
+    ViewPartner,
+    Crags,
+    Recap,
+    Convert,
     Stats,
+    Team,
+    Settings,
     Exit,
 }
 
@@ -34,7 +56,11 @@ enum Page { // All the possible pages for the app to display
 #[derive(Default, Clone)]
 struct RouteOptions { // All the info needed to add a route
     name: String,
-    grade: FullGrade,
+    grade: Option<Grade>, // Grade in the preferred system for the discipline, None until picked
+    range: bool, // Slash grade or range, from grade up to grade_high
+    grade_high: Option<Grade>,
+    grade_text: String, // Typed grade or range like "5.10c/d", fills in the pickers when it parses
+    grade_error: bool, // Typed grade didn't parse
     boulder: bool,
     sport: bool,
     trad: bool,
@@ -49,103 +75,245 @@ struct RouteOptions { // All the info needed to add a route
     length: i32,
     pitches: i32,
     location: String,
+    ice_grade: Option<Ice>, // Grades for the styles that need their own, only saved when the style is picked
+    mixed_grade: Option<Mixed>,
+    aid_grade: Option<Aid>,
+    commitment_grade: Option<Commitment>,
+    ifas_grade: Option<Ifas>,
+    danger: Option<Danger>, // Protection rating, tall walls only
     //notes: String,
 }
 
+impl RouteOptions {
+    fn apply_grade_text(&mut self, system: GradeSystem) -> bool {
+        // Fill in the grade pickers from the typed grade, false if it doesn't parse
+        let Ok(grades) = system.parse_range(self.grade_text.trim()) else { return false };
+        self.grade = Some(grades.low);
+        self.grade_high = Some(grades.high);
+        self.range = grades.low != grades.high;
+        true
+    }
+
+    fn grade_in(grade: Option<Grade>, system: GradeSystem) -> Grade {
+        // Picked grade carried over into the system, or a middling grade for a fresh pick
+        let fallback = if system.is_boulder() { Grade::Hueco(Hueco::default()) } else { Grade::Yosemite(Yosemite::default()) };
+        grade.and_then(|grade| conversion::convert(grade, system))
+            .or_else(|| conversion::convert(fallback, system))
+            .unwrap_or(fallback)
+    }
+}
+
 #[derive(Clone)]
 struct SendOptions { // All the info needed to log a send
     date: sea_orm::prelude::Date, //EGUI works really well with sea_orm's Date type so just using that
-    partner: String, // Partner's name, optional (but does not need to have an Option)
+    partners: Vec<i32>, // Ids of partners along for the send, can be empty
     send_type: SendType, // Type of send
+    opinion: GradeOpinion, // What the climber thought of the route's grade
     attempts: i32, // Number of attempts, i32 bc that's what sea_orm/sqlite uses
     notes: String, // Any notes
     route_name: String, // Name of route, should match with a route in database
-    route: Option<RouteModel>, // Route from database, fetched for the user
 }
 impl Default for SendOptions {
     fn default() -> Self {
         SendOptions {
             date: chrono::Utc::now().naive_utc().into(),
-            partner: String::new(),
+            partners: Vec::new(),
             send_type: SendType::Onsight,
             attempts: 1,
             notes: String::new(),
             route_name: String::new(),
-            route: None,
+            opinion: GradeOpinion::Agree,
         }
     }
 }
 
+#[derive(Default, Clone)]
+struct SearchOptions { // Search page's text and filters as they're picked
+    text: String,
+    grade_from: String, // Any grading system, blank for no bound
+    grade_to: String,
+    styles: Vec<Style>,
+    send_types: Vec<SendType>,
+    from: Option<chrono::NaiveDate>,
+    to: Option<chrono::NaiveDate>,
+}
+impl SearchOptions {
+    fn filters(&self) -> SearchFilters {
+        let bound = |text: &str| (!text.trim().is_empty()).then(|| text.trim().to_string());
+        SearchFilters {
+            grade_from: bound(&self.grade_from),
+            grade_to: bound(&self.grade_to),
+            styles: self.styles.clone(),
+            send_types: self.send_types.clone(),
+            from: self.from,
+            to: self.to,
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+struct FilterOptions { // Filter box on History, All Routes and Stats, shared between them
+    text: String, // As typed, @name for a saved filter
+    name: String, // Name to save the typed filter under
+    applied: Filter, // Last filter that parsed, empty when the box is
+    error: Option<String>, // Why the typed filter couldn't be saved, until it changes
+}
+
 pub struct MyApp { // The main app struct
     page: Page, // Current page to display
     route_options: RouteOptions, // Options for adding a route
     removal_name: String, // Name of route to remove
     find_name: String, // Name of route to find
-    all_routes: Arc<Mutex<Vec<(RouteModel, GradeModel)>>>, // All routes in the database, in an async context
-    database: Arc<RoutesDb>, // Database through RoutesDb
-    rt: Arc<Option<Runtime>>, // Runtime for async stuff
+    cache: Cache, // Everything loaded from the database, and changes going back to it
     should_quit: bool, // Quit flag
-    search_result: Arc<Mutex<Option<(RouteModel, GradeModel)>>>, // Result of a single search, async context
+    search_result: Option<(RouteModel, GradeModel)>, // Result of a single search
     viewing: Option<(RouteModel, GradeModel)>, // Route to view in more detail, out of the async
     send_options: SendOptions, // Options for logging a send
     session: Vec<SendOptions>, // All sends in a session
     session_id: i32, // Session id to search for
-    cur_session: Arc<Mutex<Vec<SendModel>>>, // Current session to view, async context
     view_session: Option<SendModel>, // Session to view in more detail, out of the async
-    add_grade: FullGrade, // Grade to add, with options for all types
+    add_grade: Grade, // Grade to add, the other systems are converted from it
     remove_grade: FullGrade, // Grade to remove, with options for all types
-    all_sessions_buffer: Arc<Mutex<Vec<SendModel>>>, // All sessions in the database, in an async context
-    all_sessions: Vec<SendModel>, // All sessions in the database, out of the async
-    routes_w_grades_buffer: Arc<Mutex<Vec<(RouteModel, GradeModel)>>>, // All grades in the database, in an async context
-    routes_w_grades: Vec<(RouteModel, GradeModel)>, // All grades in the database, out of the async
+    all_sessions: Vec<SendModel>, // The current climber's sessions, picked out of the cached sends
     search_date: sea_orm::prelude::Date, // Date to search for sessions
+    search: SearchOptions, // Text and filters on the Search Logbook page
+    filter: FilterOptions, // Filter on History, All Routes and Stats
+    saved_filters: SavedFilters, // Named filters, stored with the settings
+    settings: Settings, // Saved user settings
+    settings_options: Settings, // Settings being edited on the settings page
+    new_partner: String, // Name of a partner to add
+    viewing_partner: Option<PartnerModel>, // Partner to view in more detail
+    crag_filter: String, // Only show crags whose name contains this
+    crag_window_days: i32, // Only count sends from the last n days on the crags page, 0 for all time
+    recap_from: String, // First day of the recap, YYYY-MM-DD
+    recap_to: String, // Last day of the recap, YYYY-MM-DD
+    recap_path: String, // Where to export the recap to
+    recap_message: Option<String>, // Result of the last export
+    convert_grade: Grade, // Grade picked on the conversion page
+    danger_filter: Option<Danger>, // Only show routes at least this dangerous on the all routes page
+    new_climber: String, // Name of a climber to add
 }
 
 impl MyApp {
 
-    pub async fn new(rt: &Arc<Option<Runtime>>) -> Self { // Create a new app
+    pub async fn new(rt: &Arc<Option<Runtime>>) -> Result<Self, DbErr> { // Create a new app, or why the logbook couldn't be opened
+        let database = RoutesDb::new().await?;
+        let settings = Settings::load(database.clone()).await.unwrap_or_default();
+        let saved_filters = SavedFilters::load(database.clone()).await.unwrap_or_default();
         let mut app = MyApp { // Initialize all fields
             page: Page::Home,
             route_options: RouteOptions::default(),
             removal_name: String::new(),
             find_name: String::new(),
-            all_routes: Arc::new(Mutex::new(Vec::new())),
-            database: Arc::new(RoutesDb::new().await.expect("Failed to connect")),
-            rt: Arc::clone(rt),
+            cache: Cache::new(database, Arc::clone(rt)),
             should_quit: false,
-            search_result: Arc::new(Mutex::new(None)),
+            search_result: None,
             viewing: None,
             send_options: SendOptions::default(),
             session: Vec::new(),
             session_id: 0,
-            cur_session: Arc::new(Mutex::new(Vec::new())),
             view_session: None,
-            add_grade: FullGrade::default(),
+            add_grade: Grade::Yosemite(Yosemite::FiveTenA),
             remove_grade: FullGrade::default(),
-            all_sessions_buffer: Arc::new(Mutex::new(Vec::new())),
             all_sessions: Vec::new(),
-            routes_w_grades_buffer: Arc::new(Mutex::new(Vec::new())),
-            routes_w_grades: Vec::new(),
             search_date: chrono::Utc::now().naive_utc().into(),
+            search: SearchOptions::default(),
+            filter: FilterOptions::default(),
+            saved_filters,
+            settings_options: settings.clone(),
+            settings,
+            new_partner: String::new(),
+            viewing_partner: None,
+            crag_filter: String::new(),
+            crag_window_days: 0,
+            recap_from: String::new(),
+            recap_to: String::new(),
+            recap_path: String::new(),
+            recap_message: None,
+            convert_grade: Grade::Yosemite(Yosemite::default()),
+            danger_filter: None,
+            new_climber: String::new(),
         };
+        app.set_recap_year(chrono::Datelike::year(&chrono::Local::now().date_naive()));
         app.session.push(SendOptions::default());
-        app
+        Ok(app)
+    }
+
+    fn pick_climber_sends(&mut self) { // Keep the current climber's sends in step with the cached sends
+        let climber = self.settings.climber;
+        self.all_sessions = self.cache.sends.value().iter().filter(|send| send.climber == climber).cloned().collect();
+    }
+
+    fn routes_w_grades(&self) -> &[(RouteModel, GradeModel)] { // Every route with its grade, empty until loaded
+        self.cache.routes.value()
+    }
+
+    fn partners(&self) -> &[PartnerModel] {
+        self.cache.partners.value()
+    }
+
+    fn send_partners(&self) -> &[SendPartnerModel] {
+        self.cache.send_partners.value()
+    }
+
+    fn switch_climber(&mut self, climber: i32) { // Show and log another climber's sends, remembered for next time
+        self.settings.climber = climber;
+        self.settings_options.climber = climber;
+        self.cache.change(&[], move |db| async move { db.set_setting("climber", climber.to_string()).await });
+        // Pages showing sends pick the new climber's out of the cached sends, nothing to reload
+        self.pick_climber_sends();
+    }
+
+    fn profile_switcher(&mut self, ui: &mut eframe::egui::Ui) { // Climber picker shown in the header
+        self.cache.request(Data::Climbers);
+        let climbers = self.cache.climbers.value().clone();
+        let current = climbers.iter().find(|climber| climber.id == self.settings.climber).map_or_else(String::new, |climber| climber.name.clone());
+        let mut picked = self.settings.climber;
+        egui::ComboBox::from_id_source("Climber")
+            .selected_text(current)
+            .show_ui(ui, |ui| {
+                for climber in &climbers {
+                    ui.selectable_value(&mut picked, climber.id, &climber.name);
+                }
+            });
+        ui.label("Climber:");
+        if picked != self.settings.climber {
+            self.switch_climber(picked);
+        }
+    }
+
+    fn style_names(&self, route: i32) -> String { // Every style of a route, or "None"
+        self.cache.route_styles.value().get(&route)
+            .filter(|styles| !styles.is_empty())
+            .map_or("None".to_string(), |styles| styles.iter().map(std::string::ToString::to_string).join(", "))
+    }
+
+    fn partner_names(&self, send: i32) -> String { // Everyone along on a send, or "None"
+        let partners = self.partners();
+        let names: Vec<String> = self.send_partners().iter()
+            .filter(|link| link.send == send)
+            .filter_map(|link| partners.iter().find(|partner| partner.id == link.partner).map(|partner| partner.name.clone()))
+            .collect();
+        if names.is_empty() { "None".to_string() } else { names.join(", ") }
     }
 
-    pub async fn run(){ // Run the app, can call using MyApp::run().await; and will create from scratch
+    pub async fn run() -> Result<(), DbErr> { // Run the app, can call using MyApp::run().await; and will create from scratch
         let rt = Arc::new(Some(Runtime::new().unwrap())); // Set up async runtime to be able to communicate w/ db
-        let app = MyApp::new(&rt).await;
-        let win_option = NativeOptions::default(); //Using default options for now
-        // Run
-        let _ = run_native (
-            "Ascent Climbing Log",
-            win_option,
-            Box::new(|_cc| Ok(Box::new(app) as Box<dyn App + 'static>)),
-        );
+        // Nothing to show without a logbook, so its error goes back to be reported instead of opening a window
+        let opened = MyApp::new(&rt).await.map(|app| {
+            let win_option = NativeOptions::default(); //Using default options for now
+            // Run
+            let _ = run_native (
+                "Ascent Climbing Log",
+                win_option,
+                Box::new(|_cc| Ok(Box::new(app) as Box<dyn App + 'static>)),
+            );
+        });
         // Shutdown the runtime when quitting
         if let Some(runtime) = Arc::try_unwrap(rt).ok().and_then(|opt| opt) {
             runtime.shutdown_background();
         }
+        opened
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
@@ -158,6 +326,10 @@ impl MyApp {
                 ui.image(logo);
 
                 ui.heading("Ascent Climbing Log");
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    self.profile_switcher(ui);
+                });
             });
         });
 
@@ -199,10 +371,34 @@ impl MyApp {
                     self.page = Page::History;
                 }
                 ui.add_space(10.0);
+                if ui.button("Partners").clicked() {
+                    self.page = Page::Partners;
+                }
+                ui.add_space(10.0);
+                if ui.button("Crags").clicked() {
+                    self.page = Page::Crags;
+                }
+                ui.add_space(10.0);
+                if ui.button("Recap").clicked() {
+                    self.page = Page::Recap;
+                }
+                ui.add_space(10.0);
+                if ui.button("Convert").clicked() {
+                    self.page = Page::Convert;
+                }
+                ui.add_space(10.0);
                 if ui.button("Stats").clicked() {
                     self.page = Page::Stats;
                 }
                 ui.add_space(10.0);
+                if ui.button("Team").clicked() {
+                    self.page = Page::Team;
+                }
+                ui.add_space(10.0);
+                if ui.button("Settings").clicked() {
+                    self.page = Page::Settings;
+                }
+                ui.add_space(10.0);
                 if ui.button("Exit").clicked() {
                     self.page = Page::Exit;
                 }
@@ -235,95 +431,66 @@ impl MyApp {
                     if ui.button("Back").clicked() {
                         self.reset();
                     }
+                    ui.add_space(10.0);
+                    self.profile_switcher(ui);
+                    if let Some(error) = &self.cache.change_error {
+                        ui.colored_label(egui::Color32::RED, format!("Last change failed: {error}"));
+                    }
                 });
                 
             });
         });
     }
 
-    #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_add_grade(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) { //Should not be in end product
         self.header(&ctx);
         ui.add_space(20.0);
         ui.heading("Add Grade");
         ScrollArea::vertical().show(ui, |ui| {
-            ui.label("Tall Wall Grade:");
-            egui::ComboBox::from_label("Yosemite Grade")
-                .selected_text(format!("{}", self.add_grade.yosemite))
+            ui.label("Pick the grade in any system, the other systems for the same discipline are filled in from it.");
+            let mut system = conversion::system(self.add_grade);
+            egui::ComboBox::from_label("Grade System")
+                .selected_text(system.to_string())
                 .show_ui(ui, |ui| {
-                    Yosemite::iter().for_each(|grade| {
-                        ui.selectable_value(&mut self.add_grade.yosemite, grade, format!("{grade}"));
+                    GradeSystem::iter().for_each(|choice| {
+                        ui.selectable_value(&mut system, choice, choice.to_string());
                     });
                 });
-            
-            ui.separator();
-            
-            egui::ComboBox::from_label("French Grade")
-                .selected_text(format!("{}", self.add_grade.french))
-                .show_ui(ui, |ui| {
-                    French::iter().for_each(|grade| {
-                        ui.selectable_value(&mut self.add_grade.french, grade, format!("{grade}"));
-                    });
-                });
-            
-            ui.separator();
-            egui::ComboBox::from_label("UIAA Grade")
-                .selected_text(format!("{}", self.add_grade.uiaa))
-                .show_ui(ui, |ui| {
-                    Uiaa::iter().for_each(|grade| {
-                        ui.selectable_value(&mut self.add_grade.uiaa, grade, format!("{grade}"));
-                    });
-                });
-            
-            ui.separator();
-            if ui.button("Save Tall Wall Grade").clicked() {
-                let db = Arc::clone(&self.database);
-                let rt = Arc::clone(&self.rt);
-                let grade = self.add_grade;
-                rt.as_ref().as_ref().unwrap().spawn(async move {
-                    <RoutesDb as Clone>::clone(&db).add_grade(Some(grade.yosemite.to_string()), None, Some(grade.french.to_string()), None, Some(grade.uiaa.to_string())).await.expect("Error, could not add grade.");
-                });
-                self.reset();
+            if system != conversion::system(self.add_grade) { // Keep roughly the same difficulty when switching systems
+                self.add_grade = conversion::convert(self.add_grade, system).or_else(|| system.grades().first().copied()).unwrap_or(self.add_grade);
             }
 
-            ui.separator();
-
-            ui.label("Bouldering Grade:");
-
-            egui::ComboBox::from_label("Hueco Grade")
-                .selected_text(format!("{}", self.add_grade.hueco))
+            egui::ComboBox::from_label("Grade")
+                .selected_text(self.add_grade.to_string())
                 .show_ui(ui, |ui| {
-                    Hueco::iter().for_each(|grade| {
-                        ui.selectable_value(&mut self.add_grade.hueco, grade, format!("{grade}"));
+                    system.grades().into_iter().for_each(|grade| {
+                        ui.selectable_value(&mut self.add_grade, grade, grade.to_string());
                     });
                 });
-            
-            ui.separator();
-            
 
-            egui::ComboBox::from_label("Font Grade")
-                .selected_text(format!("{}", self.add_grade.font))
-                .show_ui(ui, |ui| {
-                    Font::iter().for_each(|grade| {
-                        ui.selectable_value(&mut self.add_grade.font, grade, format!("{grade}"));
-                    });
-                });
-            
             ui.separator();
+            let row = conversion::grade_row(self.add_grade);
+            for (name, grade) in [
+                (GradeSystem::Yosemite, &row.yosemite), (GradeSystem::French, &row.french), (GradeSystem::Uiaa, &row.uiaa),
+                (GradeSystem::British, &row.british), (GradeSystem::Ewbank, &row.ewbank), (GradeSystem::Saxon, &row.saxon),
+                (GradeSystem::Brazilian, &row.brazilian), (GradeSystem::Kurtyka, &row.kurtyka), (GradeSystem::Hueco, &row.hueco), (GradeSystem::Font, &row.font),
+            ] {
+                if let Some(grade) = grade {
+                    ui.label(format!("{name}: {grade}"));
+                }
+            }
 
-            if ui.button("Save Boulder Grade:").clicked() {
-                let db = Arc::clone(&self.database);
-                let rt = Arc::clone(&self.rt);
-                let grade = self.add_grade;
-                rt.as_ref().as_ref().unwrap().spawn(async move {
-                    <RoutesDb as Clone>::clone(&db).add_grade(None, Some(grade.font.to_string()), None, Some(grade.hueco.to_string()), None).await.expect("Error, could not add grade.");
-                });
+            ui.separator();
+            if ui.button("Save Grade").clicked() {
+                self.cache.change(&[], move |db| db.add_grade(row));
                 self.reset();
             }
         });
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_remove_grade(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) { //Should not be in end product
         self.header(&ctx);
         ui.add_space(20.0);
@@ -344,12 +511,10 @@ impl MyApp {
             ui.separator();
 
             if ui.button("Remove Tall Wall Grade").clicked() {
-                let db = Arc::clone(&self.database);
-                let rt = Arc::clone(&self.rt);
                 let grade = self.remove_grade.yosemite;
-                rt.as_ref().as_ref().unwrap().spawn(async move {
-                    let grade_id = <RoutesDb as Clone>::clone(&db).get_grade_id(&grade.to_string()).await.expect("Error, could not get grade id.");
-                    <RoutesDb as Clone>::clone(&db).remove_grade(grade_id).await.expect("Error, could not remove grade.");
+                self.cache.change(&[], move |db| async move {
+                    let grade_id = db.clone().get_grade_id(&grade.to_string()).await?;
+                    db.remove_grade(grade_id).await
                 });
                 self.reset();
             }
@@ -367,12 +532,10 @@ impl MyApp {
             ui.separator();
 
             if ui.button("Remove Boulder Grade").clicked() {
-                let db = Arc::clone(&self.database);
-                let rt = Arc::clone(&self.rt);
                 let grade = self.remove_grade.hueco;
-                rt.as_ref().as_ref().unwrap().spawn(async move {
-                    let grade_id = <RoutesDb as Clone>::clone(&db).get_grade_id(&grade.to_string()).await.expect("Error, could not get grade id.");
-                    <RoutesDb as Clone>::clone(&db).remove_grade(grade_id).await.expect("Error, could not remove grade.");
+                self.cache.change(&[], move |db| async move {
+                    let grade_id = db.clone().get_grade_id(&grade.to_string()).await?;
+                    db.remove_grade(grade_id).await
                 });
                 self.reset();
             }
@@ -380,6 +543,7 @@ impl MyApp {
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_add_route(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         self.header(&ctx);
         ui.add_space(20.0);
@@ -414,29 +578,74 @@ impl MyApp {
 
             ui.separator();
 
-            egui::ComboBox::from_label("Grade")
-                .selected_text({ if self.route_options.boulder { self.route_options.grade.hueco.to_string() } else { self.route_options.grade.yosemite.to_string() } }.to_string())
+            let system = self.settings.grade_system(self.route_options.boulder);
+            let options = &mut self.route_options;
+            let grade = RouteOptions::grade_in(options.grade, system);
+            options.grade = Some(grade);
+            egui::ComboBox::from_label(format!("Grade ({system})"))
+                .selected_text(grade.to_string())
                 .show_ui(ui, |ui| {
-                    if self.route_options.boulder {
-                        Hueco::iter().for_each(|grade| {
-                            ui.selectable_value(&mut self.route_options.grade.hueco, grade, format!("{grade}"));
-                        });
-                    } else {
-                        Yosemite::iter().for_each(|grade| {
-                            ui.selectable_value(&mut self.route_options.grade.yosemite, grade, format!("{grade}"));
-                        });
+                    for candidate in system.grades() {
+                        ui.selectable_value(&mut options.grade, Some(candidate), candidate.to_string());
                     }
                 });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut options.range, "Slash Grade / Range");
+                if options.range {
+                    let high = RouteOptions::grade_in(options.grade_high, system);
+                    options.grade_high = Some(high);
+                    egui::ComboBox::from_label("Up To")
+                        .selected_text(high.to_string())
+                        .show_ui(ui, |ui| {
+                            for candidate in system.grades().into_iter().filter(|candidate| conversion::score(*candidate) > conversion::score(grade)) {
+                                ui.selectable_value(&mut options.grade_high, Some(candidate), candidate.to_string());
+                            }
+                        });
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Or Type Grade:");
+                if ui.text_edit_singleline(&mut options.grade_text).changed() {
+                    options.grade_error = !options.grade_text.trim().is_empty() && !options.apply_grade_text(system);
+                }
+                if options.grade_error {
+                    ui.label(format!("Not a {system} grade or range."));
+                }
+            });
 
             ui.separator();
 
+            // Extra grades for the styles picked above
+            let options = &mut self.route_options;
+            let ice = !options.boulder && (options.ice || options.alpine);
+            let aid = !options.boulder && options.aid;
+            let alpine = !options.boulder && options.alpine;
+            if ice {
+                Self::optional_grade(ui, "Ice Grade", &mut options.ice_grade, Ice::iter());
+                Self::optional_grade(ui, "Mixed Grade", &mut options.mixed_grade, Mixed::iter());
+            }
+            if aid {
+                Self::optional_grade(ui, "Aid Grade", &mut options.aid_grade, Aid::iter());
+            }
+            if aid || alpine {
+                Self::optional_grade(ui, "Commitment Grade", &mut options.commitment_grade, Commitment::iter());
+            }
+            if alpine {
+                Self::optional_grade(ui, "Alpine Grade (IFAS)", &mut options.ifas_grade, Ifas::iter());
+            }
+            if !options.boulder {
+                Self::optional_grade(ui, "Danger Rating", &mut options.danger, Danger::iter());
+            }
+            ui.separator();
+
             ui.horizontal(|ui| {
-                ui.label("Length (ft):");
+                ui.label(format!("Length ({}):", self.settings.units.suffix()));
                 ui.text_edit_singleline(&mut self.route_options.length_buffer);
-                if let Ok(length) = self.route_options.length_buffer.parse::<u16>() { //Use u16 to prevent negative numbers
-                    self.route_options.length = i32::from(length); //Convert to i32 for database
+                // Stored in centimetres, "30m" or "100ft" override the unit setting
+                if let Ok(length) = self.settings.units.parse(&self.route_options.length_buffer) {
+                    self.route_options.length = length;
                 } else {
-                    ui.label("Invalid length, please enter a number.");
+                    ui.label("Invalid length, please enter a number like 30m or 100ft.");
                 }
             });
             
@@ -512,24 +721,42 @@ impl MyApp {
                     }
 
                     // Clone everything to pass to the async block
-                    let style_str = style.iter().map(std::string::ToString::to_string).join(", ");
+                    let style_names: Vec<String> = style.iter().map(std::string::ToString::to_string).collect();
+                    let discipline = if self.route_options.boulder { Discipline::Boulder } else { Discipline::TallWall };
                     let name = self.route_options.name.clone();
                     let length = self.route_options.length;
                     let pitches = self.route_options.pitches;
-                    #[allow(unused_variables)] //Location doesn't currently exist in the database
-                    let location = self.route_options.location.clone();
-                    #[allow(unused_variables)] //Grade is hardcoded for now
-                    let grade = self.route_options.grade;
-                    let str_grade: String = { if self.route_options.boulder { grade.hueco.to_string() } else { grade.yosemite.to_string() } }.to_string();
-                    
-                    // Add the route to the database, starting async stuffe
-                    let db = Arc::clone(&self.database);
-                    let rt = Arc::clone(&self.rt);
+                    let location = if self.route_options.location.is_empty() { None } else { Some(self.route_options.location.clone()) };
+                    // Grades are picked in the preferred system but stored as Yosemite/Hueco
+                    let system = self.settings.grade_system(self.route_options.boulder);
+                    let low = RouteOptions::grade_in(self.route_options.grade, system);
+                    let high = if self.route_options.range { RouteOptions::grade_in(self.route_options.grade_high, system) } else { low };
+                    let (str_grade, grade_high) = conversion::stored_range(low, high, self.route_options.boulder);
+                    let options = &self.route_options;
+                    let ice = !options.boulder && (options.ice || options.alpine);
+                    let aid = !options.boulder && options.aid;
+                    let alpine = !options.boulder && options.alpine;
+                    let mut route = RouteModel {
+                        id: 0,
+                        name,
+                        length,
+                        pitches,
+                        grade_id: 0,
+                        location,
+                        ice_grade: options.ice_grade.filter(|_| ice).map(|grade| grade.to_string()),
+                        mixed_grade: options.mixed_grade.filter(|_| ice).map(|grade| grade.to_string()),
+                        aid_grade: options.aid_grade.filter(|_| aid).map(|grade| grade.to_string()),
+                        commitment_grade: options.commitment_grade.filter(|_| aid || alpine).map(|grade| grade.to_string()),
+                        ifas_grade: options.ifas_grade.filter(|_| alpine).map(|grade| grade.to_string()),
+                        danger: options.danger.filter(|_| !options.boulder).map(|danger| danger.to_string()),
+                        grade_high,
+                        discipline: discipline.to_string(),
+                    };
                     
-                    rt.as_ref().as_ref().unwrap().spawn(async move { //The two as_refs are actually different: the first is for converting to a shared reference with Arc, the second is for getting an Option to a reference value
-                        //<RoutesDb as Clone>::clone(&db)... is used to clone the database connection, preventing a move error from a bad borrow
-                        let grade_id: i32 = <RoutesDb as Clone>::clone(&db).get_grade_id(&str_grade).await.expect("Error, could not get grade id.");
-                        <RoutesDb as Clone>::clone(&db).add_route(name, length, pitches, style_str, grade_id).await.expect("Error, could not add route."); //grade_id is hardcoded for now
+                    // Add the route to the database, the routes and their styles reload once it's in
+                    self.cache.change(&[Data::Routes, Data::RouteStyles], move |db| async move {
+                        route.grade_id = db.clone().get_grade_id(&str_grade).await?;
+                        db.add_route(route, style_names).await
                     });
                     
                     self.reset();
@@ -541,7 +768,28 @@ impl MyApp {
         });
     }
 
+    fn optional_grade<G: Copy + PartialEq + std::fmt::Display>(ui: &mut eframe::egui::Ui, label: &str, value: &mut Option<G>, grades: impl Iterator<Item = G>) {
+        // Grade picker that can be left empty
+        egui::ComboBox::from_label(label)
+            .selected_text(value.map_or("None".to_string(), |grade| grade.to_string()))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(value, None, "None");
+                for grade in grades {
+                    ui.selectable_value(value, Some(grade), format!("{grade}"));
+                }
+            });
+    }
+
+    fn style_grades(route: &RouteModel) -> Option<String> {
+        // Ice, mixed, aid and alpine grades of a route, None if it has none
+        let grades: Vec<String> = [&route.ice_grade, &route.mixed_grade, &route.aid_grade, &route.ifas_grade].into_iter().flatten().cloned()
+            .chain(route.commitment_grade.iter().map(|grade| format!("Grade {grade}")))
+            .collect();
+        if grades.is_empty() { None } else { Some(grades.join(", ")) }
+    }
+
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_remove_route(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) { //Make sure to check if route exists before removing, not currently doing
         self.header(&ctx);
         ui.add_space(20.0);
@@ -559,11 +807,9 @@ impl MyApp {
             ui.separator();
             if ui.button("Remove").clicked() {
                 let name = self.removal_name.clone();
-                let db = Arc::clone(&self.database);
-                let rt = Arc::clone(&self.rt);
-                rt.as_ref().as_ref().unwrap().spawn(async move {
-                    let route = <RoutesDb as Clone>::clone(&db).get_route_id(&name).await.expect("Error, could not get route id.");
-                    <RoutesDb as Clone>::clone(&db).remove_route(route).await.expect("Error, could not remove route.");
+                self.cache.change(&[Data::Routes, Data::RouteStyles], move |db| async move {
+                    let route = db.clone().get_route_id(&name).await?;
+                    db.remove_route(route).await
                 });
                 self.reset();
             }
@@ -571,6 +817,7 @@ impl MyApp {
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_search_home(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         self.header(&ctx);
         ui.add_space(20.0);
@@ -582,10 +829,179 @@ impl MyApp {
             else if ui.button("View All Routes").clicked() {
                 self.page = Page::ViewAllRoutes;
             }
+            else if ui.button("Search Logbook").clicked() {
+                self.page = Page::SearchLogbook;
+            }
         });
     }
 
+    fn search_filters(&mut self, ui: &mut eframe::egui::Ui) {
+        // Grade range, styles, send types and days to narrow the search down
+        ui.horizontal(|ui| {
+            ui.label("Grades from:");
+            ui.add(egui::TextEdit::singleline(&mut self.search.grade_from).desired_width(60.0));
+            ui.label("to:");
+            ui.add(egui::TextEdit::singleline(&mut self.search.grade_to).desired_width(60.0));
+        });
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Styles:");
+            for style in Style::iter() {
+                let mut picked = self.search.styles.contains(&style);
+                if ui.checkbox(&mut picked, style.to_string()).changed() {
+                    self.search.styles.retain(|found| *found != style);
+                    if picked {
+                        self.search.styles.push(style);
+                    }
+                }
+            }
+        });
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Send types:");
+            for send_type in SendType::iter() {
+                let mut picked = self.search.send_types.contains(&send_type);
+                if ui.checkbox(&mut picked, send_type.to_string()).changed() {
+                    self.search.send_types.retain(|found| *found != send_type);
+                    if picked {
+                        self.search.send_types.push(send_type);
+                    }
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            let today = chrono::Local::now().date_naive();
+            for (label, day, id) in [("From", &mut self.search.from, "search_from"), ("To", &mut self.search.to, "search_to")] {
+                let mut on = day.is_some();
+                if ui.checkbox(&mut on, label).changed() {
+                    *day = on.then_some(today);
+                }
+                if let Some(day) = day {
+                    ui.add(egui_extras::DatePickerButton::new(day).id_source(id));
+                }
+            }
+        });
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn render_search_logbook(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+        // Words in route names, crags, notes and partners, each result links to its route or session
+        self.header(&ctx);
+        ui.add_space(20.0);
+        ui.heading("Search Logbook");
+
+        ScrollArea::vertical().show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Search:");
+                ui.text_edit_singleline(&mut self.search.text);
+            });
+            self.search_filters(ui);
+            ui.separator();
+            if self.search.text.trim().is_empty() {
+                ui.label("Type a route, crag, partner or anything from your notes.");
+                return;
+            }
+            let text = self.search.text.clone();
+            self.cache.search(&text);
+            if !self.cache.ready(ui, &[Data::Search, Data::Sends, Data::Routes, Data::RouteStyles]) {
+                return;
+            }
+            let hits = match search::hits(self.cache.text_matches.value(), &self.logbook(), self.cache.route_styles.value(), &self.search.filters()) {
+                Ok(hits) => hits,
+                Err(error) => {
+                    ui.colored_label(egui::Color32::RED, error.to_string());
+                    return;
+                }
+            };
+            if hits.is_empty() {
+                ui.label("Nothing found.");
+                return;
+            }
+            for hit in hits {
+                ui.horizontal(|ui| {
+                    ui.label(if hit.kind == MatchKind::Route { "Route" } else { "Send" });
+                    if ui.link(&hit.title).clicked() {
+                        self.open_search_hit(hit.route, hit.send);
+                    }
+                });
+                ui.horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    for (text, matched) in search::highlights(&hit.snippet) {
+                        if matched {
+                            ui.label(egui::RichText::new(text).strong().background_color(egui::Color32::from_rgba_unmultiplied(251, 188, 4, 90)));
+                        } else {
+                            ui.label(text);
+                        }
+                    }
+                });
+                ui.separator();
+            }
+        });
+    }
+
+    fn open_search_hit(&mut self, route: i32, send: Option<i32>) {
+        // Sends open their session, routes their route page
+        if let Some(send) = send.and_then(|send| self.all_sessions.iter().find(|found| found.id == send)) {
+            self.view_session = Some(send.clone());
+            self.page = Page::ViewSession;
+        } else if let Some(found) = self.routes_w_grades().iter().find(|(found, _)| found.id == route) {
+            self.viewing = Some(found.clone());
+            self.page = Page::ViewRoute;
+        }
+    }
+
+    fn filter_box(&mut self, ui: &mut eframe::egui::Ui) -> bool {
+        // Typed or saved filter, false while what's typed doesn't parse
+        let mut picked = None;
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.add(egui::TextEdit::singleline(&mut self.filter.text).hint_text("grade>=5.11a style:trad after:2024-01-01").desired_width(320.0));
+            egui::ComboBox::from_id_source("saved_filters").selected_text("Saved").show_ui(ui, |ui| {
+                for (name, text) in &self.saved_filters.0 {
+                    if ui.selectable_label(false, name).on_hover_text(text).clicked() {
+                        picked = Some((name.clone(), text.clone()));
+                    }
+                }
+            });
+        });
+        if let Some((name, text)) = picked {
+            self.filter.name = name;
+            self.filter.text = text;
+        }
+        ui.horizontal(|ui| {
+            ui.label("Save as:");
+            ui.add(egui::TextEdit::singleline(&mut self.filter.name).desired_width(120.0));
+            let mut saved = self.saved_filters.clone();
+            if ui.button("Save").clicked() {
+                match saved.insert(&self.filter.name, &self.filter.text, &self.settings) {
+                    Ok(()) => self.save_filters(saved),
+                    Err(error) => self.filter.error = Some(error.to_string()),
+                }
+            }
+            else if ui.button("Delete").clicked() && saved.0.remove(self.filter.name.trim()).is_some() {
+                self.save_filters(saved);
+            }
+        });
+        let parsed = self.saved_filters.resolve(&self.filter.text, &self.settings);
+        if let Ok(filter) = &parsed {
+            if *filter != self.filter.applied {
+                self.filter.error = None;
+            }
+            self.filter.applied = filter.clone();
+        }
+        if let Some(error) = parsed.as_ref().err().map(ToString::to_string).or_else(|| self.filter.error.clone()) {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+        ui.separator();
+        parsed.is_ok()
+    }
+
+    fn save_filters(&mut self, saved: SavedFilters) {
+        self.filter.error = None;
+        self.saved_filters = saved.clone();
+        self.cache.change(&[], move |db| async move { saved.save(db).await });
+    }
+
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_find_route(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         self.header(&ctx);
         ui.add_space(20.0);
@@ -600,69 +1016,88 @@ impl MyApp {
                 ui.text_edit_singleline(&mut self.removal_name);
             });
             ui.separator();
+            if !self.cache.ready(ui, &[Data::Routes, Data::RouteStyles]) {
+                return;
+            }
             if ui.button("Find").clicked() {
-                let name = self.removal_name.clone();
-                let db = Arc::clone(&self.database);
-                let rt = Arc::clone(&self.rt);
-                let search_result = Arc::clone(&self.search_result);
-
-                rt.as_ref().as_ref().unwrap().spawn(async move {
-                    if let Ok((route, grade)) = <RoutesDb as Clone>::clone(&db).find_route_and_grade(&name).await {
-                        let mut result_lock = search_result.lock().unwrap();
-                        *result_lock = Some((route, grade));
-                    } else {
-                        let mut result_lock = search_result.lock().unwrap();
-                        *result_lock = None;
-                    }
-                    //let mut result_lock = search_result.lock().unwrap();
-                    //*result_lock = Some((route, grade));
-                });
+                // Routes are already cached, so finding one doesn't need a query
+                self.search_result = self.routes_w_grades().iter().find(|(route, _)| route.name == self.removal_name).cloned();
             }
             ui.separator();
-            if let Some((route, grade)) = &*self.search_result.lock().unwrap() {
+            if let Some((route, grade)) = &self.search_result {
                 ui.label(format!("Name: {}", route.name));
-                ui.label(format!("Grade Id: {}", { if route.pitches == 0 { grade.hueco.clone().unwrap().to_string() } else { grade.yosemite.clone().unwrap().to_string() } }));
-                ui.label(format!("Style: {}", route.style));
-                ui.label(format!("Length: {} ft", route.length));
+                ui.label(format!("Grade Id: {}", self.logbook().grade_label(route, grade)));
+                ui.label(format!("Discipline: {}", route.discipline));
+                ui.label(format!("Style: {}", self.style_names(route.id)));
+                ui.label(format!("Length: {}", self.settings.length(route.length)));
                 ui.label(format!("Pitches: {}", route.pitches));
-                //ui.label(format!("Location: {}", route.location));
+                ui.label(format!("Location: {}", route.location.clone().unwrap_or("Unknown".to_string())));
+                if let Some(grades) = Self::style_grades(route) {
+                    ui.label(format!("Other Grades: {grades}"));
+                }
             }
         });
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_view_route(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         self.header(&ctx);
         ui.add_space(20.0);
         ui.heading("View Route");
         if let Some((view_route, view_grade)) = self.viewing.clone() {
-            ui.label(format!("Grade Id: {}", { if view_route.pitches == 0 { view_grade.hueco.unwrap().to_string() } else { view_grade.yosemite.unwrap().to_string() } }));
-            ui.label(format!("Style: {}", view_route.style));
-            ui.label(format!("Length: {} ft", view_route.length));
+            ui.label(format!("Grade Id: {}", self.logbook().grade_label(&view_route, &view_grade)));
+            ui.label(format!("Discipline: {}", view_route.discipline));
+            ui.label(format!("Style: {}", self.style_names(view_route.id)));
+            ui.label(format!("Length: {}", self.settings.length(view_route.length)));
             ui.label(format!("Pitches: {}", view_route.pitches));
-            //ui.label(format!("Location: {}", route.location));
+            if let Some(grades) = Self::style_grades(&view_route) {
+                ui.label(format!("Other Grades: {grades}"));
+            }
+            if let Some(danger) = &view_route.danger {
+                ui.label(format!("Danger: {danger}"));
+            }
+            ui.label(format!("Location: {}", view_route.location.unwrap_or("Unknown".to_string())));
             //Display notes too once implemented
         }
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_all_routes(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         self.header(&ctx);
         ui.add_space(20.0);
         ui.heading("All Routes");
         
+        if !self.filter_box(ui) {
+            return;
+        }
+        let filtered = !self.filter.applied.is_empty();
+        if filtered {
+            self.cache.filter(&self.filter.applied, self.settings.climber);
+        }
+        let wanted: &[Data] = if filtered { &[Data::Routes, Data::RouteStyles, Data::FilteredRoutes] } else { &[Data::Routes, Data::RouteStyles] };
+        if !self.cache.ready(ui, wanted) {
+            return;
+        }
         ScrollArea::vertical().show(ui, |ui| {
-            let db = Arc::clone(&self.database);
-            let rt = Arc::clone(&self.rt);
-            let results = Arc::clone(&self.all_routes);
-            rt.as_ref().as_ref().unwrap().spawn(async move {
-                let routes = <RoutesDb as Clone>::clone(&db.clone()).find_all_routes_and_grade().await.expect("Error, could not find all routes.");
-                let mut routes_guard = results.lock().unwrap();
-                *routes_guard = routes;
-            });
-            
-            let routes = self.all_routes.lock().unwrap().clone();
-            for (i, (route, grade)) in routes.iter().enumerate() {
+            egui::ComboBox::from_label("Minimum Danger")
+                .selected_text(self.danger_filter.map_or("Any".to_string(), |danger| danger.to_string()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.danger_filter, None, "Any");
+                    for danger in Danger::iter() {
+                        ui.selectable_value(&mut self.danger_filter, Some(danger), format!("{danger}"));
+                    }
+                });
+            ui.separator();
+
+            let routes = if filtered { self.cache.filtered_routes.value().clone() } else { self.routes_w_grades().to_vec() };
+            let danger_filter = self.danger_filter;
+            let routes = routes.iter().filter(|(route, _)| danger_filter.is_none_or(|min| {
+                // Unrated routes are hidden once a rating is picked
+                route.danger.as_deref().and_then(|danger| Danger::from_str(danger).ok()).is_some_and(|danger| danger >= min)
+            }));
+            for (i, (route, grade)) in routes.enumerate() {
                 
                 ui.horizontal(|ui| {
                     ui.label(format!("Route {}: {}", i, route.name));
@@ -671,18 +1106,26 @@ impl MyApp {
                         self.page = Page::ViewRoute;
                     }
                 });
-                ui.label(format!("Grade: {}", if route.pitches == 0 { grade.clone().hueco.unwrap().to_string() } else { grade.clone().yosemite.unwrap().to_string() }));
+                ui.label(format!("Grade: {}", self.logbook().grade_label(route, grade)));
                 
-                ui.label(format!("Style: {}", route.style));
-                ui.label(format!("Length: {} ft", route.length));
+                ui.label(format!("Discipline: {}", route.discipline));
+                ui.label(format!("Style: {}", self.style_names(route.id)));
+                ui.label(format!("Length: {}", self.settings.length(route.length)));
                 ui.label(format!("Pitches: {}", route.pitches));
-                //ui.label(format!("Location: {}", route.location));
+                ui.label(format!("Location: {}", route.location.clone().unwrap_or("Unknown".to_string())));
+                if let Some(grades) = Self::style_grades(route) {
+                    ui.label(format!("Other Grades: {grades}"));
+                }
+                if let Some(danger) = &route.danger {
+                    ui.label(format!("Danger: {danger}"));
+                }
                 ui.separator();
             }
         });
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_log_session(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         self.header(&ctx);
         ui.add_space(20.0);
@@ -695,6 +1138,18 @@ impl MyApp {
                 ui.radio_value(&mut self.route_options.indoor, false, "Outdoor");
             }); */ //Not implemented for indoor yet- outdoor is actually simpler at the moment. Indoor likely needs its own table
             // ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("New Partner:");
+                ui.text_edit_singleline(&mut self.new_partner);
+                if ui.button("Add Partner").clicked() && !self.new_partner.trim().is_empty() {
+                    self.add_partner();
+                }
+            });
+            ui.separator();
+
+            self.cache.request(Data::Partners);
+            let partners = self.partners().to_vec();
+            let settings = self.settings.clone();
             let mut to_remove = None;
             for index in 0..self.session.len() {
                 let send = &mut self.session[index];
@@ -704,9 +1159,21 @@ impl MyApp {
 
                     ui.separator();
 
-                    ui.horizontal(|ui| {
-                        ui.label("Partner:");
-                        ui.text_edit_singleline(&mut send.partner);
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("Partners:");
+                        if partners.is_empty() {
+                            ui.label("Add a partner above to pick them here.");
+                        }
+                        for partner in &partners {
+                            let selected = send.partners.contains(&partner.id);
+                            if ui.selectable_label(selected, &partner.name).clicked() {
+                                if selected {
+                                    send.partners.retain(|id| *id != partner.id);
+                                } else {
+                                    send.partners.push(partner.id);
+                                }
+                            }
+                        }
                     });
 
                     ui.separator();
@@ -724,6 +1191,26 @@ impl MyApp {
 
                     ui.separator();
 
+                    ui.horizontal(|ui| {
+                        ui.label("Grade Felt:");
+                        for opinion in [GradeOpinion::Agree, GradeOpinion::Soft, GradeOpinion::Hard] {
+                            let label = opinion.to_string();
+                            ui.radio_value(&mut send.opinion, opinion, label);
+                        }
+                        let other = matches!(send.opinion, GradeOpinion::Grade(_));
+                        if ui.radio(other, "Other Grade").clicked() && !other {
+                            send.opinion = GradeOpinion::Grade(String::new());
+                        }
+                        if let GradeOpinion::Grade(grade) = &mut send.opinion {
+                            ui.text_edit_singleline(grade);
+                            if !grade.trim().is_empty() && [false, true].iter().all(|&boulder| conversion::parse_grade(grade, boulder, settings.grade_system(boulder)).is_none()) {
+                                ui.colored_label(egui::Color32::RED, "Not a grade");
+                            }
+                        }
+                    });
+
+                    ui.separator();
+
                     ui.horizontal(|ui| {
                         ui.label("Attempts:");
                         ui.add(eframe::egui::widgets::DragValue::new(&mut send.attempts).speed(1.0));
@@ -763,23 +1250,28 @@ impl MyApp {
             }
 
             if ui.button("Log Session").clicked() {
-                let db = Arc::clone(&self.database);
-                let rt = Arc::clone(&self.rt);
                 let sends = self.session.clone();
-                
-                rt.as_ref().as_ref().unwrap().spawn(async move {
-                    
-                    
-                    let session_id = <RoutesDb as Clone>::clone(&db).get_next_session_id().await.expect("Error, could not get next session id.");
+                let climber = self.settings.climber;
+                self.cache.change(&[Data::Sends, Data::Partners], move |db| async move {
+                    // Find every route and check every grade first, so one bad send doesn't leave half a session
+                    let mut routes = Vec::new();
                     for send in &sends {
                         let find_name = send.route_name.clone();
-                        let partner = if send.partner.is_empty() { None } else { Some(send.partner.clone()) };
+                        let route = db.clone().find_route_name(&find_name).await?.ok_or_else(|| DbErr::RecordNotFound(format!("Route {find_name}")))?;
+                        if let GradeOpinion::Grade(grade) = &send.opinion {
+                            let boulder = Discipline::from_str(&route.discipline) == Ok(Discipline::Boulder);
+                            if !grade.trim().is_empty() && conversion::parse_grade(grade, boulder, settings.grade_system(boulder)).is_none() {
+                                return Err(DbErr::Custom(format!("{grade} is not a grade for {find_name}")));
+                            }
+                        }
+                        routes.push(route);
+                    }
+                    let session_id = db.clone().get_next_session_id().await?;
+                    for (send, route) in sends.iter().zip(routes) {
                         let notes = if send.notes.is_empty() { None } else { Some(send.notes.clone()) };
-                        let route = <RoutesDb as Clone>::clone(&db).find_route_name(&find_name).await.expect("Error, could not find route.");
-                        let route = route.unwrap();
-                        <RoutesDb as Clone>::clone(&db).add_send(session_id, route, send.date.to_string(), partner, send.send_type.to_string(), send.attempts, notes).await.expect("Error, could not log session.");
+                        db.clone().add_send(climber, session_id, route, send.date.to_string(), send.partners.clone(), send.send_type.to_string(), send.attempts, notes, send.opinion.stored()).await?;
                     }
-                    
+                    Ok(())
                 });
                 self.reset();
             }
@@ -787,6 +1279,7 @@ impl MyApp {
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_delete_session(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         self.header(&ctx);
         ui.add_space(20.0);
@@ -800,27 +1293,16 @@ impl MyApp {
                 ui.add(egui_extras::DatePickerButton::new(&mut self.search_date));
             });
             ui.separator();
-            if ui.button("Find").clicked() {
-                let session_date = self.search_date.to_string();
-                let db = Arc::clone(&self.database);
-                let rt = Arc::clone(&self.rt);
-                let results = Arc::clone(&self.cur_session);
-                rt.as_ref().as_ref().unwrap().spawn(async move {
-                    let sessions = <RoutesDb as Clone>::clone(&db).get_session_by_date(session_date).await.expect("Error, could not get all sessions.");
-                    let mut sessions_guard = results.lock().unwrap();
-                    *sessions_guard = sessions;
-                });
+            if !self.cache.ready(ui, &[Data::Sends, Data::Partners]) {
+                return;
             }
-            let mut sessions: MutexGuard<Vec<SendModel>> = self.cur_session.lock().unwrap();
-            for session in sessions.clone().iter() {
+            // The day's sends come out of the cached sends, the list updates once a removal reloads them
+            let session_date = self.search_date.to_string();
+            let sessions: Vec<SendModel> = self.all_sessions.iter().filter(|session| session.date == session_date).cloned().collect();
+            for session in &sessions {
                 if ui.button("Delete").clicked() {
-                    let db = Arc::clone(&self.database);
-                    let rt = Arc::clone(&self.rt);
                     let session_id = session.session;
-                    rt.as_ref().as_ref().unwrap().spawn(async move {
-                        <RoutesDb as Clone>::clone(&db).remove_session(session_id).await.expect("Error, could not remove session.");
-                    });
-                    sessions.retain(|s| s.session != session_id);
+                    self.cache.change(&[Data::Sends, Data::Partners], move |db| db.remove_session(session_id));
                 } else {
                     ui.horizontal(|ui| {
                         ui.label(format!("Session {}: ", session.session));
@@ -831,9 +1313,12 @@ impl MyApp {
                     });
                     let session = session.clone();
                     ui.label(format!("Date: {}", session.date));
-                    ui.label(format!("Partner: {}", session.partner.unwrap_or("None".to_string())));
+                    ui.label(format!("Partners: {}", self.partner_names(session.id)));
                     ui.label(format!("Type: {}", session.r#type));
                     ui.label(format!("Attempts: {}", session.attempts));
+                    if let Some(opinion) = &session.personal_grade {
+                        ui.label(format!("Grade Felt: {opinion}"));
+                    }
                     ui.label(format!("Route: {}", session.route));
                     ui.separator();
                 }
@@ -845,6 +1330,7 @@ impl MyApp {
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_history(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         // Display sessions in a scroll area
         self.header(&ctx);
@@ -852,27 +1338,37 @@ impl MyApp {
         ui.heading("History");
 
         ScrollArea::vertical().show(ui, |ui| {
-            ui.horizontal(|ui| {
-                ui.label("Search: ");
-                // Search using date picker
-                ui.add(egui_extras::DatePickerButton::new(&mut self.search_date));
-            });
+            if !self.filter_box(ui) {
+                return;
+            }
+            let filtered = !self.filter.applied.is_empty();
+            if filtered {
+                // Every matching send, whatever the day
+                self.cache.filter(&self.filter.applied, self.settings.climber);
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label("Search: ");
+                    // Search using date picker
+                    ui.add(egui_extras::DatePickerButton::new(&mut self.search_date));
+                });
+                ui.separator();
+            }
 
-            ui.separator();
-            // Get all sessions thru async
-            let db = Arc::clone(&self.database);
-            let rt = Arc::clone(&self.rt);
-            let results = Arc::clone(&self.cur_session);
+            let wanted: &[Data] = if filtered { &[Data::Sends, Data::Partners, Data::FilteredSends] } else { &[Data::Sends, Data::Partners] };
+            if !self.cache.ready(ui, wanted) {
+                return;
+            }
+            // The current climber's sends on the picked day, straight from the cache
             let session_date = self.search_date.to_string();
-            rt.as_ref().as_ref().unwrap().spawn(async move {
-                let sessions = <RoutesDb as Clone>::clone(&db).get_session_by_date(session_date).await.expect("Error, could not get all sessions.");
-                let mut sessions_guard = results.lock().unwrap();
-                *sessions_guard = sessions;
-            });
-
-            let sessions: MutexGuard<Vec<SendModel>> = self.cur_session.lock().unwrap();
-            // Display all sessions
-            for session in sessions.iter() {
+            let sessions: Vec<SendModel> = if filtered {
+                self.cache.filtered_sends.value().clone()
+            } else {
+                self.all_sessions.iter().filter(|session| session.date == session_date).cloned().collect()
+            };
+            if filtered && sessions.is_empty() {
+                ui.label("No sends match the filter.");
+            }
+            for session in &sessions {
                 ui.horizontal(|ui| {
                     ui.label(format!("Session {}: ", session.session));
                     if ui.button("View").clicked() {
@@ -882,9 +1378,12 @@ impl MyApp {
                 });
                 let session = session.clone();
                 ui.label(format!("Date: {}", session.date));
-                ui.label(format!("Partner: {}", session.partner.unwrap_or("None".to_string())));
+                ui.label(format!("Partners: {}", self.partner_names(session.id)));
                 ui.label(format!("Type: {}", session.r#type));
                 ui.label(format!("Attempts: {}", session.attempts));
+                if let Some(opinion) = &session.personal_grade {
+                    ui.label(format!("Grade Felt: {opinion}"));
+                }
                 ui.label(format!("Route: {}", session.route));
                 ui.separator();
             }
@@ -893,6 +1392,7 @@ impl MyApp {
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_view_session(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         // Display info for a single session
         self.header(&ctx);
@@ -901,6 +1401,7 @@ impl MyApp {
     }
 
     #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
     fn render_stats(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
         // Display a variety of stats for the user
         self.header(&ctx);
@@ -928,414 +1429,638 @@ impl MyApp {
             But before all of that, have to actually get all the relevant info to calculate the stats.
          */
 
-        // Get the sends info
-        let db = Arc::clone(&self.database);
-        let rt = Arc::clone(&self.rt);
-        let all_sessions = Arc::clone(&self.all_sessions_buffer);
-        rt.as_ref().as_ref().unwrap().spawn(async move {
-            let sessions = <RoutesDb as Clone>::clone(&db).get_all_sends().await.expect("Error, could not get all sessions.");
-            let mut sessions_guard = all_sessions.lock().unwrap();
-            *sessions_guard = sessions;
-        });
-        self.all_sessions = self.all_sessions_buffer.lock().unwrap().clone();
-
-        
-        let db = Arc::clone(&self.database);
-        let rt = Arc::clone(&self.rt);
-        let all_routes_w_grades = Arc::clone(&self.routes_w_grades_buffer);
-        rt.as_ref().as_ref().unwrap().spawn(async move {
-            let routes = <RoutesDb as Clone>::clone(&db).find_all_routes_and_grade().await.expect("Error, could not find all routes.");
-            let mut routes_guard = all_routes_w_grades.lock().unwrap();
-            *routes_guard = routes;
-        });
-        self.routes_w_grades = self.routes_w_grades_buffer.lock().unwrap().clone();
-
-        // Display the stats
-        if self.all_sessions.is_empty() || self.routes_w_grades.is_empty() {
-            // Change this to a loading circle at some point
-            ui.label("Loading...");
+        // Display the stats, over just the sends matching the filter when there is one
+        if !self.filter_box(ui) {
             return;
         }
-        ui.vertical(|ui| {
+        let filtered = !self.filter.applied.is_empty();
+        let mut wanted = LOGBOOK_DATA.to_vec();
+        if filtered {
+            self.cache.filter(&self.filter.applied, self.settings.climber);
+            wanted.push(Data::FilteredSends);
+        }
+        if !self.cache.ready(ui, &wanted) {
+            return;
+        }
+        if self.stats_logbook().sends.is_empty() {
+            ui.label(if filtered { "No sends match the filter." } else { "Nothing logged yet." });
+            return;
+        }
+        ScrollArea::vertical().show(ui, |ui| {
             self.render_stats_content(ui);
+            ui.separator();
+            self.render_pyramid_content(ui);
+            ui.separator();
+            self.render_opinion_content(ui);
+            ui.separator();
+            self.render_rating_content(ui);
         });
     }
 
-    fn render_stats_content(&self, ui: &mut eframe::egui::Ui) {
-        ui.label(format!("Total Sends: {}", self.total_sends()));
-        ui.label(format!("Total Sessions: {}", self.total_sessions()));
-        ui.label(format!("Average Sends per Session: {}", self.avg_sends()));
-        ui.label(format!("Average Attempts per Send: {}", self.avg_attempts()));
-        ui.label(format!("Average Tall Wall Grade: {}", self.avg_tall_grade()));
-        ui.label(format!("Average Boulder Grade: {}", self.avg_boulder_grade()));
-        ui.label(format!("Favorite Style: {}", self.fav_style()));
-        ui.label(format!("Favorite Route: {}", self.fav_route()));
-        ui.label(format!("Favorite Partner: {}", self.fav_partner()));
-        ui.label(format!("Favorite Crag: {}", self.fav_crag()));
-        ui.label(format!("Flash Grade (Tall Wall): {}", self.flash_grade_tall()));
-        ui.label(format!("Flash Grade (Boulder): {}", self.flash_grade_boulder()));
-        ui.label(format!("Redpoint Grade (Tall Wall): {}", self.redpoint_grade_tall()));
-        ui.label(format!("Redpoint Grade (Boulder): {}", self.redpoint_grade_boulder()));
-        let (tall_grade, tall_route) = self.top_tall_grade();
-        let (boulder_grade, boulder_route) = self.top_boulder_grade();
-        ui.label(format!("Top Tall Wall Grade: {} ({})", tall_grade, tall_route));
-        ui.label(format!("Top Boulder Grade: {} ({})", boulder_grade, boulder_route));
-        ui.label(format!("Other Stats: {}", self.other_stats()));
-    }
-
-    fn total_sends(&self) -> i32 {
-        // Get the total number of sends
-        self.all_sessions.len() as i32
+    fn logbook(&self) -> Logbook<'_> { // Stats view over the loaded sends and routes
+        Logbook { sends: &self.all_sessions, routes: self.routes_w_grades(), settings: &self.settings }
     }
 
-    fn total_sessions(&self) -> i32 {
-        // Get the total number of sessions (Currently just the highest id, but logic should be changed to get the number of unique ids)
-        let mut total_sessions: Vec<i32> = Vec::new();
-        for session in &self.all_sessions {
-            if total_sessions.contains(&session.session) {
-                continue;
-            } else {
-                total_sessions.push(session.session);
-            }
+    fn stats_logbook(&self) -> Logbook<'_> { // Stats view over the sends matching the filter, or all of them without one
+        if self.filter.applied.is_empty() {
+            return self.logbook();
         }
-
-        total_sessions.len() as i32
+        Logbook { sends: self.cache.filtered_sends.value(), routes: self.routes_w_grades(), settings: &self.settings }
     }
 
-    fn avg_sends(&self) -> f32 {
-        // Get the average number of sends per session
-        if self.total_sessions() == 0 {
-            return 0.0;
+    fn render_stats_content(&self, ui: &mut eframe::egui::Ui) {
+        let summary = self.stats_logbook().summary(self.cache.route_styles.value(), self.partners(), self.send_partners());
+        for (label, value) in summary {
+            ui.label(format!("{label}: {value}"));
         }
-        self.total_sends() as f32 / self.total_sessions() as f32
     }
 
-    fn avg_attempts(&self) -> f32 {
-        // Get the average number of attempts per send
-        if self.total_sends() == 0 {
-            return 0.0;
-        }
-        self.all_sessions.iter().map(|session| session.attempts).sum::<i32>() as f32 / self.total_sends() as f32
+    fn add_climber(&mut self) {
+        // Add the climber typed into the new climber field
+        let name = self.new_climber.trim().to_string();
+        self.cache.change(&[Data::Climbers], move |db| async move { db.add_climber(&name).await.map(|_| ()) });
+        self.new_climber = String::new();
     }
 
-    fn avg_tall_grade(&self) -> Yosemite {
-        // Get the average tall wall grade
-        let mut routes_and_grades: Vec<(RouteModel, GradeModel)> = Vec::new();
-        for session in &self.all_sessions {
-            
-            let route: (RouteModel, GradeModel) = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route).unwrap().clone();
-            if route.0.pitches == 0 {
-                continue;
+    #[allow(clippy::needless_pass_by_value)]
+    fn render_team(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+        // Everyone sharing the logbook side by side, over the same routes and grades
+        self.header(&ctx);
+        ui.add_space(20.0);
+        ui.heading("Team");
+
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut self.new_climber);
+            if ui.button("Add Climber").clicked() && !self.new_climber.trim().is_empty() {
+                self.add_climber();
             }
-            routes_and_grades.push(route);
-        }
+        });
+        ui.separator();
 
-        let mut grade_sum = 0;
-        for (_route, grade) in &routes_and_grades {
-            grade_sum += Yosemite::from(grade.yosemite.clone().unwrap()) as i32;
-        }
-        if routes_and_grades.is_empty() {
-            return Yosemite::None;
+        if !self.cache.ready(ui, &[Data::Climbers, Data::Sends, Data::Routes, Data::RouteStyles, Data::Partners]) {
+            return;
         }
-        let grade_num = (grade_sum / routes_and_grades.len() as i32) as i32;
-        Yosemite::from(grade_num)
-
+        // Everyone's sends, not just the current climber's
+        let logbook = Logbook { sends: self.cache.sends.value(), routes: self.routes_w_grades(), settings: &self.settings };
+        let team = logbook.team(self.cache.climbers.value(), self.cache.route_styles.value(), self.partners(), self.send_partners());
+        ScrollArea::both().show(ui, |ui| {
+            egui::Grid::new("Team Stats").striped(true).show(ui, |ui| {
+                ui.label("");
+                for (name, _) in &team {
+                    ui.strong(name);
+                }
+                ui.end_row();
+                for label in TEAM_STATS {
+                    ui.label(label);
+                    for (_, summary) in &team {
+                        ui.label(summary.iter().find(|(found, _)| *found == label).map_or("", |(_, value)| value.as_str()));
+                    }
+                    ui.end_row();
+                }
+            });
+        });
     }
 
-    fn avg_boulder_grade(&self) -> Hueco {
-        // Get the average boulder grade
-        let mut routes_and_grades: Vec<(RouteModel, GradeModel)> = Vec::new();
-        for session in &self.all_sessions {
-            
-            let route: (RouteModel, GradeModel) = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route).unwrap().clone();
-            if route.0.pitches != 0 {
-                continue;
-            }
-            routes_and_grades.push(route);
-        }
+    fn render_pyramid_content(&self, ui: &mut eframe::egui::Ui) {
+        // Completed sends at each grade, hardest first
+        ui.heading(if self.settings.personal_grades { "Grade Pyramid (Your Grades)" } else { "Grade Pyramid" });
+        ui.label("Tall Wall:");
+        self.render_pyramid(ui, &stats::pyramid(&self.stats_logbook().tall_samples()), false);
+        ui.label("Boulder:");
+        self.render_pyramid(ui, &stats::pyramid(&self.stats_logbook().boulder_samples()), true);
+    }
 
-        let mut grade_sum = 0;
-        for (_, grade) in &routes_and_grades {
-            grade_sum += Hueco::from(grade.hueco.clone().unwrap()) as i32 - 1; // For some reason this needs to be -1, not sure why but it fixes everything here
+    fn render_pyramid(&self, ui: &mut eframe::egui::Ui, pyramid: &[(i32, usize)], boulder: bool) {
+        if pyramid.is_empty() {
+            ui.label("No sends yet");
+            return;
         }
-        if routes_and_grades.is_empty() {
-            return Hueco::None;
+        let most = pyramid.iter().map(|(_, count)| *count).max().unwrap_or(1);
+        for (grade, count) in pyramid.iter().take(8) { // Only the top of the pyramid
+            ui.horizontal(|ui| {
+                ui.add_sized([60.0, 18.0], egui::Label::new(self.settings.grade_name(*grade, boulder)));
+                let fill = f32::from(u16::try_from(*count).unwrap_or(u16::MAX)) / f32::from(u16::try_from(most).unwrap_or(u16::MAX));
+                ui.add(egui::ProgressBar::new(fill).desired_width(240.0).text(count.to_string()));
+            });
         }
-        let grade_num = grade_sum / routes_and_grades.len() as i32;
-        Hueco::from(grade_num)
     }
 
-    fn fav_style(&self) -> String {
-        // Get the favorite climbing style
-        
-        let mut routes: Vec<RouteModel> = Vec::new();
-        for session in &self.all_sessions {
-            let route = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route).unwrap().0.clone();
-            routes.push(route);
+    fn render_opinion_content(&self, ui: &mut eframe::egui::Ui) {
+        // Routes the climber graded differently from the given grade
+        ui.heading("Grade Opinions");
+        let describe = |disagreement: &GradeDisagreement, boulder: bool| format!(
+            "{}: {} (you: {}, {} send{})",
+            self.stats_logbook().route_name(disagreement.route),
+            self.settings.grade_name(disagreement.consensus, boulder),
+            self.settings.grade_name(stats::round_to_i32(disagreement.personal), boulder),
+            disagreement.votes,
+            if disagreement.votes == 1 { "" } else { "s" },
+        );
+        let mut opinions: Vec<(f64, String)> = stats::grade_disagreements(&self.stats_logbook().grade_opinions(false)).iter()
+            .map(|disagreement| (disagreement.difference(), describe(disagreement, false)))
+            .chain(stats::grade_disagreements(&self.stats_logbook().grade_opinions(true)).iter()
+                .map(|disagreement| (disagreement.difference(), describe(disagreement, true))))
+            .collect();
+        opinions.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        ui.label("Most Sandbagged:");
+        let sandbagged: Vec<&String> = opinions.iter().filter(|(difference, _)| *difference > 0.0).take(5).map(|(_, line)| line).collect();
+        if sandbagged.is_empty() {
+            ui.label("None yet, mark sends as hard when logging to see them here");
         }
-        let mut style_map = std::collections::HashMap::new();
-        for route in routes {
-            let count = style_map.entry(route.style.clone()).or_insert(0);
-            *count += 1;
+        for line in sandbagged {
+            ui.label(line);
         }
-        let mut max = 0;
-        let mut fav = String::new();
-        for (style, count) in style_map {
-            if count > max {
-                max = count;
-                fav = style;
-            }
+        ui.label("Softest:");
+        let soft: Vec<&String> = opinions.iter().rev().filter(|(difference, _)| *difference < 0.0).take(5).map(|(_, line)| line).collect();
+        if soft.is_empty() {
+            ui.label("None yet, mark sends as soft when logging to see them here");
+        }
+        for line in soft {
+            ui.label(line);
         }
-        fav
     }
 
-    fn fav_route(&self) -> String {
-        // Get the favorite route
-        let mut routes: Vec<RouteModel> = Vec::new();
-        for session in &self.all_sessions {
-            let route = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route).unwrap().0.clone();
-            routes.push(route);
-        }
-        let mut route_map = std::collections::HashMap::new();
-        for route in routes {
-            let count = route_map.entry(route.name.clone()).or_insert(0);
-            *count += 1;
-        }
-        let mut max = 0;
-        let mut fav = String::new();
-        for (route, count) in route_map {
-            if count > max {
-                max = count;
-                fav = route;
-            }
-        }
-        fav
+    fn render_rating_content(&self, ui: &mut eframe::egui::Ui) {
+        // Personal rating per discipline, charted with the flash and redpoint grades over time
+        ui.heading("Personal Rating");
+        let tall = self.stats_logbook().tall_samples();
+        let tall_history = rating::rating_history(tall.iter().copied());
+        ui.label(format!("Rating (Tall Wall): {}", self.describe_rating(tall_history.last(), false)));
+        self.render_rating_chart(ui, &tall, &tall_history, false);
+
+        ui.add_space(10.0);
+        let boulder = self.stats_logbook().boulder_samples();
+        let boulder_history = rating::rating_history(boulder.iter().copied());
+        ui.label(format!("Rating (Boulder): {}", self.describe_rating(boulder_history.last(), true)));
+        self.render_rating_chart(ui, &boulder, &boulder_history, true);
     }
 
-    fn fav_partner(&self) -> String {
-        // Get the favorite partner
-        let partners = self.all_sessions.iter().map(|session| session.partner.clone().unwrap_or("Solo".to_string()));
-        let mut partner_map = std::collections::HashMap::new();
-        for partner in partners {
-            let count = partner_map.entry(partner).or_insert(0);
-            *count += 1;
-        }
-        let mut max = 0;
-        let mut fav = "Solo".to_string();
-        for (partner, count) in partner_map {
-            if count > max {
-                max = count;
-                fav = partner;
-            }
+    fn render_rating_chart(&self, ui: &mut eframe::egui::Ui, samples: &[(i32, &SendModel)], history: &[RatingPoint], boulder: bool) {
+        // Rating with its confidence band, plus flash and redpoint grade steps, against the date
+        let day = |date: chrono::NaiveDate| f64::from(chrono::Datelike::num_days_from_ce(&date));
+        let z = self.settings.z_score();
+        let series = [
+            Series {
+                name: "Rating".to_string(),
+                color: egui::Color32::from_rgb(66, 133, 244),
+                points: history.iter().map(|point| (day(point.date), point.rating)).collect(),
+            },
+            Series {
+                name: "Flash Grade".to_string(),
+                color: egui::Color32::from_rgb(52, 168, 83),
+                points: stats::grade_metric_history(samples, &self.settings.flash, z).into_iter().map(|(date, grade)| (day(date), f64::from(grade))).collect(),
+            },
+            Series {
+                name: "Redpoint Grade".to_string(),
+                color: egui::Color32::from_rgb(234, 67, 53),
+                points: stats::grade_metric_history(samples, &self.settings.redpoint, z).into_iter().map(|(date, grade)| (day(date), f64::from(grade))).collect(),
+            },
+        ];
+        let band = Band {
+            name: format!("Rating {:.0}% Interval", self.settings.confidence * 100.0),
+            color: egui::Color32::from_rgba_unmultiplied(66, 133, 244, 40),
+            points: history.iter().map(|point| (day(point.date), point.rating - z * point.deviation, point.rating + z * point.deviation)).collect(),
+        };
+        charts::line_chart(
+            ui,
+            &series,
+            Some(&band),
+            |x| chrono::NaiveDate::from_num_days_from_ce_opt(stats::round_to_i32(x)).map_or_else(String::new, |date| date.to_string()),
+            |y| self.settings.grade_name(stats::round_to_i32(y), boulder),
+        );
+    }
+
+    fn describe_rating(&self, point: Option<&RatingPoint>, boulder: bool) -> String {
+        // Current rating as a grade with its uncertainty, e.g. "5.11b (±1.3 grades)"
+        match point {
+            Some(point) => format!(
+                "{} (±{:.1} grades at {:.0}%)",
+                self.settings.grade_name(stats::round_to_i32(point.rating), boulder),
+                self.settings.z_score() * point.deviation,
+                self.settings.confidence * 100.0,
+            ),
+            None => "Not enough data".to_string(),
         }
-        fav
     }
 
-    fn fav_crag(&self) -> String {
-        // Get the favorite crag
-        // Need to fix the location stuff in the database first
-        "Crag".to_string()
+    fn add_partner(&mut self) {
+        // Add the partner typed into the new partner field
+        let name = self.new_partner.trim().to_string();
+        self.cache.change(&[Data::Partners], move |db| async move { db.add_partner(&name).await.map(|_| ()) });
+        self.new_partner = String::new();
     }
 
-    fn flash_grade_tall(&self) -> Yosemite {
-        // Get the flash grade for tall walls
-        // Flash grade = hardest grade you can send first try 80% of the time
-        let mut routes_and_grades: Vec<(RouteModel, GradeModel, SendModel)> = Vec::new();
-        for session in &self.all_sessions {
-            
-            let route: (RouteModel, GradeModel) = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route).unwrap().clone();
-            if route.0.pitches == 0 {
-                continue;
-            }
-            routes_and_grades.push((route.0, route.1, session.clone()));
-        }
+    #[allow(clippy::needless_pass_by_value)]
+    fn render_partners(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+        // List partners, add or remove them, and see the hardest sends done together
+        self.header(&ctx);
+        ui.add_space(20.0);
+        ui.heading("Partners");
 
-        // flash_map is a hashmap of the grade and (non-flashed, flashed) sends
-        let mut flash_map: HashMap<GradeModel, (i32, i32)> = std::collections::HashMap::new();
-        for (_route, grade, session) in &routes_and_grades {
-            let count = flash_map.entry(grade.clone()).or_insert((0, 0));
-            if session.attempts == 1 {
-                count.1 += 1;
-            } else if session.r#type != "Repeat" {
-                count.0 += 1;
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut self.new_partner);
+            if ui.button("Add Partner").clicked() && !self.new_partner.trim().is_empty() {
+                self.add_partner();
             }
+        });
+        ui.separator();
+
+        if !self.cache.ready(ui, &LOGBOOK_DATA) {
+            return;
         }
-        let mut max_grade = -5;
-        for (grade, (non_flashed, flashed)) in flash_map {
-            if flashed as f32 / (flashed + non_flashed) as f32 >= 0.8 {
-                let grade_num = Yosemite::from(grade.yosemite.clone().unwrap());
-                if grade_num as i32 > max_grade {
-                    max_grade = grade_num as i32;
-                }
-            }
+        let partners = self.partners().to_vec();
+        if partners.is_empty() {
+            ui.label("No partners yet.");
+            return;
         }
-        Yosemite::from(max_grade)
+        ScrollArea::vertical().show(ui, |ui| {
+            let mut to_remove = None;
+            for partner in &partners {
+                ui.horizontal(|ui| {
+                    ui.label(&partner.name);
+                    if ui.button("View").clicked() {
+                        self.viewing_partner = Some(partner.clone());
+                        self.page = Page::ViewPartner;
+                    }
+                    if ui.button("Remove").clicked() {
+                        to_remove = Some(partner.id);
+                    }
+                });
+            }
+            if let Some(id) = to_remove {
+                self.cache.change(&[Data::Partners], move |db| db.remove_partner(id));
+            }
+            ui.separator();
 
+            ui.label("Hardest Sends with Partners:");
+            // The hardest sends overall and who was along for them, rather than the hardest of those done with someone
+            let partnered = |send: &SendModel| match self.partner_names(send.id).as_str() {
+                "None" => "solo".to_string(),
+                names => format!("with {names}"),
+            };
+            for (boulder, samples) in [(false, self.logbook().tall_samples()), (true, self.logbook().boulder_samples())] {
+                for (grade, send) in stats::hardest_sends(&samples, 5) {
+                    ui.label(format!("{} - {} ({}, {})", self.settings.grade_name(grade, boulder), self.logbook().route_name(send.route), send.date, partnered(send)));
+                }
+            }
+        });
     }
 
-    fn flash_grade_boulder(&self) -> Hueco {
-        // Get the flash grade for bouldering
-        // Flash grade = hardest grade you can send first try 80% of the time
-        let mut routes_and_grades: Vec<(RouteModel, GradeModel, SendModel)> = Vec::new();
-        for session in &self.all_sessions {
-            
-            let route: (RouteModel, GradeModel) = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route).unwrap().clone();
-            if route.0.pitches != 0 {
-                continue;
-            }
-            routes_and_grades.push((route.0, route.1, session.clone()));
+    #[allow(clippy::needless_pass_by_value)]
+    fn render_view_partner(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+        // Everything climbed with a single partner
+        self.header(&ctx);
+        ui.add_space(20.0);
+        let Some(partner) = self.viewing_partner.clone() else {
+            self.page = Page::Partners;
+            return;
+        };
+        ui.heading(&partner.name);
+        if !self.cache.ready(ui, &LOGBOOK_DATA) {
+            return;
         }
 
-        let mut flash_map: HashMap<GradeModel, (i32, i32)> = std::collections::HashMap::new();
-        for (_route, grade, session) in &routes_and_grades {
-            let count = flash_map.entry(grade.clone()).or_insert((0, 0));
-            if session.attempts == 1 {
-                count.1 += 1;
-            } else if session.r#type != "Repeat" {
-                count.0 += 1;
+        let profile = stats::partner_profile(partner.id, &self.all_sessions, self.routes_w_grades(), self.send_partners());
+        ScrollArea::vertical().show(ui, |ui| {
+            ui.label(format!("Days Together: {}", profile.days));
+            ui.label(format!("Sessions Together: {}", profile.sessions));
+            ui.label(format!("First Climbed Together: {}", profile.first.unwrap_or("Never".to_string())));
+            ui.label(format!("Last Climbed Together: {}", profile.last.unwrap_or("Never".to_string())));
+            ui.separator();
+            ui.label("Crags Visited Together:");
+            if profile.crags.is_empty() {
+                ui.label("None");
             }
-        }
-        let mut max_grade = -5;
-        for (grade, (non_flashed, flashed)) in flash_map {
-            if flashed as f32 / (flashed + non_flashed) as f32 >= 0.8 && flashed >= 10 {
-                let grade_num = Hueco::from(grade.hueco.clone().unwrap()) as i32 - 1;
-                if grade_num > max_grade {
-                    max_grade = grade_num;
-                }
+            for (crag, days) in &profile.crags {
+                ui.label(format!("{crag} ({days} days)"));
             }
+            ui.separator();
+            ui.label("Routes Climbed Together:");
+            if profile.routes.is_empty() {
+                ui.label("None");
+            }
+            for route in &profile.routes {
+                ui.label(route);
+            }
+        });
+        if ui.button("Back").clicked() {
+            self.page = Page::Partners;
         }
-        Hueco::from(max_grade)
     }
 
-    fn redpoint_grade_tall(&self) -> Yosemite {
-        // Get the redpoint grade
-        // Max grade you can get after projecting (80% chance of sending in one session)
-        let mut routes_and_grades: Vec<(RouteModel, GradeModel, SendModel)> = Vec::new();
-        for session in &self.all_sessions {
-            
-            let route: (RouteModel, GradeModel) = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route).unwrap().clone();
-            if route.0.pitches == 0 {
-                continue;
-            }
-            routes_and_grades.push((route.0, route.1, session.clone()));
+    #[allow(clippy::needless_pass_by_value)]
+    fn render_crags(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+        // Per crag summaries, filterable by name and by how far back to look
+        self.header(&ctx);
+        ui.add_space(20.0);
+        ui.heading("Crags");
+
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.text_edit_singleline(&mut self.crag_filter);
+            ui.label("Only use the last");
+            ui.add(egui::DragValue::new(&mut self.crag_window_days).range(0..=3650));
+            ui.label("days (0 for all time)");
+        });
+        ui.separator();
+
+        if !self.cache.ready(ui, &LOGBOOK_DATA) {
+            return;
         }
+        let since = (self.crag_window_days > 0)
+            .then(|| chrono::Local::now().date_naive() - chrono::Duration::days(i64::from(self.crag_window_days) - 1));
+        let filter = self.crag_filter.trim().to_lowercase();
+        let summaries: Vec<stats::CragSummary> = stats::crag_summaries(&self.logbook().tall_samples(), &self.logbook().boulder_samples(), self.routes_w_grades(), since)
+            .into_iter()
+            .filter(|summary| summary.name.to_lowercase().contains(&filter))
+            .collect();
 
-        let mut redpoint_map: HashMap<GradeModel, (i32, i32)> = std::collections::HashMap::new();
-        for (_route, grade, session) in &routes_and_grades {
-            let count = redpoint_map.entry(grade.clone()).or_insert((0, 0));
-            if session.r#type == "Redpoint" || session.r#type == "Onsight" || session.r#type == "Flash" {
-                count.1 += 1;
-            } else if session.r#type != "Repeat" {
-                count.0 += 1;
+        ScrollArea::vertical().show(ui, |ui| {
+            if summaries.is_empty() {
+                ui.label("No crags found. Add a location to your routes to see them here.");
             }
-        }
-        
-        let mut max_grade = -5;
-        for (grade, (non_redpoint, redpoint)) in redpoint_map {
-            if redpoint as f32 / (redpoint + non_redpoint) as f32 >= 0.8 && redpoint >= 5 {
-                let grade_num = Yosemite::from(grade.yosemite.clone().unwrap()) as i32;
-                if grade_num > max_grade {
-                    max_grade = grade_num;
-                }
+            for summary in &summaries {
+                egui::CollapsingHeader::new(&summary.name).id_source(&summary.name).show(ui, |ui| {
+                    ui.label(format!("Sends: {}", summary.sends));
+                    ui.label(format!("Days Climbed: {}", summary.visits.len()));
+                    ui.label(format!("Routes Ticked: {} of {} ({:.0}%)", summary.ticked, summary.routes, summary.tick_rate() * 100.0));
+                    if let Some((grade, route)) = summary.hardest_tall {
+                        ui.label(format!("Hardest Tall Wall Send: {} ({})", self.settings.grade_name(grade, false), self.logbook().route_name(route)));
+                    }
+                    if let Some((grade, route)) = summary.hardest_boulder {
+                        ui.label(format!("Hardest Boulder Send: {} ({})", self.settings.grade_name(grade, true), self.logbook().route_name(route)));
+                    }
+                    ui.label("Visits:");
+                    for date in summary.visits.iter().rev() {
+                        ui.label(date);
+                    }
+                });
             }
-        }
-        Yosemite::from(max_grade)
+            ui.separator();
+
+            ui.label("Favorite Crag by Year:");
+            for (year, crag, days) in stats::favourite_crag_by_year(&self.all_sessions, self.routes_w_grades()) {
+                ui.label(format!("{year}: {crag} ({days} days)"));
+            }
+        });
     }
 
-    fn redpoint_grade_boulder(&self) -> Hueco {
-        // Get the redpoint grade for bouldering
-        let mut routes_and_grades: Vec<(RouteModel, GradeModel, SendModel)> = Vec::new();
-        for session in &self.all_sessions {
-            
-            let route: (RouteModel, GradeModel) = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route).unwrap().clone();
-            if route.0.pitches > 0 {
-                continue;
+    fn set_recap_year(&mut self, year: i32) {
+        // Point the recap at a whole calendar year
+        self.recap_from = format!("{year}-01-01");
+        self.recap_to = format!("{year}-12-31");
+        self.recap_path = format!("recap-{year}.html");
+        self.recap_message = None;
+    }
+
+    #[allow(clippy::too_many_lines)] //This function is long, but it's mostly just UI stuff
+    #[allow(clippy::needless_pass_by_value)]
+    fn render_recap(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+        // Year in review, or any other date range, with an HTML export
+        self.header(&ctx);
+        ui.add_space(20.0);
+        ui.heading("Recap");
+
+        let this_year = chrono::Datelike::year(&chrono::Local::now().date_naive());
+        ui.horizontal(|ui| {
+            ui.label("From:");
+            ui.add(egui::TextEdit::singleline(&mut self.recap_from).desired_width(90.0));
+            ui.label("To:");
+            ui.add(egui::TextEdit::singleline(&mut self.recap_to).desired_width(90.0));
+            if ui.button("This Year").clicked() {
+                self.set_recap_year(this_year);
+            }
+            if ui.button("Last Year").clicked() {
+                self.set_recap_year(this_year - 1);
             }
-            routes_and_grades.push((route.0, route.1, session.clone()));
+        });
+        let (Ok(from), Ok(to)) = (chrono::NaiveDate::from_str(&self.recap_from), chrono::NaiveDate::from_str(&self.recap_to)) else {
+            ui.colored_label(egui::Color32::RED, "Dates must be in YYYY-MM-DD format.");
+            return;
+        };
+        if from > to {
+            ui.colored_label(egui::Color32::RED, "The start date must be before the end date.");
+            return;
+        }
+        if !self.cache.ready(ui, &LOGBOOK_DATA) {
+            return;
         }
 
-        let mut redpoint_map: HashMap<GradeModel, (i32, i32)> = std::collections::HashMap::new();
-        for (_route, grade, session) in &routes_and_grades {
-            let count = redpoint_map.entry(grade.clone()).or_insert((0, 0));
-            if session.r#type == "Redpoint" || session.r#type == "Onsight" || session.r#type == "Flash" {
-                count.1 += 1;
-            } else if session.r#type != "Repeat" {
-                count.0 += 1;
+        let recap = Recap::build(from, to, &self.all_sessions, &self.logbook().tall_samples(), &self.logbook().boulder_samples(), self.routes_w_grades(),
+            self.partners(), self.send_partners(), (self.settings.rope_system, self.settings.boulder_system), self.settings.units);
+        ui.horizontal(|ui| {
+            ui.label("Export to:");
+            ui.text_edit_singleline(&mut self.recap_path);
+            if ui.button("Export HTML").clicked() {
+                self.recap_message = Some(match std::fs::write(&self.recap_path, recap.to_html()) {
+                    Ok(()) => format!("Saved to {}", self.recap_path),
+                    Err(error) => format!("Could not save recap: {error}"),
+                });
             }
+        });
+        if let Some(message) = &self.recap_message {
+            ui.label(message);
         }
-        
-        let mut max_grade = -5;
-        for (grade, (non_redpoint, redpoint)) in redpoint_map {
-            if redpoint as f32 / (redpoint + non_redpoint) as f32 >= 0.8 && redpoint >= 5 {
-                let grade_num = Hueco::from(grade.hueco.clone().unwrap()) as i32;
-                if grade_num > max_grade {
-                    max_grade = grade_num;
+        ui.separator();
+
+        ScrollArea::vertical().show(ui, |ui| {
+            ui.heading(recap.title());
+            ui.label(format!("Days Climbing: {}", recap.days));
+            ui.label(format!("Sessions: {}", recap.sessions));
+            ui.label(format!("Sends: {}", recap.sends));
+            ui.label(format!("Attempts: {}", recap.attempts));
+            ui.label(format!("Vertical: {}", self.settings.length(recap.vertical)));
+            ui.label(format!("Longest Streak: {}", recap.describe_streak()));
+            ui.separator();
+            for (heading, sends) in [("Hardest Tall Wall Sends:", &recap.hardest_tall), ("Hardest Boulder Sends:", &recap.hardest_boulder), ("Personal Records:", &recap.records)] {
+                ui.label(heading);
+                if sends.is_empty() {
+                    ui.label("None");
+                }
+                for send in sends {
+                    ui.label(format!("{} - {} ({})", send.grade_name, send.route, send.date));
                 }
+                ui.separator();
             }
-        }
-        Hueco::from(max_grade)
-    }
+            ui.label(format!("New Crags: {}", if recap.new_crags.is_empty() { "None".to_string() } else { recap.new_crags.join(", ") }));
+            ui.label("Top Partners:");
+            if recap.top_partners.is_empty() {
+                ui.label("None");
+            }
+            for (name, days) in &recap.top_partners {
+                ui.label(format!("{name} ({days} days)"));
+            }
+            ui.separator();
 
-    fn top_tall_grade(&self) -> (Yosemite, String) {
-        // Get the top tall wall grade
-        let mut routes_and_grades: Vec<(RouteModel, GradeModel)> = Vec::new();
-        for session in &self.all_sessions {
-            
-            if let Some(route) = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route) {
-                if route.0.pitches == 0 {
-                    continue;
-                }
-                routes_and_grades.push(route.clone());
+            ui.label("Grade Progression:");
+            let month_label = |x: f64| chrono::NaiveDate::from_num_days_from_ce_opt(stats::round_to_i32(x)).map_or(String::new(), |date| date.format("%b %Y").to_string());
+            let to_points = |grade: fn(&recap::ProgressPoint) -> Option<i32>| -> Vec<(f64, f64)> {
+                recap.progression.iter()
+                    .filter_map(|point| grade(point).map(|grade| (f64::from(chrono::Datelike::num_days_from_ce(&point.month)), f64::from(grade))))
+                    .collect()
+            };
+            let tall = to_points(|point| point.tall);
+            if !tall.is_empty() {
+                charts::line_chart(ui, &[Series { name: "Tall Wall".to_string(), color: egui::Color32::LIGHT_BLUE, points: tall }], None,
+                    month_label, |y| self.settings.grade_name(stats::round_to_i32(y), false));
             }
-        }
-        let mut max_grade = -5;
-        let mut hardest_route: Option<RouteModel> = None;
-        for (route, grade) in &routes_and_grades {
-            let grade_num = Yosemite::from(grade.yosemite.clone().unwrap()) as i32;
-            if grade_num > max_grade {
-                max_grade = grade_num;
-                hardest_route = Some(route.clone());
+            let boulder = to_points(|point| point.boulder);
+            if !boulder.is_empty() {
+                charts::line_chart(ui, &[Series { name: "Boulder".to_string(), color: egui::Color32::LIGHT_RED, points: boulder }], None,
+                    month_label, |y| self.settings.grade_name(stats::round_to_i32(y), true));
             }
-        }
-        if let Some(route) = hardest_route.clone() {
-            return (Yosemite::from(max_grade), route.name.clone());
-        } else {
-            return (Yosemite::None, "None".to_string());
-        }
+        });
     }
 
-    fn top_boulder_grade(&self) -> (Hueco, String) {
-        // Get the top boulder grade
-        let mut routes_and_grades: Vec<(RouteModel, GradeModel)> = Vec::new();
-        for session in &self.all_sessions {
-            if let Some(route) = self.routes_w_grades.iter().find(|(route, _)| route.id == session.route) {
-                if route.0.pitches != 0 {
-                    continue;
+    #[allow(clippy::needless_pass_by_value)]
+    fn render_convert(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+        // Pick a grade in any system and see it in all the others
+        self.header(&ctx);
+        ui.add_space(20.0);
+        ui.heading("Grade Conversion");
+
+        let current = conversion::system(self.convert_grade);
+        let mut picked = current;
+        egui::ComboBox::from_label("System")
+            .selected_text(format!("{current}"))
+            .show_ui(ui, |ui| {
+                for system in GradeSystem::iter() {
+                    ui.selectable_value(&mut picked, system, format!("{system}"));
                 }
-                routes_and_grades.push(route.clone());
-            }
+            });
+        if picked != current { // Keep the same difficulty when switching systems
+            self.convert_grade = conversion::convert(self.convert_grade, picked).unwrap_or(picked.grades()[0]);
         }
-        let mut max_grade = -5;
-        let mut hardest_route: Option<RouteModel> = None;
-        for (route, grade) in &routes_and_grades {
-            let grade_num = Hueco::from(grade.hueco.clone().unwrap()) as i32 - 1;
-            if grade_num > max_grade {
-                max_grade = grade_num;
-                hardest_route = Some(route.clone());
+        egui::ComboBox::from_label("Grade")
+            .selected_text(format!("{}", self.convert_grade))
+            .show_ui(ui, |ui| {
+                for grade in picked.grades() {
+                    ui.selectable_value(&mut self.convert_grade, grade, format!("{grade}"));
+                }
+            });
+        ui.separator();
+
+        egui::Grid::new("conversions").striped(true).show(ui, |ui| {
+            for grade in conversion::equivalents(self.convert_grade) {
+                ui.label(format!("{}", conversion::system(grade)));
+                ui.label(format!("{grade}"));
+                ui.end_row();
             }
-        }
-        if let Some(route) = hardest_route.clone() {
-            return (Hueco::from(max_grade), route.name.clone());
-        } else {
-            return (Hueco::None, "None".to_string());
-        }
-        
+        });
+        ui.label("Conversions between bouldering and roped grades are rough.");
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn render_settings(&mut self, ctx: eframe::egui::Context, ui: &mut eframe::egui::Ui) {
+        // Edit and save user settings
+        self.header(&ctx);
+        ui.add_space(20.0);
+        ui.heading("Settings");
 
+        ScrollArea::vertical().show(ui, |ui| {
+            ui.label("Flash Grade:");
+            Self::render_grade_metric(ui, &mut self.settings_options.flash);
+            ui.separator();
+
+            ui.label("Redpoint Grade:");
+            Self::render_grade_metric(ui, &mut self.settings_options.redpoint);
+            ui.separator();
+
+            egui::ComboBox::from_label("Confidence Level")
+                .selected_text(format!("{:.0}%", self.settings_options.confidence * 100.0))
+                .show_ui(ui, |ui| {
+                    for (level, _) in CONFIDENCE_LEVELS {
+                        ui.selectable_value(&mut self.settings_options.confidence, level, format!("{:.0}%", level * 100.0));
+                    }
+                });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label("Slash grades and ranges count as:");
+                for range_grade in RangeGrade::iter() {
+                    ui.radio_value(&mut self.settings_options.range_grade, range_grade, format!("{range_grade}"));
+                }
+            });
+            ui.separator();
+
+            egui::ComboBox::from_label("Route Grades")
+                .selected_text(self.settings_options.rope_system.to_string())
+                .show_ui(ui, |ui| {
+                    for system in GradeSystem::iter().filter(|system| !system.is_boulder()) {
+                        ui.selectable_value(&mut self.settings_options.rope_system, system, system.to_string());
+                    }
+                });
+            egui::ComboBox::from_label("Boulder Grades")
+                .selected_text(self.settings_options.boulder_system.to_string())
+                .show_ui(ui, |ui| {
+                    for system in GradeSystem::iter().filter(|system| system.is_boulder()) {
+                        ui.selectable_value(&mut self.settings_options.boulder_system, system, system.to_string());
+                    }
+                });
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.label("Lengths in:");
+                for units in LengthUnit::iter() {
+                    ui.radio_value(&mut self.settings_options.units, units, units.to_string());
+                }
+            });
+            ui.separator();
+
+            ui.checkbox(&mut self.settings_options.personal_grades, "Use my own grades for stats (from soft/hard/other grade when logging)");
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.button("Save").clicked() {
+                    let settings = self.settings_options.clone();
+                    self.cache.change(&[], move |db| async move { settings.save(db).await });
+                    self.settings = self.settings_options.clone();
+                    self.reset();
+                }
+                if ui.button("Restore Defaults").clicked() {
+                    self.settings_options = Settings { climber: self.settings.climber, ..Settings::default() };
+                }
+            });
+        });
     }
 
-    fn other_stats(&self) -> String {
-        // Get other stats from notes
-        "Other stats may be added in the future.".to_string()
+    fn render_grade_metric(ui: &mut eframe::egui::Ui, metric: &mut GradeMetric) {
+        // Controls for one grade metric definition
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("Success rate needed:");
+                let mut percent = metric.threshold * 100.0;
+                if ui.add(egui::Slider::new(&mut percent, 50.0..=100.0).suffix("%")).changed() {
+                    metric.threshold = percent / 100.0;
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Minimum sends at a grade:");
+                ui.add(egui::DragValue::new(&mut metric.min_sample).range(1..=100));
+            });
+            ui.horizontal(|ui| {
+                ui.label("Only use the last");
+                ui.add(egui::DragValue::new(&mut metric.window_days).range(0..=3650));
+                ui.label("days (0 for all time)");
+            });
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Counts as a success:");
+                for send_type in SendType::iter() {
+                    let mut counts = metric.send_types.contains(&send_type);
+                    if ui.checkbox(&mut counts, format!("{send_type}")).changed() {
+                        if counts {
+                            metric.send_types.push(send_type);
+                        } else {
+                            metric.send_types.retain(|kept| *kept != send_type);
+                        }
+                    }
+                }
+            });
+            ui.checkbox(&mut metric.conservative, "Use the lower end of the confidence interval");
+        });
     }
 
     fn render_exit(&mut self, ui: &mut eframe::egui::Ui) {
@@ -1357,19 +2082,24 @@ impl MyApp {
         self.route_options = RouteOptions::default();
         self.removal_name = String::new();
         self.find_name = String::new();
-        self.all_routes = Arc::new(Mutex::new(Vec::new()));
-        self.search_result = Arc::new(Mutex::new(None));
+        self.search_result = None;
         self.viewing = None;
         self.send_options = SendOptions::default();
         self.session = Vec::new();
         self.session.push(SendOptions::default());
-        self.cur_session = Arc::new(Mutex::new(Vec::new()));
         self.view_session = None;
-        self.add_grade = FullGrade::default();
+        self.add_grade = Grade::Yosemite(Yosemite::FiveTenA);
         self.remove_grade = FullGrade::default();
         self.session_id = 0;
-        self.all_sessions_buffer = Arc::new(Mutex::new(Vec::new()));
-        self.all_sessions = Vec::new();
+        self.settings_options = self.settings.clone();
+        self.new_climber = String::new();
+        self.new_partner = String::new();
+        self.viewing_partner = None;
+        self.crag_filter = String::new();
+        self.crag_window_days = 0;
+        self.recap_message = None;
+        self.search = SearchOptions::default();
+        self.filter = FilterOptions::default();
     }
 }
 
@@ -1379,6 +2109,9 @@ impl App for MyApp {
     fn update(&mut self, context: &eframe::egui::Context, frame: &mut eframe::Frame) {
         // Control function to move between pages. Also adds the image loaders
         egui_extras::install_image_loaders(context);
+        if self.cache.poll(context) {
+            self.pick_climber_sends();
+        }
         CentralPanel::default().show(context, |ui| {
             match self.page {
                 Page::Home => self.render_home(context),
@@ -1390,11 +2123,19 @@ impl App for MyApp {
                 Page::FindRoute => self.render_find_route(context.clone(), ui),
                 Page::ViewRoute => self.render_view_route(context.clone(), ui),
                 Page::ViewAllRoutes => self.render_all_routes(context.clone(), ui),
+                Page::SearchLogbook => self.render_search_logbook(context.clone(), ui),
                 Page::LogSession => self.render_log_session(context.clone(), ui),
                 Page::RemoveSession => self.render_delete_session(context.clone(), ui),
                 Page::ViewSession => self.render_view_session(context.clone(), ui),
                 Page::History => self.render_history(context.clone(), ui),
+                Page::Partners => self.render_partners(context.clone(), ui),
+                Page::ViewPartner => self.render_view_partner(context.clone(), ui),
+                Page::Crags => self.render_crags(context.clone(), ui),
+                Page::Recap => self.render_recap(context.clone(), ui),
+                Page::Convert => self.render_convert(context.clone(), ui),
                 Page::Stats => self.render_stats(context.clone(), ui),
+                Page::Team => self.render_team(context.clone(), ui),
+                Page::Settings => self.render_settings(context.clone(), ui),
                 Page::Exit => self.render_exit(ui),
             }
         });
@@ -0,0 +1,424 @@
+use std::{collections::HashMap, future::Future, sync::{mpsc::{self, Receiver, Sender}, Arc}};
+use eframe::egui;
+use sea_orm::DbErr;
+use tokio::runtime::Runtime;
+use climb_log_core::{climbing::Style, filter::Filter, logbook, search::TextMatch, store::LogbookStore};
+use climb_log_core::routes_db::{entities::{climbers::Model as ClimberModel, grades::Model as GradeModel, partners::Model as PartnerModel, routes::Model as RouteModel, send_partners::Model as SendPartnerModel, sends::Model as SendModel}, RoutesDb};
+
+// Everything the pages show, loaded off the UI thread and handed back over a channel
+// A query only runs when a page first wants its data or after a change made it stale, never once per frame
+// Old values stay up while a reload runs, so pages don't flicker back to "Loading..."
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Data { // What a query loads, and what a change can make stale
+    Sends,
+    Routes,
+    RouteStyles,
+    Partners, // Partners and which sends they were on
+    Climbers,
+    Search, // Matches for the last text searched for
+    FilteredSends, // The climber's sends matching the filter in use
+    FilteredRoutes, // Routes matching the filter in use
+}
+
+enum Event { // Sent back from the runtime when a query or change finishes
+    Sends(Vec<SendModel>),
+    Routes(Vec<(RouteModel, GradeModel)>),
+    RouteStyles(HashMap<i32, Vec<Style>>),
+    Partners(Vec<PartnerModel>, Vec<SendPartnerModel>),
+    Climbers(Vec<ClimberModel>),
+    Searched(String, Vec<TextMatch>),
+    FilteredSends(Filter, i32, Vec<SendModel>),
+    FilteredRoutes(Filter, i32, Vec<(RouteModel, GradeModel)>),
+    LoadFailed(Data, String),
+    Changed(Vec<Data>),
+    ChangeFailed(String),
+}
+
+pub struct Cached<T> { // One set of loaded data and where its query is at
+    value: T, // Empty until the first load finishes
+    loaded: bool,
+    loading: bool, // A query is running
+    stale: bool, // Changed since it was loaded, reloaded the next time a page wants it
+    error: Option<String>, // Why the last load failed, kept until a retry
+}
+
+impl<T: Default> Default for Cached<T> {
+    fn default() -> Self {
+        Cached { value: T::default(), loaded: false, loading: false, stale: false, error: None }
+    }
+}
+
+impl<T> Cached<T> {
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    fn needs_load(&self) -> bool {
+        !self.loading && self.error.is_none() && (!self.loaded || self.stale)
+    }
+
+    fn start(&mut self) {
+        self.loading = true;
+        self.stale = false;
+    }
+
+    fn finish(&mut self, value: T) {
+        self.value = value;
+        self.loaded = true;
+        self.loading = false;
+    }
+
+    fn fail(&mut self, error: String) {
+        self.loading = false;
+        self.error = Some(error);
+    }
+
+    fn invalidate(&mut self) {
+        // A failed load gets another go too, the change may have fixed it
+        self.stale = true;
+        self.error = None;
+    }
+}
+
+pub struct Cache {
+    db: RoutesDb,
+    rt: Arc<Option<Runtime>>,
+    sender: Sender<Event>,
+    receiver: Receiver<Event>,
+    ctx: Option<egui::Context>, // Woken up when a result comes in, so it shows without waiting for input
+    pub sends: Cached<Vec<SendModel>>, // Every climber's sends
+    pub routes: Cached<Vec<(RouteModel, GradeModel)>>,
+    pub route_styles: Cached<HashMap<i32, Vec<Style>>>,
+    pub partners: Cached<Vec<PartnerModel>>,
+    pub send_partners: Cached<Vec<SendPartnerModel>>, // Loaded along with partners
+    pub climbers: Cached<Vec<ClimberModel>>,
+    pub text_matches: Cached<Vec<TextMatch>>,
+    searched: String, // Text the matches are for, or are being found for
+    pub filtered_sends: Cached<Vec<SendModel>>,
+    pub filtered_routes: Cached<Vec<(RouteModel, GradeModel)>>,
+    filtered: (Filter, i32), // Filter and climber the filtered data is for, or is being loaded for
+    pub change_error: Option<String>, // Why the last change failed, shown in the header
+}
+
+impl Cache {
+    pub fn new(db: RoutesDb, rt: Arc<Option<Runtime>>) -> Cache {
+        let (sender, receiver) = mpsc::channel();
+        Cache {
+            db,
+            rt,
+            sender,
+            receiver,
+            ctx: None,
+            sends: Cached::default(),
+            routes: Cached::default(),
+            route_styles: Cached::default(),
+            partners: Cached::default(),
+            send_partners: Cached::default(),
+            climbers: Cached::default(),
+            text_matches: Cached::default(),
+            searched: String::new(),
+            filtered_sends: Cached::default(),
+            filtered_routes: Cached::default(),
+            filtered: (Filter::default(), 1),
+            change_error: None,
+        }
+    }
+
+    fn spawn(&self, task: impl Future<Output = Event> + Send + 'static) {
+        let sender = self.sender.clone();
+        let ctx = self.ctx.clone();
+        self.rt.as_ref().as_ref().unwrap().spawn(async move {
+            // The app may be closing, nothing's left to tell then
+            let _ = sender.send(task.await);
+            if let Some(ctx) = ctx {
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    fn needs_load(&self, data: Data) -> bool {
+        match data {
+            Data::Sends => self.sends.needs_load(),
+            Data::Routes => self.routes.needs_load(),
+            Data::RouteStyles => self.route_styles.needs_load(),
+            Data::Partners => self.partners.needs_load(),
+            Data::Climbers => self.climbers.needs_load(),
+            Data::Search => self.text_matches.needs_load(),
+            Data::FilteredSends => self.filtered_sends.needs_load(),
+            Data::FilteredRoutes => self.filtered_routes.needs_load(),
+        }
+    }
+
+    fn start(&mut self, data: Data) {
+        match data {
+            Data::Sends => self.sends.start(),
+            Data::Routes => self.routes.start(),
+            Data::RouteStyles => self.route_styles.start(),
+            Data::Partners => self.partners.start(),
+            Data::Climbers => self.climbers.start(),
+            Data::Search => self.text_matches.start(),
+            Data::FilteredSends => self.filtered_sends.start(),
+            Data::FilteredRoutes => self.filtered_routes.start(),
+        }
+    }
+
+    fn error(&self, data: Data) -> Option<&str> {
+        match data {
+            Data::Sends => self.sends.error.as_deref(),
+            Data::Routes => self.routes.error.as_deref(),
+            Data::RouteStyles => self.route_styles.error.as_deref(),
+            Data::Partners => self.partners.error.as_deref(),
+            Data::Climbers => self.climbers.error.as_deref(),
+            Data::Search => self.text_matches.error.as_deref(),
+            Data::FilteredSends => self.filtered_sends.error.as_deref(),
+            Data::FilteredRoutes => self.filtered_routes.error.as_deref(),
+        }
+    }
+
+    fn is_loaded(&self, data: Data) -> bool {
+        match data {
+            Data::Sends => self.sends.loaded,
+            Data::Routes => self.routes.loaded,
+            Data::RouteStyles => self.route_styles.loaded,
+            Data::Partners => self.partners.loaded,
+            Data::Climbers => self.climbers.loaded,
+            Data::Search => self.text_matches.loaded,
+            Data::FilteredSends => self.filtered_sends.loaded,
+            Data::FilteredRoutes => self.filtered_routes.loaded,
+        }
+    }
+
+    pub fn invalidate(&mut self, data: Data) {
+        match data {
+            Data::Sends => self.sends.invalidate(),
+            Data::Routes => self.routes.invalidate(),
+            Data::RouteStyles => self.route_styles.invalidate(),
+            Data::Partners => self.partners.invalidate(),
+            Data::Climbers => self.climbers.invalidate(),
+            Data::Search => self.text_matches.invalidate(),
+            Data::FilteredSends => self.filtered_sends.invalidate(),
+            Data::FilteredRoutes => self.filtered_routes.invalidate(),
+        }
+    }
+
+    /// Starts loading data that isn't loaded yet or went stale. Cheap to call every frame.
+    pub fn request(&mut self, data: Data) {
+        if !self.needs_load(data) {
+            return;
+        }
+        self.start(data);
+        let db = self.db.clone();
+        let searched = self.searched.clone();
+        let (filter, climber) = self.filtered.clone();
+        self.spawn(async move {
+            let loaded = match data {
+                Data::Sends => db.get_all_sends().await.map(Event::Sends),
+                Data::Routes => db.find_all_routes_and_grade().await.map(Event::Routes),
+                Data::RouteStyles => db.get_all_route_styles().await.map(|styles| Event::RouteStyles(logbook::styles_by_route(styles))),
+                Data::Partners => match db.clone().get_all_partners().await {
+                    Ok(partners) => db.get_all_send_partners().await.map(|send_partners| Event::Partners(partners, send_partners)),
+                    Err(error) => Err(error),
+                },
+                Data::Climbers => db.get_all_climbers().await.map(Event::Climbers),
+                Data::Search => db.search_text(&searched).await.map(|matches| Event::Searched(searched, matches)),
+                Data::FilteredSends => db.filter_sends(&filter, climber).await.map(|sends| Event::FilteredSends(filter, climber, sends)),
+                Data::FilteredRoutes => db.filter_routes(&filter, climber).await.map(|routes| Event::FilteredRoutes(filter, climber, routes)),
+            };
+            loaded.unwrap_or_else(|error| Event::LoadFailed(data, error.to_string()))
+        });
+    }
+
+    /// Finds matches for the text, once per text searched for and again after a change.
+    pub fn search(&mut self, text: &str) {
+        if text != self.searched {
+            self.searched = text.to_string();
+            self.text_matches.invalidate();
+        }
+        self.request(Data::Search);
+    }
+
+    /// Sets the filter and climber the filtered sends and routes are for, reloading them if either changed.
+    pub fn filter(&mut self, filter: &Filter, climber: i32) {
+        if self.filtered.0 != *filter || self.filtered.1 != climber {
+            self.filtered = (filter.clone(), climber);
+            self.filtered_sends.invalidate();
+            self.filtered_routes.invalidate();
+        }
+    }
+
+    /// Runs a change in the background, then marks the data it touched as stale.
+    pub fn change<F, Fut>(&mut self, touches: &[Data], change: F)
+    where
+        F: FnOnce(RoutesDb) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), DbErr>> + Send,
+    {
+        let db = self.db.clone();
+        let touches = touches.to_vec();
+        self.spawn(async move {
+            match change(db).await {
+                Ok(()) => Event::Changed(touches),
+                Err(error) => Event::ChangeFailed(error.to_string()),
+            }
+        });
+    }
+
+    /// Takes in every result that's come back since the last frame. True if any data changed.
+    pub fn poll(&mut self, ctx: &egui::Context) -> bool {
+        self.ctx.get_or_insert_with(|| ctx.clone());
+        let mut changed = false;
+        while let Ok(event) = self.receiver.try_recv() {
+            changed = true;
+            match event {
+                Event::Sends(sends) => self.sends.finish(sends),
+                Event::Routes(routes) => self.routes.finish(routes),
+                Event::RouteStyles(route_styles) => self.route_styles.finish(route_styles),
+                Event::Partners(partners, send_partners) => {
+                    self.partners.finish(partners);
+                    self.send_partners.finish(send_partners);
+                }
+                Event::Climbers(climbers) => self.climbers.finish(climbers),
+                // Matches for text since changed are dropped, the next search asks for the new text
+                Event::Searched(text, matches) if text == self.searched => self.text_matches.finish(matches),
+                Event::Searched(..) => self.text_matches.loading = false,
+                // Same for a filter or climber since changed
+                Event::FilteredSends(filter, climber, sends) if filter == self.filtered.0 && climber == self.filtered.1 => self.filtered_sends.finish(sends),
+                Event::FilteredSends(..) => self.filtered_sends.loading = false,
+                Event::FilteredRoutes(filter, climber, routes) if filter == self.filtered.0 && climber == self.filtered.1 => self.filtered_routes.finish(routes),
+                Event::FilteredRoutes(..) => self.filtered_routes.loading = false,
+                Event::LoadFailed(data, error) => match data {
+                    Data::Sends => self.sends.fail(error),
+                    Data::Routes => self.routes.fail(error),
+                    Data::RouteStyles => self.route_styles.fail(error),
+                    Data::Partners => self.partners.fail(error),
+                    Data::Climbers => self.climbers.fail(error),
+                    Data::Search => self.text_matches.fail(error),
+                    Data::FilteredSends => self.filtered_sends.fail(error),
+                    Data::FilteredRoutes => self.filtered_routes.fail(error),
+                },
+                Event::Changed(touches) => {
+                    self.change_error = None;
+                    // Routes, sends and partners are what the search index is made from
+                    if touches.iter().any(|data| matches!(data, Data::Sends | Data::Routes | Data::Partners)) {
+                        self.invalidate(Data::Search);
+                    }
+                    // And with styles, what filters look at
+                    if touches.iter().any(|data| matches!(data, Data::Sends | Data::Routes | Data::Partners | Data::RouteStyles)) {
+                        self.invalidate(Data::FilteredSends);
+                        self.invalidate(Data::FilteredRoutes);
+                    }
+                    for data in touches {
+                        self.invalidate(data);
+                    }
+                }
+                Event::ChangeFailed(error) => self.change_error = Some(error),
+            }
+        }
+        changed
+    }
+
+    /// Requests the data a page needs and shows a spinner or the error until it's all there.
+    /// True once the page can be drawn.
+    pub fn ready(&mut self, ui: &mut egui::Ui, wanted: &[Data]) -> bool {
+        for data in wanted {
+            self.request(*data);
+        }
+        if let Some((data, error)) = wanted.iter().find_map(|data| self.error(*data).map(|error| (*data, error.to_string()))) {
+            ui.colored_label(egui::Color32::RED, format!("Could not load {data:?}: {error}"));
+            if ui.button("Retry").clicked() {
+                self.invalidate(data);
+            }
+            return false;
+        }
+        if wanted.iter().all(|data| self.is_loaded(*data)) {
+            return true;
+        }
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label("Loading...");
+        });
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use climb_log_core::settings::Settings;
+
+    fn cache() -> (Cache, Arc<Option<Runtime>>) {
+        let rt = Arc::new(Some(Runtime::new().unwrap()));
+        let db = rt.as_ref().as_ref().unwrap().block_on(RoutesDb::open("sqlite::memory:")).unwrap();
+        (Cache::new(db, Arc::clone(&rt)), rt)
+    }
+
+    fn wait(cache: &mut Cache, ctx: &egui::Context) {
+        // Results come in on another thread, give them a moment
+        for _ in 0..200 {
+            if cache.poll(ctx) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        panic!("nothing came back from the runtime");
+    }
+
+    #[test]
+    fn queries_run_once_until_a_change() {
+        let (mut cache, _rt) = cache();
+        let ctx = egui::Context::default();
+        cache.poll(&ctx);
+        cache.request(Data::Climbers);
+        assert!(cache.climbers.loading);
+        // Asking again while it runs, or once it's loaded, doesn't start another query
+        cache.request(Data::Climbers);
+        wait(&mut cache, &ctx);
+        assert!(cache.climbers.loaded);
+        assert_eq!(cache.climbers.value().len(), 1);
+        cache.request(Data::Climbers);
+        assert!(!cache.climbers.loading);
+
+        // A change marks what it touched as stale, the old value stays up while it reloads
+        cache.change(&[Data::Climbers], |db| async move { db.add_climber("Sam").await.map(|_| ()) });
+        wait(&mut cache, &ctx);
+        assert!(cache.climbers.stale);
+        cache.request(Data::Climbers);
+        assert_eq!(cache.climbers.value().len(), 1);
+        wait(&mut cache, &ctx);
+        assert_eq!(cache.climbers.value().iter().map(|climber| climber.name.as_str()).collect::<Vec<_>>(), vec!["Me", "Sam"]);
+    }
+
+    #[test]
+    fn filtered_data_follows_the_filter() {
+        let (mut cache, _rt) = cache();
+        let ctx = egui::Context::default();
+        cache.poll(&ctx);
+        cache.filter(&Filter::parse("type:onsight", &Settings::default()).unwrap(), 1);
+        cache.request(Data::FilteredSends);
+        // Results for a filter changed while they load are dropped, not shown under the new one
+        cache.filter(&Filter::parse("type:flash", &Settings::default()).unwrap(), 1);
+        wait(&mut cache, &ctx);
+        assert!(!cache.filtered_sends.loaded && !cache.filtered_sends.loading);
+        cache.request(Data::FilteredSends);
+        wait(&mut cache, &ctx);
+        assert!(cache.filtered_sends.loaded);
+
+        // Same filter and climber, nothing to reload until sends change
+        cache.filter(&Filter::parse("type:flash", &Settings::default()).unwrap(), 1);
+        assert!(!cache.filtered_sends.stale);
+        cache.change(&[Data::Sends], |_| async move { Ok(()) });
+        wait(&mut cache, &ctx);
+        assert!(cache.filtered_sends.stale && cache.filtered_routes.stale);
+    }
+
+    #[test]
+    fn failed_changes_are_kept_for_the_header() {
+        let (mut cache, _rt) = cache();
+        let ctx = egui::Context::default();
+        cache.poll(&ctx);
+        cache.change(&[Data::Routes], |db| async move { db.remove_grade(1).await.and(Err(DbErr::Custom("no such grade".to_string()))) });
+        wait(&mut cache, &ctx);
+        assert_eq!(cache.change_error.as_deref(), Some("Custom Error: no such grade"));
+        assert!(!cache.routes.stale);
+    }
+}
@@ -0,0 +1,100 @@
+use eframe::egui::{self, Align2, Color32, FontId, Pos2, Rect, Sense, Shape, Stroke, Vec2};
+
+// Simple line charts drawn straight onto an egui painter
+
+pub struct Series { // A named line on a chart
+    pub name: String,
+    pub color: Color32,
+    pub points: Vec<(f64, f64)>, // (x, y), sorted by x
+}
+
+pub struct Band { // A shaded area on a chart, e.g. an uncertainty range
+    pub name: String,
+    pub color: Color32,
+    pub points: Vec<(f64, f64, f64)>, // (x, low, high), sorted by x
+}
+
+const CHART_HEIGHT: f32 = 260.0;
+const MARGIN_LEFT: f32 = 60.0; // Room for the y axis labels
+const MARGIN_BOTTOM: f32 = 24.0; // Room for the x axis labels
+const X_TICKS: i32 = 5;
+const MAX_Y_TICKS: f64 = 8.0;
+
+pub fn line_chart(ui: &mut egui::Ui, series: &[Series], band: Option<&Band>, x_label: impl Fn(f64) -> String, y_label: impl Fn(f64) -> String) {
+    // Draw the series and band with shared axes, followed by a legend
+    let xs = series.iter().flat_map(|line| line.points.iter().map(|(x, _)| *x))
+        .chain(band.iter().flat_map(|band| band.points.iter().map(|(x, _, _)| *x)));
+    let ys = series.iter().flat_map(|line| line.points.iter().map(|(_, y)| *y))
+        .chain(band.iter().flat_map(|band| band.points.iter().flat_map(|(_, low, high)| [*low, *high])));
+    let Some((x_min, x_max)) = bounds(xs) else {
+        ui.label("Nothing to plot yet.");
+        return;
+    };
+    let (y_min, y_max) = bounds(ys).unwrap_or((0.0, 1.0));
+    let (x_min, x_max) = if x_max - x_min < 1.0 { (x_min - 1.0, x_max + 1.0) } else { (x_min, x_max) };
+    let (y_min, y_max) = (y_min.floor() - 0.5, y_max.ceil() + 0.5);
+
+    let (response, painter) = ui.allocate_painter(Vec2::new(ui.available_width(), CHART_HEIGHT), Sense::hover());
+    let plot = Rect::from_min_max(
+        response.rect.min + Vec2::new(MARGIN_LEFT, 0.0),
+        response.rect.max - Vec2::new(0.0, MARGIN_BOTTOM),
+    );
+    #[allow(clippy::cast_possible_truncation)] // Screen coordinates are f32
+    let to_screen = |x: f64, y: f64| Pos2::new(
+        plot.left() + ((x - x_min) / (x_max - x_min)) as f32 * plot.width(),
+        plot.bottom() - ((y - y_min) / (y_max - y_min)) as f32 * plot.height(),
+    );
+    let text_color = ui.visuals().text_color();
+    let grid = Stroke::new(0.5, ui.visuals().weak_text_color());
+
+    // Axes and grid lines
+    painter.rect_stroke(plot, 0.0, Stroke::new(1.0, text_color));
+    let y_step = ((y_max - y_min) / MAX_Y_TICKS).ceil().max(1.0);
+    let mut y = y_min.ceil();
+    while y <= y_max {
+        let left = to_screen(x_min, y);
+        painter.line_segment([left, to_screen(x_max, y)], grid);
+        painter.text(left - Vec2::new(6.0, 0.0), Align2::RIGHT_CENTER, y_label(y), FontId::proportional(11.0), text_color);
+        y += y_step;
+    }
+    for tick in 0..=X_TICKS {
+        let x = x_min + (x_max - x_min) * f64::from(tick) / f64::from(X_TICKS);
+        let bottom = to_screen(x, y_min);
+        painter.line_segment([bottom, to_screen(x, y_max)], grid);
+        painter.text(bottom + Vec2::new(0.0, 4.0), Align2::CENTER_TOP, x_label(x), FontId::proportional(11.0), text_color);
+    }
+
+    // Band first so lines draw over it, one quad per segment keeps every polygon convex
+    if let Some(band) = band {
+        for pair in band.points.windows(2) {
+            let (x0, low0, high0) = pair[0];
+            let (x1, low1, high1) = pair[1];
+            let quad = vec![to_screen(x0, low0), to_screen(x1, low1), to_screen(x1, high1), to_screen(x0, high0)];
+            painter.add(Shape::convex_polygon(quad, band.color, Stroke::NONE));
+        }
+    }
+    for line in series {
+        let points: Vec<Pos2> = line.points.iter().map(|(x, y)| to_screen(*x, *y)).collect();
+        if points.len() == 1 {
+            painter.circle_filled(points[0], 3.0, line.color);
+        } else {
+            painter.add(Shape::line(points, Stroke::new(2.0, line.color)));
+        }
+    }
+
+    // Legend
+    ui.horizontal_wrapped(|ui| {
+        for (name, color) in series.iter().map(|line| (&line.name, line.color)).chain(band.map(|band| (&band.name, band.color))) {
+            ui.colored_label(color, "■");
+            ui.label(name.as_str());
+            ui.add_space(10.0);
+        }
+    });
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    values.filter(|value| value.is_finite()).fold(None, |bounds, value| match bounds {
+        None => Some((value, value)),
+        Some((low, high)) => Some((low.min(value), high.max(value))),
+    })
+}
@@ -1,5 +0,0 @@
-//! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.15
-
-pub use super::grades::Entity as Grades;
-pub use super::routes::Entity as Routes;
-pub use super::sends::Entity as Sends;